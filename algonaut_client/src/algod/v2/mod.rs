@@ -3,10 +3,13 @@ use crate::extensions::reqwest::{to_header_map, ResponseExt};
 use crate::Headers;
 use algonaut_core::{Address, Round};
 use algonaut_model::algod::v2::{
-    Account, Application, Asset, Block, Catchup, CompiledTeal, DryrunRequest, DryrunResponse,
-    GenesisBlock, KeyRegistration, NodeStatus, PendingTransaction, PendingTransactions, Supply,
-    TransactionParams, TransactionResponse, Version,
+    Account, AccountApplicationInformation, AccountAssetInformation, Application, ApplicationBox,
+    Asset, Block, BlockHash, BoxesResponse, Catchup, CompiledTeal, DisassembledTeal, DryrunRequest,
+    DryrunResponse, GenesisBlock, HashType, KeyRegistration, LightBlockHeaderProof, NodeStatus,
+    ParticipationKey, ParticipationKeyId, PendingTransaction, PendingTransactions, Supply,
+    SyncRound, TransactionParams, TransactionProof, TransactionResponse, Version,
 };
+use data_encoding::BASE64;
 use reqwest::header::HeaderMap;
 use reqwest::Url;
 
@@ -85,6 +88,50 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn account_asset_information(
+        &self,
+        address: &str,
+        asset_id: u64,
+    ) -> Result<AccountAssetInformation, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!(
+                "{}v2/accounts/{}/assets/{}",
+                self.url, address, asset_id
+            ))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn account_application_information(
+        &self,
+        address: &str,
+        application_id: u64,
+    ) -> Result<AccountApplicationInformation, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!(
+                "{}v2/accounts/{}/applications/{}",
+                self.url, address, application_id
+            ))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     pub async fn pending_transactions_for(
         &self,
         address: &str,
@@ -122,6 +169,42 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn application_box(
+        &self,
+        id: u64,
+        name: &[u8],
+    ) -> Result<ApplicationBox, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/applications/{}/box", self.url, id))
+            .headers(self.headers.clone())
+            .query(&[("name", format!("b64:{}", BASE64.encode(name)))])
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn application_boxes(&self, id: u64, max: u64) -> Result<BoxesResponse, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/applications/{}/boxes", self.url, id))
+            .headers(self.headers.clone())
+            .query(&[("max", max.to_string())])
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     pub async fn asset_information(&self, id: u64) -> Result<Asset, ClientError> {
         let response = self
             .http_client
@@ -152,6 +235,66 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn block_hash(&self, round: Round) -> Result<BlockHash, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/blocks/{}/hash", self.url, round))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn transaction_proof(
+        &self,
+        round: Round,
+        txid: &str,
+        hash_type: HashType,
+    ) -> Result<TransactionProof, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!(
+                "{}v2/blocks/{}/transactions/{}/proof",
+                self.url, round, txid
+            ))
+            .headers(self.headers.clone())
+            .query(&[("hashtype", hash_type)])
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn light_block_header_proof(
+        &self,
+        round: Round,
+    ) -> Result<LightBlockHeaderProof, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!(
+                "{}v2/blocks/{}/lightheader/proof",
+                self.url, round
+            ))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     pub async fn start_catchup(&self, catchpoint: &str) -> Result<Catchup, ClientError> {
         let response = self
             .http_client
@@ -197,6 +340,47 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn sync_round(&self) -> Result<SyncRound, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/ledger/sync", self.url))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn set_sync_round(&self, round: Round) -> Result<(), ClientError> {
+        let _ = self
+            .http_client
+            .post(&format!("{}v2/ledger/sync/{}", self.url, round))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn unset_sync_round(&self) -> Result<(), ClientError> {
+        let _ = self
+            .http_client
+            .delete(&format!("{}v2/ledger/sync", self.url))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn register_participation_keys(
         &self,
         address: &Address,
@@ -221,6 +405,90 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn participation_keys(&self) -> Result<Vec<ParticipationKey>, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/participation", self.url))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn add_participation_key(
+        &self,
+        key_file: &[u8],
+    ) -> Result<ParticipationKeyId, ClientError> {
+        let response = self
+            .http_client
+            .post(&format!("{}v2/participation", self.url))
+            .headers(self.headers.clone())
+            .header("Content-Type", "application/msgpack")
+            .body(key_file.to_vec())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn participation_key(&self, id: &str) -> Result<ParticipationKey, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/participation/{}", self.url, id))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn append_participation_keys(
+        &self,
+        id: &str,
+        keys: &[u8],
+    ) -> Result<ParticipationKey, ClientError> {
+        let response = self
+            .http_client
+            .post(&format!("{}v2/participation/{}", self.url, id))
+            .headers(self.headers.clone())
+            .header("Content-Type", "application/msgpack")
+            .body(keys.to_vec())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn delete_participation_key(&self, id: &str) -> Result<(), ClientError> {
+        let _ = self
+            .http_client
+            .delete(&format!("{}v2/participation/{}", self.url, id))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn shutdown(&self, timeout: usize) -> Result<(), ClientError> {
         self.http_client
             .post(&format!("{}v2/shutdown", self.url))
@@ -286,6 +554,26 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn disassemble_teal(
+        &self,
+        program: Vec<u8>,
+    ) -> Result<DisassembledTeal, ClientError> {
+        let response = self
+            .http_client
+            .post(&format!("{}v2/teal/disassemble", self.url))
+            .headers(self.headers.clone())
+            .header("Content-Type", "application/x-binary")
+            .body(program)
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     pub async fn dryrun_teal(&self, req: &DryrunRequest) -> Result<DryrunResponse, ClientError> {
         let response = self
             .http_client
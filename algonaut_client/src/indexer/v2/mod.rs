@@ -3,12 +3,16 @@ use crate::extensions::reqwest::{to_header_map, ResponseExt};
 use crate::Headers;
 use algonaut_core::{Address, Round};
 use algonaut_model::indexer::v2::{
-    AccountInfoResponse, AccountResponse, AccountTransactionResponse, ApplicationInfoResponse,
-    ApplicationResponse, AssetResponse, AssetTransactionResponse, AssetsInfoResponse,
-    BalancesResponse, Block, QueryAccount, QueryAccountInfo, QueryAccountTransaction,
-    QueryApplicationInfo, QueryApplications, QueryAssetTransaction, QueryAssets, QueryAssetsInfo,
-    QueryBalances, QueryTransaction, TransactionResponse,
+    AccountInfoResponse, AccountResponse, AccountTransactionResponse, ApplicationBox,
+    ApplicationInfoResponse, ApplicationLocalStatesResponse, ApplicationLogsResponse,
+    ApplicationResponse, AssetHoldingsResponse, AssetResponse, AssetTransactionResponse,
+    AssetsInfoResponse, BalancesResponse, Block, BlockHeadersResponse, BoxesResponse, QueryAccount,
+    QueryAccountApplications, QueryAccountAssets, QueryAccountInfo, QueryAccountTransaction,
+    QueryApplicationBoxes, QueryApplicationInfo, QueryApplicationLogs, QueryApplications,
+    QueryAssetTransaction, QueryAssets, QueryAssetsInfo, QueryBalances, QueryBlockHeaders,
+    QueryTransaction, TransactionResponse,
 };
+use data_encoding::BASE64;
 use reqwest::header::HeaderMap;
 use reqwest::Url;
 
@@ -105,6 +109,99 @@ impl Client {
         Ok(response)
     }
 
+    /// Lookup assets created by an account.
+    pub async fn account_created_assets(
+        &self,
+        address: &Address,
+        query: &QueryAccountAssets,
+    ) -> Result<AssetResponse, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!(
+                "{}v2/accounts/{}/created-assets",
+                self.url, address
+            ))
+            .headers(self.headers.clone())
+            .query(query)
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Lookup applications created by an account.
+    pub async fn account_created_applications(
+        &self,
+        address: &Address,
+        query: &QueryAccountApplications,
+    ) -> Result<ApplicationResponse, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!(
+                "{}v2/accounts/{}/created-applications",
+                self.url, address
+            ))
+            .headers(self.headers.clone())
+            .query(query)
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Lookup the asset holdings of an account.
+    pub async fn account_assets(
+        &self,
+        address: &Address,
+        query: &QueryAccountAssets,
+    ) -> Result<AssetHoldingsResponse, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/accounts/{}/assets", self.url, address))
+            .headers(self.headers.clone())
+            .query(query)
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Lookup the application local states of an account.
+    pub async fn account_apps_local_state(
+        &self,
+        address: &Address,
+        query: &QueryAccountApplications,
+    ) -> Result<ApplicationLocalStatesResponse, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!(
+                "{}v2/accounts/{}/apps-local-state",
+                self.url, address
+            ))
+            .headers(self.headers.clone())
+            .query(query)
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     /// Search for applications
     pub async fn applications(
         &self,
@@ -146,6 +243,69 @@ impl Client {
         Ok(response)
     }
 
+    /// Lookup the logs of an application.
+    pub async fn application_logs(
+        &self,
+        id: u64,
+        query: &QueryApplicationLogs,
+    ) -> Result<ApplicationLogsResponse, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/applications/{}/logs", self.url, id))
+            .headers(self.headers.clone())
+            .query(query)
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Lookup the box names of an application.
+    pub async fn application_boxes(
+        &self,
+        id: u64,
+        query: &QueryApplicationBoxes,
+    ) -> Result<BoxesResponse, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/applications/{}/boxes", self.url, id))
+            .headers(self.headers.clone())
+            .query(query)
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Lookup a box of an application by its name.
+    pub async fn application_box(
+        &self,
+        id: u64,
+        name: &[u8],
+    ) -> Result<ApplicationBox, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/applications/{}/box", self.url, id))
+            .headers(self.headers.clone())
+            .query(&[("name", format!("b64:{}", BASE64.encode(name)))])
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     /// Search for assets.
     pub async fn assets(&self, query: &QueryAssets) -> Result<AssetResponse, ClientError> {
         let response = self
@@ -242,6 +402,26 @@ impl Client {
         Ok(response)
     }
 
+    /// Search for block headers.
+    pub async fn block_headers(
+        &self,
+        query: &QueryBlockHeaders,
+    ) -> Result<BlockHeadersResponse, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/block-headers", self.url))
+            .headers(self.headers.clone())
+            .query(query)
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     /// Search for transactions.
     pub async fn transactions(
         &self,
@@ -278,3 +458,63 @@ impl Client {
         Ok(response)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algonaut_model::indexer::v2::TransactionType;
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_transactions_query_serialization() {
+        let address = Address([1; 32]);
+        let query = QueryTransaction {
+            address: Some(address),
+            after_time: Some("2021-08-01T12:30:00Z".parse::<DateTime<Utc>>().unwrap()),
+            note_prefix: Some(b"algonaut".to_vec()),
+            tx_type: Some(TransactionType::AssetFreezeTransaction),
+            txid: Some("TXID".to_owned()),
+            ..QueryTransaction::default()
+        };
+
+        let request = reqwest::Client::new()
+            .get("http://localhost/v2/transactions")
+            .query(&query)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            Some(format!(
+                "address={}&after-time=2021-08-01T12%3A30%3A00Z&note-prefix=YWxnb25hdXQ%3D&tx-type=afrz&txid=TXID",
+                address
+            ))
+            .as_deref(),
+            request.url().query()
+        );
+    }
+
+    #[test]
+    fn test_block_headers_query_serialization() {
+        let query = QueryBlockHeaders {
+            proposers: Some(vec![Address([1; 32]), Address([2; 32])]),
+            min_round: Some(Round(10)),
+            ..QueryBlockHeaders::default()
+        };
+
+        let request = reqwest::Client::new()
+            .get("http://localhost/v2/block-headers")
+            .query(&query)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            Some(format!(
+                "min-round=10&proposers={}%2C{}",
+                Address([1; 32]),
+                Address([2; 32])
+            ))
+            .as_deref(),
+            request.url().query()
+        );
+    }
+}
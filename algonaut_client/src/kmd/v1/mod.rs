@@ -517,7 +517,7 @@ mod tests {
     #[test]
     fn test_create_wallet_request_successful_serialization() {
         let req = CreateWalletRequest {
-            master_derivation_key: MasterDerivationKey([0; 32]),
+            master_derivation_key: MasterDerivationKey::new([0; 32]),
             wallet_driver_name: "sqlite".into(),
             wallet_name: "testwallet".into(),
             wallet_password: "testpassword".into(),
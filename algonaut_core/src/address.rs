@@ -13,7 +13,7 @@ const CHECKSUM_LEN: usize = 4;
 const HASH_LEN: usize = 32;
 
 /// Public key address
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Address(pub [u8; HASH_LEN]);
 
 impl Address {
@@ -41,6 +41,17 @@ impl Address {
         }
     }
 
+    /// Address of the account of an application, which holds its funds and assets
+    /// and is used as sender of its inner transactions.
+    pub fn for_application(app_id: u64) -> Address {
+        let mut buf = b"appID".to_vec();
+        buf.extend_from_slice(&app_id.to_be_bytes());
+        let hashed = ChecksumAlg::digest(&buf);
+        let mut bytes = [0; HASH_LEN];
+        bytes.copy_from_slice(&hashed);
+        Address::new(bytes)
+    }
+
     pub fn as_public_key(&self) -> Ed25519PublicKey {
         Ed25519PublicKey(self.0)
     }
@@ -201,4 +212,12 @@ mod tests {
         let deserialized: Address = rmp_serde::from_slice(&bytes).unwrap();
         assert_eq!(deserialized, addr);
     }
+
+    #[test]
+    fn for_application() {
+        assert_eq!(
+            "PCYUFPA2ZTOYWTP43MX2MOX2OWAIAXUDNC2WFCXAGMRUZ3DYD6BWFDL5YM",
+            Address::for_application(77).to_string()
+        );
+    }
 }
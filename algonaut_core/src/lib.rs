@@ -1,6 +1,8 @@
+use algonaut_crypto::error::CryptoError;
+use algonaut_crypto::vrf::{VrfOutput, VrfProof};
 use algonaut_crypto::HashDigest;
 use algonaut_crypto::Signature;
-use algonaut_encoding::U8_32Visitor;
+use algonaut_encoding::{U8_32Visitor, U8_64Visitor};
 use data_encoding::BASE64;
 use derive_more::{Add, Display, Sub};
 use error::CoreError;
@@ -15,6 +17,7 @@ pub use address::Address;
 pub use address::MultisigAddress;
 pub use multisig::MultisigSignature;
 pub use multisig::MultisigSubsig;
+pub use multisig::MULTISIG_VERSION;
 
 mod address;
 mod error;
@@ -170,6 +173,49 @@ impl VrfPk {
     pub fn to_base64_str(self) -> String {
         BASE64.encode(&self.0)
     }
+
+    /// Verifies a VRF proof of `message` with this key, returning the VRF output (see [VrfProof::verify]).
+    pub fn verify(&self, proof: &VrfProof, message: &[u8]) -> Result<VrfOutput, CryptoError> {
+        proof.verify(&self.0, message)
+    }
+}
+
+/// State proof public key (the root of the Merkle tree of the state proof keys) used in key registration transactions
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct StateProofPk(pub [u8; 64]);
+
+impl Serialize for StateProofPk {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0[..])
+    }
+}
+
+impl<'de> Deserialize<'de> for StateProofPk {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(StateProofPk(deserializer.deserialize_bytes(U8_64Visitor)?))
+    }
+}
+
+impl Debug for StateProofPk {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_base64_str())
+    }
+}
+
+impl StateProofPk {
+    pub fn from_base64_str(base64_str: &str) -> Result<StateProofPk, CoreError> {
+        Ok(StateProofPk(base64_str_to_u8_array(base64_str)?))
+    }
+
+    pub fn to_base64_str(self) -> String {
+        BASE64.encode(&self.0)
+    }
 }
 
 #[derive(Eq, PartialEq, Clone)]
@@ -192,7 +238,9 @@ impl SignedLogic {
                 let pk = address.as_public_key();
                 pk.verify(&self.logic.bytes_to_sign(), sig)
             }
-            LogicSignature::DelegatedMultiSig(msig) => msig.verify(&self.logic.bytes_to_sign()),
+            LogicSignature::DelegatedMultiSig(msig) => {
+                msig.address() == address && msig.verify(&self.logic.bytes_to_sign())
+            }
         }
     }
 }
@@ -212,7 +260,7 @@ impl Debug for SignedLogic {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Default, Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct CompiledTealBytes(pub Vec<u8>);
 
 impl CompiledTealBytes {
@@ -1,3 +1,4 @@
+use crate::{Address, MultisigAddress};
 use algonaut_crypto::Ed25519PublicKey;
 use algonaut_crypto::Signature;
 use serde::{Deserialize, Serialize, Serializer};
@@ -28,6 +29,16 @@ impl MultisigSignature {
         self.verify_subsigs(message)
     }
 
+    /// The multisig address of the signature's keys, version and threshold.
+    pub fn address(&self) -> Address {
+        MultisigAddress {
+            version: self.version,
+            threshold: self.threshold,
+            public_keys: self.subsigs.iter().map(|s| s.key).collect(),
+        }
+        .address()
+    }
+
     /// Checks threshold subsigs are signed and that the signatures are valid.
     fn verify_subsigs(&self, message: &[u8]) -> bool {
         self.subsigs
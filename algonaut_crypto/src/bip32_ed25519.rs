@@ -0,0 +1,415 @@
+use crate::error::CryptoError;
+use crate::secret::Secret;
+use crate::Ed25519PublicKey;
+use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::scalar::Scalar;
+use hmac::{Hmac, Mac, NewMac};
+use sha2::{Digest, Sha256, Sha512};
+use std::convert::TryInto;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::str::FromStr;
+use zeroize::Zeroize;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Indexes from this value on derive hardened children.
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// BIP44 coin type registered for Algorand.
+pub const ALGORAND_COIN_TYPE: u32 = 283;
+
+/// How the left half of the child key is computed from the HMAC output.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DerivationScheme {
+    /// The original BIP32-Ed25519 scheme, keeping 28 bytes of the HMAC output. Used by Ledger.
+    Khovratovich,
+    /// ARC-52 default, keeping 256 - 9 bits of the HMAC output, which allows deeper paths.
+    Peikert,
+}
+
+impl DerivationScheme {
+    /// Number of most significant bits of `zL` cleared before adding it to the parent key.
+    fn truncated_bits(&self) -> usize {
+        match self {
+            DerivationScheme::Khovratovich => 32,
+            DerivationScheme::Peikert => 9,
+        }
+    }
+}
+
+/// A BIP32 derivation path, e.g. m/44'/283'/0'/0/0
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DerivationPath(pub Vec<u32>);
+
+impl DerivationPath {
+    /// The path of the account's nth address used by Algorand wallets: m/44'/283'/account'/0/index
+    pub fn algorand(account: u32, index: u32) -> DerivationPath {
+        DerivationPath(vec![
+            44 + HARDENED_OFFSET,
+            ALGORAND_COIN_TYPE + HARDENED_OFFSET,
+            account + HARDENED_OFFSET,
+            0,
+            index,
+        ])
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(CryptoError::InvalidDerivationPath);
+        }
+        parts
+            .map(|part| {
+                let (index, offset) = match part.strip_suffix('\'') {
+                    Some(index) => (index, HARDENED_OFFSET),
+                    None => (part, 0),
+                };
+                match index.parse::<u32>() {
+                    Ok(index) if index < HARDENED_OFFSET => Ok(index + offset),
+                    _ => Err(CryptoError::InvalidDerivationPath),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(DerivationPath)
+    }
+}
+
+impl Display for DerivationPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if *index >= HARDENED_OFFSET {
+                write!(f, "/{}'", index - HARDENED_OFFSET)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A BIP32-Ed25519 extended private key: the scalar `kL`, the nonce `kR` and the chain code.
+/// `kL || kR` is an expanded ed25519 secret key, which can be used to sign directly.
+pub struct ExtendedPrivateKey {
+    kl: [u8; 32],
+    kr: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedPrivateKey {
+    /// Creates the root key from a BIP39 seed, as specified in ARC-52.
+    pub fn from_seed(seed: &[u8]) -> ExtendedPrivateKey {
+        let mut k: [u8; 64] = Sha512::digest(seed)[..].try_into().unwrap();
+        // The third highest bit of the last byte of kL has to be zero
+        while k[31] & 0b0010_0000 != 0 {
+            let mut mac = HmacSha512::new_from_slice(&k[..32]).expect("HMAC accepts all key sizes");
+            mac.update(&k[32..]);
+            k = mac.finalize().into_bytes()[..].try_into().unwrap();
+        }
+        let mut kl: [u8; 32] = k[..32].try_into().unwrap();
+        kl[0] &= 0b1111_1000;
+        kl[31] &= 0b0111_1111;
+        kl[31] |= 0b0100_0000;
+
+        let key = ExtendedPrivateKey {
+            kl,
+            kr: k[32..].try_into().unwrap(),
+            chain_code: Sha256::new().chain([0x01]).chain(seed).finalize().into(),
+        };
+        kl.zeroize();
+        k.zeroize();
+        key
+    }
+
+    /// Creates the root key from a BIP39 mnemonic and an optional passphrase (empty if not used).
+    pub fn from_mnemonic(
+        mnemonic: &str,
+        passphrase: &str,
+    ) -> Result<ExtendedPrivateKey, CryptoError> {
+        Ok(Self::from_seed(
+            crate::bip39::to_seed(mnemonic, passphrase)?.expose_secret(),
+        ))
+    }
+
+    /// Derives the child key at `index`, hardened if index >= [HARDENED_OFFSET].
+    pub fn derive_child(
+        &self,
+        index: u32,
+        scheme: DerivationScheme,
+    ) -> Result<ExtendedPrivateKey, CryptoError> {
+        let (z, chain_code) = if index < HARDENED_OFFSET {
+            let public_key = self.public_key();
+            hmac_pair(&self.chain_code, 0x02, 0x03, &[&public_key.0[..]], index)
+        } else {
+            hmac_pair(&self.chain_code, 0x00, 0x01, &[&self.kl, &self.kr], index)
+        };
+
+        let zl = truncate(z[..32].try_into().unwrap(), scheme.truncated_bits());
+        let kl = add_256(&mul_8(&zl), &self.kl);
+        // kL has to stay below 2^255 to be a valid scalar
+        if kl[31] & 0b1000_0000 != 0 {
+            return Err(CryptoError::InvalidDerivedKey);
+        }
+        let kr = add_256(z[32..].try_into().unwrap(), &self.kr);
+
+        Ok(ExtendedPrivateKey {
+            kl,
+            kr,
+            chain_code: chain_code[32..].try_into().unwrap(),
+        })
+    }
+
+    /// Derives the key at `path`, starting from this key.
+    pub fn derive_path(
+        &self,
+        path: &DerivationPath,
+        scheme: DerivationScheme,
+    ) -> Result<ExtendedPrivateKey, CryptoError> {
+        let mut key = ExtendedPrivateKey {
+            kl: self.kl,
+            kr: self.kr,
+            chain_code: self.chain_code,
+        };
+        for index in &path.0 {
+            key = key.derive_child(*index, scheme)?;
+        }
+        Ok(key)
+    }
+
+    /// The ed25519 public key: `kL` multiplied by the base point, without clamping.
+    pub fn public_key(&self) -> Ed25519PublicKey {
+        Ed25519PublicKey(
+            (&Scalar::from_bits(self.kl) * &ED25519_BASEPOINT_TABLE)
+                .compress()
+                .to_bytes(),
+        )
+    }
+
+    /// The expanded ed25519 secret key `kL || kR`.
+    pub fn expanded_secret_key(&self) -> Secret<[u8; 64]> {
+        let mut bytes = Secret::new([0; 64]);
+        bytes.expose_secret_mut()[..32].copy_from_slice(&self.kl);
+        bytes.expose_secret_mut()[32..].copy_from_slice(&self.kr);
+        bytes
+    }
+
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.kl.zeroize();
+        self.kr.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+impl Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "ExtendedPrivateKey({:?})", self.public_key())
+    }
+}
+
+/// Derives the public key of the non-hardened child at `index` from the parent's public key and chain code,
+/// without knowledge of the private key.
+pub fn derive_public_child(
+    public_key: &Ed25519PublicKey,
+    chain_code: &[u8; 32],
+    index: u32,
+    scheme: DerivationScheme,
+) -> Result<(Ed25519PublicKey, [u8; 32]), CryptoError> {
+    if index >= HARDENED_OFFSET {
+        return Err(CryptoError::InvalidDerivationPath);
+    }
+    let (z, child_chain_code) = hmac_pair(chain_code, 0x02, 0x03, &[&public_key.0[..]], index);
+    let zl = truncate(z[..32].try_into().unwrap(), scheme.truncated_bits());
+    let parent = CompressedEdwardsY(public_key.0)
+        .decompress()
+        .ok_or(CryptoError::InvalidKeyLength)?;
+    let child = &Scalar::from_bits(mul_8(&zl)) * &ED25519_BASEPOINT_TABLE + parent;
+    Ok((
+        Ed25519PublicKey(child.compress().to_bytes()),
+        child_chain_code[32..].try_into().unwrap(),
+    ))
+}
+
+/// HMAC-SHA512 of `prefix || data || index` (index little endian), keyed with the chain code,
+/// with the prefix for `z` and the one for the child chain code.
+fn hmac_pair(
+    chain_code: &[u8; 32],
+    z_prefix: u8,
+    chain_code_prefix: u8,
+    data: &[&[u8]],
+    index: u32,
+) -> ([u8; 64], [u8; 64]) {
+    let hmac = |prefix: u8| -> [u8; 64] {
+        let mut mac = HmacSha512::new_from_slice(chain_code).expect("HMAC accepts all key sizes");
+        mac.update(&[prefix]);
+        for d in data {
+            mac.update(d);
+        }
+        mac.update(&index.to_le_bytes());
+        mac.finalize().into_bytes()[..].try_into().unwrap()
+    };
+    (hmac(z_prefix), hmac(chain_code_prefix))
+}
+
+/// Clears the `bits` most significant bits of the little endian number.
+fn truncate(mut bytes: [u8; 32], bits: usize) -> [u8; 32] {
+    let mut remaining = bits;
+    for byte in bytes.iter_mut().rev() {
+        if remaining >= 8 {
+            *byte = 0;
+            remaining -= 8;
+        } else {
+            *byte &= 0xff >> remaining;
+            break;
+        }
+    }
+    bytes
+}
+
+/// Multiplies the little endian number by 8. The truncated input guarantees that there's no overflow.
+fn mul_8(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0; 32];
+    let mut carry = 0;
+    for (o, b) in out.iter_mut().zip(bytes.iter()) {
+        *o = (b << 3) | carry;
+        carry = b >> 5;
+    }
+    out
+}
+
+/// Adds two little endian numbers, modulo 2^256.
+fn add_256(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0; 32];
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let sum = a[i] as u16 + b[i] as u16 + carry;
+        out[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_encoding::HEXLOWER;
+
+    fn root() -> ExtendedPrivateKey {
+        let mnemonic = "salon zoo engage submit smile frost later decide wing sight chaos renew lizard rely canal coral scene hobby scare step bus leaf tobacco slice";
+        ExtendedPrivateKey::from_mnemonic(mnemonic, "").unwrap()
+    }
+
+    /// Test vectors of the ARC-52 reference implementation
+    #[test]
+    fn test_arc52_vectors() {
+        let root = root();
+        assert_eq!(
+            "a8ba80028922d9fcfa055c78aede55b5c575bcd8d5a53168edf45f36d9ec8f46\
+             94592b4bc892907583e22669ecdf1b0409a9f3bd5549f2dd751b51360909cd05\
+             796b9206ec30e142e94b790a98805bf999042b55046963174ee6cee2d0375946",
+            HEXLOWER.encode(
+                &[
+                    &root.expanded_secret_key().expose_secret()[..],
+                    &root.chain_code()
+                ]
+                .concat()
+            )
+        );
+
+        let key = root
+            .derive_path(
+                &"m/44'/283'/0'/0/0".parse().unwrap(),
+                DerivationScheme::Peikert,
+            )
+            .unwrap();
+        assert_eq!(
+            "7bda7ac12627b2c259f1df6875d30c10b35f55b33ad2cc8ea2736eaa3ebcfab9",
+            HEXLOWER.encode(&key.public_key().0)
+        );
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let path: DerivationPath = "m/44'/283'/1'/0/7".parse().unwrap();
+        assert_eq!(DerivationPath::algorand(1, 7), path);
+        assert_eq!("m/44'/283'/1'/0/7", path.to_string());
+
+        assert!("44'/283'".parse::<DerivationPath>().is_err());
+        assert!("m/44''".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648".parse::<DerivationPath>().is_err());
+    }
+
+    #[test]
+    fn test_root_key_is_clamped() {
+        let key = root();
+        assert_eq!(0, key.kl[0] & 0b0000_0111);
+        assert_eq!(0b0100_0000, key.kl[31] & 0b1110_0000);
+    }
+
+    #[test]
+    fn test_public_derivation_matches_private_derivation() {
+        for scheme in &[DerivationScheme::Khovratovich, DerivationScheme::Peikert] {
+            let account = root()
+                .derive_path(&"m/44'/283'/0'".parse().unwrap(), *scheme)
+                .unwrap();
+            let change = account.derive_child(0, *scheme).unwrap();
+            let (public_change, change_chain_code) =
+                derive_public_child(&account.public_key(), &account.chain_code(), 0, *scheme)
+                    .unwrap();
+            assert_eq!(change.public_key(), public_change);
+            assert_eq!(change.chain_code(), change_chain_code);
+
+            for index in 0..5 {
+                let key = change.derive_child(index, *scheme).unwrap();
+                let (public_key, _) =
+                    derive_public_child(&public_change, &change_chain_code, index, *scheme)
+                        .unwrap();
+                assert_eq!(key.public_key(), public_key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_derived_keys_are_distinct() {
+        let scheme = DerivationScheme::Peikert;
+        let k0 = root()
+            .derive_path(&DerivationPath::algorand(0, 0), scheme)
+            .unwrap();
+        let k1 = root()
+            .derive_path(&DerivationPath::algorand(0, 1), scheme)
+            .unwrap();
+        let k1_khovratovich = root()
+            .derive_path(
+                &DerivationPath::algorand(0, 1),
+                DerivationScheme::Khovratovich,
+            )
+            .unwrap();
+        assert_ne!(k0.public_key(), k1.public_key());
+        assert_ne!(k1.public_key(), k1_khovratovich.public_key());
+    }
+
+    #[test]
+    fn test_signature_verifies() {
+        let key = root()
+            .derive_path(&DerivationPath::algorand(0, 0), DerivationScheme::Peikert)
+            .unwrap();
+        let expanded =
+            ed25519_dalek::ExpandedSecretKey::from_bytes(key.expanded_secret_key().expose_secret())
+                .unwrap();
+        let public_key = ed25519_dalek::PublicKey::from_bytes(&key.public_key().0).unwrap();
+        let signature = expanded.sign(b"message", &public_key);
+        assert!(key
+            .public_key()
+            .verify(b"message", &crate::Signature(signature.to_bytes())));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::error::CryptoError;
+use crate::mnemonic::wordlist::WORDLIST;
+use crate::secret::Secret;
+use hmac::Hmac;
+use sha2::{Digest, Sha256, Sha512};
+
+const BITS_PER_WORD: usize = 11;
+const PBKDF2_ROUNDS: u32 = 2048;
+const MNEMONIC_DELIM: &str = " ";
+
+/// Length of the seed generated from a mnemonic.
+pub const SEED_LEN_BYTES: usize = 64;
+
+/// Converts entropy into a BIP39 mnemonic. The entropy has to be between 16 and 32 bytes long,
+/// in steps of 4 bytes. 32 bytes of entropy produce a 24 words mnemonic.
+/// Each word represents 11 bits: the entropy is followed by a checksum of `entropy bits / 32` bits.
+pub fn from_entropy(entropy: &[u8]) -> Result<String, CryptoError> {
+    if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let checksum_bits = entropy.len() / 4;
+    let hash = Sha256::digest(entropy);
+    let mut bits: Vec<bool> = bits(entropy).collect();
+    bits.extend(self::bits(&hash).take(checksum_bits));
+
+    Ok(bits
+        .chunks(BITS_PER_WORD)
+        .map(|chunk| {
+            let index = chunk
+                .iter()
+                .fold(0usize, |acc, bit| (acc << 1) | *bit as usize);
+            *WORDLIST.get_index(index).expect("Word out of range")
+        })
+        .collect::<Vec<_>>()
+        .join(MNEMONIC_DELIM))
+}
+
+/// Converts a BIP39 mnemonic into the entropy used to create it.
+/// It returns an error if the number of words is unexpected, if one of the words is not found
+/// in the words list or if the checksum is incorrect.
+pub fn to_entropy(mnemonic: &str) -> Result<Vec<u8>, CryptoError> {
+    let words: Vec<&str> = mnemonic.split(MNEMONIC_DELIM).collect();
+    if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+        return Err(CryptoError::InvalidMnemonicLength);
+    }
+    let mut bits = Vec::with_capacity(words.len() * BITS_PER_WORD);
+    for word in words {
+        let (index, _) = WORDLIST
+            .get_full(word)
+            .ok_or(CryptoError::InvalidWordsInMnemonic)?;
+        bits.extend((0..BITS_PER_WORD).rev().map(|i| (index >> i) & 1 == 1));
+    }
+
+    let checksum_bits = bits.len() / 33;
+    let (entropy_bits, checksum) = bits.split_at(bits.len() - checksum_bits);
+    let entropy: Vec<u8> = entropy_bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, bit| (acc << 1) | *bit as u8))
+        .collect();
+    let hash = Sha256::digest(&entropy);
+    if !self::bits(&hash)
+        .take(checksum_bits)
+        .eq(checksum.iter().copied())
+    {
+        return Err(CryptoError::InvalidChecksum);
+    }
+    Ok(entropy)
+}
+
+/// Generates the BIP39 seed of a mnemonic, protected by an optional passphrase (empty if not used).
+/// The mnemonic is validated first.
+/// Note that the mnemonic and passphrase are not NFKD normalized: the passphrase should be ASCII.
+pub fn to_seed(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<Secret<[u8; SEED_LEN_BYTES]>, CryptoError> {
+    to_entropy(mnemonic)?;
+    let salt = Secret::new(format!("mnemonic{}", passphrase));
+    let mut seed = Secret::new([0; SEED_LEN_BYTES]);
+    pbkdf2::pbkdf2::<Hmac<Sha512>>(
+        mnemonic.as_bytes(),
+        salt.expose_secret().as_bytes(),
+        PBKDF2_ROUNDS,
+        seed.expose_secret_mut(),
+    );
+    Ok(seed)
+}
+
+// Bits of the bytes, most significant first
+fn bits(bytes: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_encoding::HEXLOWER;
+
+    #[test]
+    fn test_from_entropy() {
+        let mnemonic = from_entropy(&[0; 32]).unwrap();
+        let expected = [vec!["abandon"; 23], vec!["art"]].concat().join(" ");
+        assert_eq!(expected, mnemonic);
+
+        let mnemonic = from_entropy(&[0xff; 16]).unwrap();
+        let expected = [vec!["zoo"; 11], vec!["wrong"]].concat().join(" ");
+        assert_eq!(expected, mnemonic);
+    }
+
+    #[test]
+    fn test_to_entropy() {
+        for len in &[16, 20, 24, 28, 32] {
+            let entropy: Vec<u8> = (0..*len).map(|i| i as u8 * 7).collect();
+            let mnemonic = from_entropy(&entropy).unwrap();
+            assert_eq!(entropy, to_entropy(&mnemonic).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_invalid_checksum() {
+        let mnemonic = [vec!["abandon"; 23], vec!["zoo"]].concat().join(" ");
+        assert!(matches!(
+            to_entropy(&mnemonic),
+            Err(CryptoError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn test_to_seed() {
+        let mnemonic = [vec!["abandon"; 23], vec!["art"]].concat().join(" ");
+        let seed = to_seed(&mnemonic, "TREZOR").unwrap();
+        assert_eq!(
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+            HEXLOWER.encode(seed.expose_secret())
+        );
+    }
+}
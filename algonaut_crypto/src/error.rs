@@ -11,6 +11,35 @@ pub enum CryptoError {
     InvalidMnemonicLength,
     #[display(fmt = "Mnemonic contains invalid words.")]
     InvalidWordsInMnemonic,
+    #[display(
+        fmt = "Unknown word {} at position {}, did you mean one of {:?}?",
+        word,
+        index,
+        suggestions
+    )]
+    UnknownWord {
+        index: usize,
+        word: String,
+        suggestions: Vec<String>,
+    },
     #[display(fmt = "Invalid checksum.")]
     InvalidChecksum,
+    #[display(fmt = "Derivation path is invalid.")]
+    InvalidDerivationPath,
+    #[display(fmt = "Derived key is invalid.")]
+    InvalidDerivedKey,
+    #[display(fmt = "Share threshold must be between 1 and the number of shares.")]
+    InvalidShareThreshold,
+    #[display(
+        fmt = "Recovering the secret requires {} shares, got {}.",
+        threshold,
+        shares
+    )]
+    InsufficientShares { threshold: u8, shares: usize },
+    #[display(fmt = "Shares are invalid or don't belong to the same secret.")]
+    InvalidShare,
+    #[display(fmt = "VRF public key is invalid.")]
+    InvalidVrfPublicKey,
+    #[display(fmt = "VRF proof is invalid.")]
+    InvalidVrfProof,
 }
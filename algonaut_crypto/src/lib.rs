@@ -2,17 +2,34 @@ use std::fmt::{self, Formatter};
 
 use algonaut_encoding::{deserialize_bytes32, SignatureVisitor, U8_32Visitor};
 use data_encoding::{BASE32_NOPAD, BASE64};
+use error::CryptoError;
 use fmt::Debug;
+use secret::{Secret, SecretSeed};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Support for turning 32 byte keys into human-readable mnemonics and back
 pub mod mnemonic;
 
+/// BIP39 mnemonics and seeds
+pub mod bip39;
+
+/// Hierarchical deterministic key derivation (BIP32-Ed25519, ARC-52)
+pub mod bip32_ed25519;
+
+/// Wrappers for secret key material, zeroized on drop
+pub mod secret;
+
+/// Verification of Algorand's VRF proofs (ECVRF-ED25519-SHA512-Elligator2)
+pub mod vrf;
+
+/// Shamir's secret sharing of seeds and master derivation keys, with shares encoded as mnemonics
+pub mod shamir;
+
 ///
 pub mod error;
 
 /// A SHA512_256 hash
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Default, Eq, PartialEq)]
 pub struct HashDigest(pub [u8; 32]);
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -31,18 +48,91 @@ impl Ed25519PublicKey {
             Err(_) => return false,
         };
 
-        match public_key.verify(message, &signature) {
-            Ok(()) => true,
-            Err(_e) => {
-                println!("Signature verification failed");
-                false
-            }
-        }
+        public_key.verify(message, &signature).is_ok()
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
-pub struct MasterDerivationKey(pub [u8; 32]);
+/// Verifies a batch of signatures at once, which is considerably faster than verifying them one by one.
+/// `messages`, `signatures` and `public_keys` are matched by index.
+/// Returns true only if all the signatures are valid.
+pub fn verify_batch(
+    messages: &[&[u8]],
+    signatures: &[Signature],
+    public_keys: &[Ed25519PublicKey],
+) -> bool {
+    let public_keys = match public_keys
+        .iter()
+        .map(|key| ed25519_dalek::PublicKey::from_bytes(&key.0))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(it) => it,
+        Err(_) => return false,
+    };
+
+    let signatures = match signatures
+        .iter()
+        .map(|signature| ed25519_dalek::Signature::from_bytes(&signature.0))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(it) => it,
+        Err(_) => return false,
+    };
+
+    ed25519_dalek::verify_batch(messages, &signatures, &public_keys).is_ok()
+}
+
+/// The master derivation key of a kmd wallet, zeroized on drop.
+#[derive(Clone)]
+pub struct MasterDerivationKey(Secret<[u8; 32]>);
+
+impl MasterDerivationKey {
+    pub fn new(bytes: [u8; 32]) -> MasterDerivationKey {
+        MasterDerivationKey(Secret::new(bytes))
+    }
+
+    pub fn expose_secret(&self) -> &[u8; 32] {
+        self.0.expose_secret()
+    }
+
+    /// Uses the secret as key, e.g. the result of [mnemonic::to_key] for a wallet backup phrase.
+    pub fn from_secret(secret: Secret<[u8; 32]>) -> MasterDerivationKey {
+        MasterDerivationKey(secret)
+    }
+
+    /// Derives the ed25519 seed of the key kmd generates at `index` for a wallet with this master derivation key.
+    /// Like kmd, it skips the HKDF extract step: the key is expanded with HKDF-SHA512/256 directly,
+    /// with the index as a uvarint in a 10 byte buffer as info.
+    /// kmd generates keys with increasing indexes starting at 0, skipping keys which were already imported in the wallet.
+    pub fn derive_seed(&self, index: u64) -> SecretSeed {
+        // binary.PutUvarint into a buffer of binary.MaxVarintLen64 bytes
+        let mut info = [0u8; 10];
+        let mut rest = index;
+        let mut i = 0;
+        while rest >= 0x80 {
+            info[i] = (rest as u8) | 0x80;
+            rest >>= 7;
+            i += 1;
+        }
+        info[i] = rest as u8;
+
+        let mut seed = Secret::new([0; 32]);
+        hkdf::Hkdf::<sha2::Sha512Trunc256>::from_prk(self.expose_secret())
+            .expect("32 bytes is a valid PRK length")
+            .expand(&info, seed.expose_secret_mut())
+            .expect("32 bytes is a valid HKDF output length");
+        seed
+    }
+
+    /// Splits the key into `shares` shares, any `threshold` of which recover it (see [shamir::split]).
+    pub fn split(&self, threshold: u8, shares: u8) -> Result<Vec<shamir::Share>, CryptoError> {
+        shamir::split(self.expose_secret(), threshold, shares)
+    }
+
+    /// Recovers a key split with [MasterDerivationKey::split].
+    pub fn from_shares(shares: &[shamir::Share]) -> Result<MasterDerivationKey, CryptoError> {
+        Ok(MasterDerivationKey(shamir::combine(shares)?))
+    }
+}
 
 /// An Ed25519 Signature
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -124,7 +214,7 @@ impl Debug for Ed25519PublicKey {
 
 impl Debug for MasterDerivationKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", BASE32_NOPAD.encode(&self.0))
+        write!(f, "MasterDerivationKey([REDACTED])")
     }
 }
 
@@ -139,7 +229,14 @@ pub fn deserialize_mdk<'de, D>(deserializer: D) -> Result<MasterDerivationKey, D
 where
     D: Deserializer<'de>,
 {
-    Ok(MasterDerivationKey(deserialize_bytes32(deserializer)?))
+    Ok(MasterDerivationKey::new(deserialize_bytes32(deserializer)?))
+}
+
+pub fn serialize_mdk<S>(mdk: &MasterDerivationKey, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    mdk.expose_secret().serialize(serializer)
 }
 
 pub fn deserialize_public_keys<'de, D>(deserializer: D) -> Result<Vec<Ed25519PublicKey>, D::Error>
@@ -157,3 +254,28 @@ where
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_encoding::HEXLOWER;
+    use std::convert::TryInto;
+
+    #[test]
+    fn test_derive_seed_from_master_derivation_key() {
+        let mdk = MasterDerivationKey::new((0..32).collect::<Vec<u8>>().try_into().unwrap());
+        assert_eq!(
+            "9b8d02f44d562df1e59e0de3477e7d007f35a9dada3fed4f64c5180179da2a82",
+            HEXLOWER.encode(mdk.derive_seed(0).expose_secret())
+        );
+        assert_eq!(
+            "46cfef8984cd029ed8baa6e6baf3fba1f9d47af8215d17e64a4832f2f1681719",
+            HEXLOWER.encode(mdk.derive_seed(1).expose_secret())
+        );
+        // Multi-byte uvarint
+        assert_eq!(
+            "0e8209b248c433e1f0f75eb07258ac5ade00fda0eaaa50605df3e73037287d26",
+            HEXLOWER.encode(mdk.derive_seed(300).expose_secret())
+        );
+    }
+}
@@ -1,8 +1,12 @@
 use crate::error::CryptoError;
+use crate::secret::{Secret, SecretSeed};
 use sha2::Digest;
 use static_assertions::const_assert_eq;
 
-mod wordlist;
+pub(crate) mod wordlist;
+
+/// Correction of misspelled or abbreviated words and recovery of missing words
+pub mod recovery;
 
 const BITS_PER_WORD: usize = 11;
 #[allow(dead_code)]
@@ -11,7 +15,7 @@ const KEY_LEN_BYTES: usize = 32;
 const MNEM_LEN_WORDS: usize = 25; // includes checksum word
 #[allow(dead_code)]
 const PADDING_ZEROS: usize = BITS_PER_WORD - ((KEY_LEN_BYTES * 8) % BITS_PER_WORD);
-const MNEMONIC_DELIM: &str = " ";
+pub(crate) const MNEMONIC_DELIM: &str = " ";
 type ChecksumAlg = sha2::Sha512Trunc256;
 
 const_assert_eq!(
@@ -36,7 +40,7 @@ pub fn from_key(key: &[u8]) -> Result<String, CryptoError> {
 /// key used to create it. It returns an error if the passed mnemonic has
 /// an incorrect checksum, if the number of words is unexpected, or if one
 /// of the passed words is not found in the words list.
-pub fn to_key(string: &str) -> Result<[u8; KEY_LEN_BYTES], CryptoError> {
+pub fn to_key(string: &str) -> Result<SecretSeed, CryptoError> {
     let mut mnemonic: Vec<&str> = string.split(MNEMONIC_DELIM).collect();
     if mnemonic.len() != MNEM_LEN_WORDS {
         return Err(CryptoError::InvalidMnemonicLength);
@@ -49,27 +53,28 @@ pub fn to_key(string: &str) -> Result<[u8; KEY_LEN_BYTES], CryptoError> {
             .ok_or(CryptoError::InvalidWordsInMnemonic)?;
         nums.push(n.0 as u32);
     }
-    let mut bytes = to_byte_array(&nums);
-    if bytes.len() != KEY_LEN_BYTES + 1 {
+    let mut bytes = Secret::new(to_byte_array(&nums));
+    if bytes.expose_secret().len() != KEY_LEN_BYTES + 1 {
         return Err(CryptoError::InvalidKeyLength);
     }
-    let _ = bytes.pop();
-    if check_word != checksum(&bytes) {
+    let _ = bytes.expose_secret_mut().pop();
+    if check_word != checksum(bytes.expose_secret()) {
         return Err(CryptoError::InvalidChecksum);
     }
-    let mut key = [0; KEY_LEN_BYTES];
-    key.copy_from_slice(&bytes);
+    let mut key = SecretSeed::new([0; KEY_LEN_BYTES]);
+    key.expose_secret_mut()
+        .copy_from_slice(bytes.expose_secret());
     Ok(key)
 }
 
 // Returns a word corresponding to the 11 bit checksum of the data
-fn checksum(data: &[u8]) -> &str {
+pub(crate) fn checksum(data: &[u8]) -> &str {
     let d = ChecksumAlg::digest(data);
     get_word(to_u11_array(&d[0..2])[0])
 }
 
 // Assumes little-endian
-fn to_u11_array(bytes: &[u8]) -> Vec<u32> {
+pub(crate) fn to_u11_array(bytes: &[u8]) -> Vec<u32> {
     let mut buf = 0u32;
     let mut bit_count = 0;
     let mut out = Vec::with_capacity((bytes.len() * 8 + BITS_PER_WORD - 1) / BITS_PER_WORD);
@@ -89,7 +94,7 @@ fn to_u11_array(bytes: &[u8]) -> Vec<u32> {
 }
 
 // takes an array of 11 byte numbers and converts them to 8 bit numbers
-fn to_byte_array(nums: &[u32]) -> Vec<u8> {
+pub(crate) fn to_byte_array(nums: &[u32]) -> Vec<u8> {
     let mut buf = 0;
     let mut bit_count = 0;
     let mut out = Vec::with_capacity((nums.len() * BITS_PER_WORD + 8 - 1) / 8);
@@ -109,7 +114,7 @@ fn to_byte_array(nums: &[u32]) -> Vec<u8> {
 }
 
 // Gets the word corresponding to the 11 bit number from the word list
-fn get_word(i: u32) -> &'static str {
+pub(crate) fn get_word(i: u32) -> &'static str {
     wordlist::WORDLIST
         .get_index(i as usize)
         .expect("Word out of range")
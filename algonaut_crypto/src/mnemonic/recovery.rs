@@ -0,0 +1,207 @@
+use super::{from_key, to_key, wordlist::WORDLIST, MNEMONIC_DELIM, MNEM_LEN_WORDS};
+use crate::error::CryptoError;
+
+/// Number of letters which identify a word of the word list.
+pub const UNIQUE_PREFIX_LEN: usize = 4;
+
+/// Placeholder for a missing or unreadable word, see [recover_missing_word].
+pub const MISSING_WORD: &str = "?";
+
+/// Max Levenshtein distance of the words returned by [suggestions].
+pub const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Returns the word of the word list matching `word`, either exactly or by its first
+/// [UNIQUE_PREFIX_LEN] letters, which are unique in the word list. Letters after the prefix are ignored,
+/// so damaged endings can be read.
+pub fn expand_word(word: &str) -> Option<&'static str> {
+    let word = word.to_lowercase();
+    if let Some(w) = WORDLIST.get(word.as_str()) {
+        return Some(w);
+    }
+    let prefix = word.get(..UNIQUE_PREFIX_LEN)?;
+    WORDLIST.iter().find(|w| w.starts_with(prefix)).copied()
+}
+
+/// Words of the word list within [MAX_SUGGESTION_DISTANCE] edits of `word`, closest first.
+pub fn suggestions(word: &str) -> Vec<&'static str> {
+    let word = word.to_lowercase();
+    let mut suggestions: Vec<(usize, &'static str)> = WORDLIST
+        .iter()
+        .map(|w| (levenshtein(&word, w), *w))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .collect();
+    suggestions.sort();
+    suggestions.into_iter().map(|(_, w)| w).collect()
+}
+
+/// Replaces abbreviated words with the full words of the word list (see [expand_word]).
+/// Returns [CryptoError::UnknownWord] with suggestions for the first word which can't be matched.
+/// Note that the checksum is not validated: use [super::to_key] on the result.
+pub fn normalize(mnemonic: &str) -> Result<String, CryptoError> {
+    Ok(expand_words(mnemonic)?.join(MNEMONIC_DELIM))
+}
+
+/// Recovers a mnemonic with a single missing word, by trying all the words of the word list
+/// and keeping the mnemonics with a valid checksum.
+///
+/// Mark a word which is unreadable with [MISSING_WORD]. If the mnemonic has one word less
+/// and no marker, the position of the missing word is unknown and all positions are tried.
+/// Abbreviated words are expanded (see [expand_word]).
+///
+/// The checksum word doesn't always identify the missing word, so there can be more than one candidate:
+/// check which one corresponds to the expected address.
+pub fn recover_missing_word(mnemonic: &str) -> Result<Vec<String>, CryptoError> {
+    let mut words: Vec<Option<&'static str>> = vec![];
+    for (index, word) in mnemonic.split_whitespace().enumerate() {
+        if word == MISSING_WORD {
+            words.push(None);
+        } else {
+            words.push(Some(expand(index, word)?));
+        }
+    }
+
+    let missing: Vec<usize> = (0..words.len()).filter(|i| words[*i].is_none()).collect();
+    let positions: Vec<usize> = match (words.len(), missing.as_slice()) {
+        (MNEM_LEN_WORDS, [index]) => vec![*index],
+        (len, []) if len == MNEM_LEN_WORDS - 1 => {
+            words.push(None);
+            (0..MNEM_LEN_WORDS).collect()
+        }
+        _ => return Err(CryptoError::InvalidMnemonicLength),
+    };
+    // Position of the missing word in `words`, which is moved to each position to try
+    let gap = missing.first().copied().unwrap_or(MNEM_LEN_WORDS - 1);
+
+    let mut candidates = vec![];
+    for position in positions {
+        let mut words = words.clone();
+        let gap_word = words.remove(gap);
+        words.insert(position, gap_word);
+        for word in WORDLIST.iter() {
+            words[position] = Some(word);
+            let candidate = words
+                .iter()
+                .map(|w| w.expect("Only the gap is empty"))
+                .collect::<Vec<_>>()
+                .join(MNEMONIC_DELIM);
+            // The last data word has padding bits, which have to be zero in the canonical mnemonic
+            if let Ok(key) = to_key(&candidate) {
+                if from_key(key.expose_secret())? == candidate && !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+fn expand_words(mnemonic: &str) -> Result<Vec<&'static str>, CryptoError> {
+    mnemonic
+        .split_whitespace()
+        .enumerate()
+        .map(|(index, word)| expand(index, word))
+        .collect()
+}
+
+fn expand(index: usize, word: &str) -> Result<&'static str, CryptoError> {
+    expand_word(word).ok_or_else(|| CryptoError::UnknownWord {
+        index,
+        word: word.to_owned(),
+        suggestions: suggestions(word)
+            .into_iter()
+            .map(|w| w.to_owned())
+            .collect(),
+    })
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "actress tongue harbor tray suspect odor load topple vocal avoid ignore apple lunch unknown tissue museum once switch captain place lemon sail outdoor absent creek";
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(0, levenshtein("harbor", "harbor"));
+        assert_eq!(1, levenshtein("harbr", "harbor"));
+        assert_eq!(2, levenshtein("hrabor", "harbor"));
+        assert_eq!(6, levenshtein("", "harbor"));
+    }
+
+    #[test]
+    fn test_expand_word() {
+        assert_eq!(Some("harbor"), expand_word("harbor"));
+        assert_eq!(Some("harbor"), expand_word("harb"));
+        assert_eq!(Some("harbor"), expand_word("HARBxx"));
+        assert_eq!(None, expand_word("har"));
+        assert_eq!(None, expand_word("xyzw"));
+    }
+
+    #[test]
+    fn test_suggestions() {
+        assert_eq!("harbor", suggestions("hrbor")[0]);
+        assert!(suggestions("qqqqqqqq").is_empty());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let abbreviated: Vec<String> = MNEMONIC
+            .split(' ')
+            .map(|w| w.chars().take(UNIQUE_PREFIX_LEN).collect())
+            .collect();
+        assert_eq!(MNEMONIC, normalize(&abbreviated.join(" ")).unwrap());
+
+        let misspelled = MNEMONIC.replace("tongue", "tnogue");
+        match normalize(&misspelled) {
+            Err(CryptoError::UnknownWord {
+                index, suggestions, ..
+            }) => {
+                assert_eq!(1, index);
+                assert!(suggestions.contains(&"tongue".to_owned()));
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_recover_marked_word() {
+        let words: Vec<&str> = MNEMONIC.split(' ').collect();
+        for index in &[0, 12, 23, 24] {
+            let mut damaged = words.clone();
+            damaged[*index] = MISSING_WORD;
+            let candidates = recover_missing_word(&damaged.join(" ")).unwrap();
+            assert!(candidates.contains(&MNEMONIC.to_owned()));
+            for candidate in candidates {
+                assert!(to_key(&candidate).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_recover_omitted_word() {
+        let mut words: Vec<&str> = MNEMONIC.split(' ').collect();
+        words.remove(5);
+        let candidates = recover_missing_word(&words.join(" ")).unwrap();
+        assert!(candidates.contains(&MNEMONIC.to_owned()));
+    }
+
+    #[test]
+    fn test_recover_invalid_length() {
+        let words: Vec<&str> = MNEMONIC.split(' ').take(20).collect();
+        assert!(recover_missing_word(&words.join(" ")).is_err());
+    }
+}
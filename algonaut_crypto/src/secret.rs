@@ -0,0 +1,72 @@
+use std::fmt::{self, Debug, Formatter};
+use zeroize::Zeroize;
+
+/// Wrapper for secret key material.
+///
+/// The wrapped value is overwritten with zeros when dropped and is redacted in `Debug` output.
+/// It's not `Copy` and only exposes a reference, so copies can only be made explicitly,
+/// with `clone` or by copying from [Secret::expose_secret].
+pub struct Secret<T: Zeroize>(T);
+
+/// A secret 32 byte ed25519 seed.
+pub type SecretSeed = Secret<[u8; 32]>;
+
+impl<T: Zeroize> Secret<T> {
+    pub fn new(value: T) -> Secret<T> {
+        Secret(value)
+    }
+
+    /// Reference to the secret value.
+    /// Take care to not copy it in memory that isn't zeroized afterwards.
+    pub fn expose_secret(&self) -> &T {
+        &self.0
+    }
+
+    /// Mutable reference to the secret value, e.g. to fill it in place.
+    pub fn expose_secret_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Secret<T> {
+    fn clone(&self) -> Self {
+        Secret(self.0.clone())
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> Debug for Secret<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret([REDACTED])")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let secret = Secret::new([7u8; 32]);
+        assert_eq!("Secret([REDACTED])", format!("{:?}", secret));
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let secret = Secret::new(vec![1u8, 2, 3]);
+        let clone = secret.clone();
+        drop(secret);
+        assert_eq!(&vec![1u8, 2, 3], clone.expose_secret());
+    }
+}
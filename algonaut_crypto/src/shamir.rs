@@ -0,0 +1,314 @@
+use crate::error::CryptoError;
+use crate::mnemonic::{checksum, get_word, to_byte_array, to_u11_array, wordlist, MNEMONIC_DELIM};
+use crate::secret::{Secret, SecretSeed};
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+const SECRET_LEN_BYTES: usize = 32;
+// Index, threshold and share value
+const SHARE_LEN_BYTES: usize = 2 + SECRET_LEN_BYTES;
+/// Number of words of a share mnemonic, including the checksum word.
+pub const SHARE_MNEM_LEN_WORDS: usize = 26;
+
+/// A share of a secret split with [split].
+///
+/// Any `threshold` shares with distinct indexes recover the secret with [combine].
+/// Shares are secret key material: fewer than `threshold` shares reveal nothing about the secret,
+/// but `threshold` shares reveal all of it.
+#[derive(Debug, Clone)]
+pub struct Share {
+    index: u8,
+    threshold: u8,
+    value: Secret<[u8; SECRET_LEN_BYTES]>,
+}
+
+impl Share {
+    /// Point at which the polynomial was evaluated for this share, between 1 and 255.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Number of shares needed to recover the secret.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Converts the share into a 26 word mnemonic, using the word list of [crate::mnemonic].
+    /// The index and threshold are encoded in the mnemonic, and the last word is a checksum.
+    pub fn to_mnemonic(&self) -> Secret<String> {
+        let mut bytes = Secret::new(Vec::with_capacity(SHARE_LEN_BYTES));
+        bytes.expose_secret_mut().push(self.index);
+        bytes.expose_secret_mut().push(self.threshold);
+        bytes
+            .expose_secret_mut()
+            .extend_from_slice(self.value.expose_secret());
+
+        let mut words: Vec<&str> = to_u11_array(bytes.expose_secret())
+            .into_iter()
+            .map(get_word)
+            .collect();
+        words.push(checksum(bytes.expose_secret()));
+        Secret::new(words.join(MNEMONIC_DELIM))
+    }
+
+    /// Parses a share mnemonic created with [Share::to_mnemonic].
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Share, CryptoError> {
+        let mut words: Vec<&str> = mnemonic.split_whitespace().collect();
+        if words.len() != SHARE_MNEM_LEN_WORDS {
+            return Err(CryptoError::InvalidMnemonicLength);
+        }
+        let check_word = words.pop().unwrap();
+        let nums = words
+            .into_iter()
+            .map(|word| {
+                wordlist::WORDLIST
+                    .get_full(word)
+                    .map(|(index, _)| index as u32)
+                    .ok_or(CryptoError::InvalidWordsInMnemonic)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut bytes = Secret::new(to_byte_array(&nums));
+        // The last word has 3 padding bits, which end up in an extra byte
+        if bytes.expose_secret_mut().pop() != Some(0) {
+            return Err(CryptoError::InvalidWordsInMnemonic);
+        }
+        let bytes = bytes.expose_secret();
+        if check_word != checksum(bytes) {
+            return Err(CryptoError::InvalidChecksum);
+        }
+
+        let mut value = Secret::new([0; SECRET_LEN_BYTES]);
+        value.expose_secret_mut().copy_from_slice(&bytes[2..]);
+        let share = Share {
+            index: bytes[0],
+            threshold: bytes[1],
+            value,
+        };
+        if share.index == 0 || share.threshold == 0 {
+            return Err(CryptoError::InvalidShare);
+        }
+        Ok(share)
+    }
+}
+
+/// Splits a 32 byte secret, e.g. an account seed or a master derivation key, into `shares` shares,
+/// any `threshold` of which recover it (Shamir's secret sharing over GF(256)).
+///
+/// Each byte of the secret is the constant term of a random polynomial of degree `threshold - 1`,
+/// which is evaluated at the share indexes 1 to `shares`.
+pub fn split(
+    secret: &[u8; SECRET_LEN_BYTES],
+    threshold: u8,
+    shares: u8,
+) -> Result<Vec<Share>, CryptoError> {
+    if threshold == 0 || threshold > shares {
+        return Err(CryptoError::InvalidShareThreshold);
+    }
+
+    // Coefficients of the terms of degree 1 and up, for each byte of the secret
+    let mut coefficients = Secret::new(vec![0u8; (threshold as usize - 1) * SECRET_LEN_BYTES]);
+    OsRng.fill_bytes(coefficients.expose_secret_mut());
+    let coefficients = coefficients.expose_secret();
+
+    Ok((1..=shares)
+        .map(|x| {
+            let mut value = Secret::new([0; SECRET_LEN_BYTES]);
+            for (i, byte) in value.expose_secret_mut().iter_mut().enumerate() {
+                // Horner's method, from the highest degree term
+                let mut y = 0;
+                for degree in (1..threshold as usize).rev() {
+                    y = gf_mul(y, x) ^ coefficients[(degree - 1) * SECRET_LEN_BYTES + i];
+                }
+                *byte = gf_mul(y, x) ^ secret[i];
+            }
+            Share {
+                index: x,
+                threshold,
+                value,
+            }
+        })
+        .collect())
+}
+
+/// Recovers the secret from at least `threshold` shares created by the same [split].
+///
+/// Duplicated shares are ignored. Shares with different thresholds, or different values for the same index,
+/// are rejected. Note that shares of different secrets with the same threshold can't be detected:
+/// they recover a wrong secret, so check it against the expected address.
+pub fn combine(shares: &[Share]) -> Result<SecretSeed, CryptoError> {
+    let threshold = shares
+        .first()
+        .map(|s| s.threshold)
+        .ok_or(CryptoError::InsufficientShares {
+            threshold: 0,
+            shares: 0,
+        })?;
+
+    let mut distinct: Vec<&Share> = vec![];
+    for share in shares {
+        if share.threshold != threshold || share.index == 0 {
+            return Err(CryptoError::InvalidShare);
+        }
+        match distinct.iter().find(|s| s.index == share.index) {
+            Some(s) if s.value.expose_secret() != share.value.expose_secret() => {
+                return Err(CryptoError::InvalidShare)
+            }
+            Some(_) => {}
+            None => distinct.push(share),
+        }
+    }
+    if distinct.len() < threshold as usize {
+        return Err(CryptoError::InsufficientShares {
+            threshold,
+            shares: distinct.len(),
+        });
+    }
+    let points = &distinct[..threshold as usize];
+
+    // Lagrange interpolation at x = 0. Addition and subtraction are xor in GF(256).
+    let mut secret = Secret::new([0; SECRET_LEN_BYTES]);
+    for share in points {
+        let mut basis = 1;
+        for other in points.iter().filter(|s| s.index != share.index) {
+            basis = gf_mul(basis, gf_div(other.index, other.index ^ share.index));
+        }
+        for (byte, y) in secret
+            .expose_secret_mut()
+            .iter_mut()
+            .zip(share.value.expose_secret())
+        {
+            *byte ^= gf_mul(basis, *y);
+        }
+    }
+    Ok(secret)
+}
+
+// Multiplication in GF(256) with the AES polynomial x^8 + x^4 + x^3 + x + 1,
+// without branches or table lookups depending on the (secret) operands
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = a >> 7;
+        a = (a << 1) ^ (0x1b & 0u8.wrapping_sub(carry));
+        b >>= 1;
+    }
+    product
+}
+
+// a / b, with b != 0. The inverse of b is b^254, as b^255 = 1.
+fn gf_div(a: u8, b: u8) -> u8 {
+    let mut inverse = 1;
+    let mut power = b;
+    for bit in 0..8 {
+        if (254u8 >> bit) & 1 == 1 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: [u8; 32] = [
+        0x8a, 0x1d, 0x47, 0x03, 0xff, 0x00, 0x12, 0x99, 0x5e, 0x6b, 0xc4, 0x27, 0x31, 0xd0, 0x7a,
+        0x88, 0x0f, 0xee, 0x41, 0x5c, 0x9b, 0x23, 0x64, 0xa7, 0x10, 0x02, 0xcd, 0x7f, 0x36, 0xb1,
+        0x58, 0xe9,
+    ];
+
+    #[test]
+    fn test_gf_arithmetic() {
+        // Known product from FIPS-197
+        assert_eq!(0xc1, gf_mul(0x57, 0x83));
+        for a in 1..=255u8 {
+            assert_eq!(1, gf_div(a, a));
+            assert_eq!(a, gf_mul(gf_div(a, 0x53), 0x53));
+        }
+    }
+
+    #[test]
+    fn test_split_and_combine() {
+        let shares = split(&SECRET, 3, 5).unwrap();
+        assert_eq!(5, shares.len());
+
+        for subset in &[[0, 1, 2], [0, 2, 4], [4, 3, 1], [1, 3, 4]] {
+            let subset: Vec<Share> = subset.iter().map(|i| shares[*i].clone()).collect();
+            assert_eq!(&SECRET, combine(&subset).unwrap().expose_secret());
+        }
+        assert_eq!(&SECRET, combine(&shares).unwrap().expose_secret());
+    }
+
+    #[test]
+    fn test_insufficient_shares() {
+        let shares = split(&SECRET, 3, 5).unwrap();
+        let duplicated = vec![shares[0].clone(), shares[1].clone(), shares[1].clone()];
+        match combine(&duplicated) {
+            Err(CryptoError::InsufficientShares { threshold, shares }) => {
+                assert_eq!(3, threshold);
+                assert_eq!(2, shares);
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(combine(&[]).is_err());
+    }
+
+    #[test]
+    fn test_inconsistent_shares() {
+        let shares = split(&SECRET, 2, 3).unwrap();
+        let other = split(&SECRET, 3, 3).unwrap();
+        assert!(combine(&[shares[0].clone(), other[1].clone()]).is_err());
+
+        let again = split(&SECRET, 2, 3).unwrap();
+        assert!(combine(&[shares[0].clone(), again[0].clone(), shares[1].clone()]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_threshold() {
+        assert!(split(&SECRET, 0, 3).is_err());
+        assert!(split(&SECRET, 4, 3).is_err());
+        assert_eq!(1, split(&SECRET, 1, 1).unwrap().len());
+    }
+
+    #[test]
+    fn test_mnemonic_round_trip() {
+        let shares = split(&SECRET, 2, 3).unwrap();
+        let parsed: Vec<Share> = shares
+            .iter()
+            .map(|s| {
+                let mnemonic = s.to_mnemonic();
+                assert_eq!(
+                    SHARE_MNEM_LEN_WORDS,
+                    mnemonic.expose_secret().split(' ').count()
+                );
+                Share::from_mnemonic(mnemonic.expose_secret()).unwrap()
+            })
+            .collect();
+
+        assert_eq!(2, parsed[1].index());
+        assert_eq!(2, parsed[1].threshold());
+        assert_eq!(&SECRET, combine(&parsed[1..]).unwrap().expose_secret());
+    }
+
+    #[test]
+    fn test_mnemonic_invalid_checksum() {
+        let share = split(&SECRET, 2, 3).unwrap().remove(0);
+        let mnemonic = share.to_mnemonic();
+        let mut words: Vec<&str> = mnemonic.expose_secret().split(' ').collect();
+        words[5] = if words[5] == "abandon" {
+            "ability"
+        } else {
+            "abandon"
+        };
+        assert!(Share::from_mnemonic(&words.join(" ")).is_err());
+
+        let account_mnemonic = crate::mnemonic::from_key(&SECRET).unwrap();
+        assert!(matches!(
+            Share::from_mnemonic(&account_mnemonic),
+            Err(CryptoError::InvalidMnemonicLength)
+        ));
+    }
+}
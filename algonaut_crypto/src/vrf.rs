@@ -0,0 +1,347 @@
+use crate::error::CryptoError;
+use crate::secret::SecretSeed;
+use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::scalar::Scalar;
+use num_bigint::BigUint;
+use rand::rngs::OsRng;
+use rand::Rng;
+use sha2::{Digest, Sha512};
+use std::fmt::{self, Debug, Formatter};
+
+/// Length of a VRF proof in bytes: Gamma (32), c (16) and s (32).
+pub const PROOF_LEN_BYTES: usize = 80;
+
+/// Length of a VRF output in bytes.
+pub const OUTPUT_LEN_BYTES: usize = 64;
+
+// Suite string of ECVRF-ED25519-SHA512-Elligator2 (draft-irtf-cfrg-vrf-03)
+const SUITE: u8 = 0x04;
+const HASH_TO_CURVE: u8 = 0x01;
+const HASH_POINTS: u8 = 0x02;
+const PROOF_TO_HASH: u8 = 0x03;
+
+/// Montgomery curve25519 parameter A
+const CURVE25519_A: u32 = 486662;
+
+/// A proof of ECVRF-ED25519-SHA512-Elligator2, as specified in draft-irtf-cfrg-vrf-03
+/// and implemented by Algorand's libsodium fork.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct VrfProof(pub [u8; PROOF_LEN_BYTES]);
+
+/// The output (beta) of a VRF proof, e.g. the seed of a block or the hash used for sortition.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct VrfOutput(pub [u8; OUTPUT_LEN_BYTES]);
+
+impl VrfProof {
+    /// Verifies the proof for `message` with the VRF public key, returning the output.
+    ///
+    /// Fails if the public key is not a canonically encoded point of large order,
+    /// if Gamma is not a canonically encoded point or if the proof doesn't match.
+    pub fn verify(&self, public_key: &[u8; 32], message: &[u8]) -> Result<VrfOutput, CryptoError> {
+        let y = decode_point(public_key)
+            .filter(|y| !y.is_small_order())
+            .ok_or(CryptoError::InvalidVrfPublicKey)?;
+        let (gamma, c, s) = self.decode()?;
+
+        let h = hash_to_curve(public_key, message).ok_or(CryptoError::InvalidVrfProof)?;
+        // U = s*B - c*Y, V = s*H - c*Gamma
+        let u = EdwardsPoint::vartime_double_scalar_mul_basepoint(&(-c), &y, &s);
+        let v = s * h - c * gamma;
+
+        if hash_points(&h, &gamma, &u, &v) != self.0[32..48] {
+            return Err(CryptoError::InvalidVrfProof);
+        }
+        self.to_hash()
+    }
+
+    /// Output of the proof, without verifying it.
+    pub fn to_hash(&self) -> Result<VrfOutput, CryptoError> {
+        let (gamma, _, _) = self.decode()?;
+        let mut output = [0; OUTPUT_LEN_BYTES];
+        output.copy_from_slice(
+            &Sha512::new()
+                .chain([SUITE, PROOF_TO_HASH])
+                .chain(gamma.mul_by_cofactor().compress().as_bytes())
+                .finalize(),
+        );
+        Ok(VrfOutput(output))
+    }
+
+    fn decode(&self) -> Result<(EdwardsPoint, Scalar, Scalar), CryptoError> {
+        let mut gamma = [0; 32];
+        gamma.copy_from_slice(&self.0[..32]);
+        let gamma = decode_point(&gamma).ok_or(CryptoError::InvalidVrfProof)?;
+
+        let mut c = [0; 32];
+        c[..16].copy_from_slice(&self.0[32..48]);
+        let mut s = [0; 32];
+        s.copy_from_slice(&self.0[48..]);
+        // Like libsodium, s is reduced instead of being required to be canonical
+        Ok((
+            gamma,
+            Scalar::from_bytes_mod_order(c),
+            Scalar::from_bytes_mod_order(s),
+        ))
+    }
+}
+
+/// A VRF secret key. Like an ed25519 key, it's a 32 byte seed from which the secret scalar
+/// and the public key are derived.
+#[derive(Debug, Clone)]
+pub struct VrfSecretKey(SecretSeed);
+
+impl VrfSecretKey {
+    pub fn generate() -> VrfSecretKey {
+        VrfSecretKey(SecretSeed::new(OsRng.gen()))
+    }
+
+    pub fn from_seed(seed: SecretSeed) -> VrfSecretKey {
+        VrfSecretKey(seed)
+    }
+
+    pub fn expose_secret(&self) -> &[u8; 32] {
+        self.0.expose_secret()
+    }
+
+    /// The public key, derived like an ed25519 public key.
+    pub fn public_key(&self) -> [u8; 32] {
+        (&ED25519_BASEPOINT_TABLE * &self.scalar())
+            .compress()
+            .to_bytes()
+    }
+
+    /// Proves `message`. The proof can be verified with [VrfProof::verify].
+    pub fn prove(&self, message: &[u8]) -> VrfProof {
+        let public_key = self.public_key();
+        let x = self.scalar();
+        let h = hash_to_curve(&public_key, message)
+            .expect("The public key of a secret key is a valid point");
+        let gamma = x * h;
+
+        let nonce = Sha512::new()
+            .chain(&Sha512::digest(self.expose_secret())[32..])
+            .chain(h.compress().as_bytes())
+            .finalize();
+        let mut k = [0; 64];
+        k.copy_from_slice(&nonce);
+        let k = Scalar::from_bytes_mod_order_wide(&k);
+
+        let c = hash_points(&h, &gamma, &(&ED25519_BASEPOINT_TABLE * &k), &(k * h));
+        let mut c_bytes = [0; 32];
+        c_bytes[..16].copy_from_slice(&c);
+        let s = k + Scalar::from_bytes_mod_order(c_bytes) * x;
+
+        let mut proof = [0; PROOF_LEN_BYTES];
+        proof[..32].copy_from_slice(gamma.compress().as_bytes());
+        proof[32..48].copy_from_slice(&c);
+        proof[48..].copy_from_slice(s.as_bytes());
+        VrfProof(proof)
+    }
+
+    // Clamped first half of SHA512(seed), like ed25519
+    fn scalar(&self) -> Scalar {
+        let mut x = [0; 32];
+        x.copy_from_slice(&Sha512::digest(self.expose_secret())[..32]);
+        x[0] &= 248;
+        x[31] &= 127;
+        x[31] |= 64;
+        Scalar::from_bytes_mod_order(x)
+    }
+}
+
+impl Debug for VrfProof {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "VrfProof({})", hex(&self.0))
+    }
+}
+
+impl Debug for VrfOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "VrfOutput({})", hex(&self.0))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    data_encoding::HEXLOWER.encode(bytes)
+}
+
+// Decodes a point, rejecting non canonical encodings (y >= p) like libsodium
+fn decode_point(bytes: &[u8; 32]) -> Option<EdwardsPoint> {
+    let mut y = *bytes;
+    y[31] &= 0x7f;
+    if BigUint::from_bytes_le(&y) >= field_prime() {
+        return None;
+    }
+    CompressedEdwardsY(*bytes).decompress()
+}
+
+// Elligator2 map of SHA512(suite || 0x01 || public key || message) to the curve, multiplied by the cofactor
+fn hash_to_curve(public_key: &[u8; 32], message: &[u8]) -> Option<EdwardsPoint> {
+    let hash = Sha512::new()
+        .chain([SUITE, HASH_TO_CURVE])
+        .chain(public_key)
+        .chain(message)
+        .finalize();
+    let mut r = [0; 32];
+    r.copy_from_slice(&hash[..32]);
+    r[31] &= 0x7f;
+
+    let p = field_prime();
+    let a = BigUint::from(CURVE25519_A);
+    let r = BigUint::from_bytes_le(&r) % &p;
+
+    // u = -A / (1 + 2r^2)
+    let denominator = (BigUint::from(2u8) * &r * &r + 1u8) % &p;
+    let mut u = (&p - &a) * invert(&denominator, &p) % &p;
+    // If u^3 + Au^2 + u is not a square, use the other candidate -u - A
+    let e = (&u * &u * &u + &a * &u * &u + &u) % &p;
+    if e.modpow(&((&p - 1u8) >> 1), &p) == &p - 1u8 {
+        u = (&p + &p - &u - &a) % &p;
+    }
+
+    let mut u_bytes = [0; 32];
+    let u_le = u.to_bytes_le();
+    u_bytes[..u_le.len()].copy_from_slice(&u_le);
+    MontgomeryPoint(u_bytes)
+        .to_edwards(0)
+        .map(|point| point.mul_by_cofactor())
+}
+
+fn hash_points(
+    p1: &EdwardsPoint,
+    p2: &EdwardsPoint,
+    p3: &EdwardsPoint,
+    p4: &EdwardsPoint,
+) -> [u8; 16] {
+    let hash = Sha512::new()
+        .chain([SUITE, HASH_POINTS])
+        .chain(p1.compress().as_bytes())
+        .chain(p2.compress().as_bytes())
+        .chain(p3.compress().as_bytes())
+        .chain(p4.compress().as_bytes())
+        .finalize();
+    let mut c = [0; 16];
+    c.copy_from_slice(&hash[..16]);
+    c
+}
+
+// 2^255 - 19
+fn field_prime() -> BigUint {
+    (BigUint::from(1u8) << 255) - 19u8
+}
+
+// Inverse by Fermat's little theorem, 0 for 0 like libsodium's fe25519_invert
+fn invert(x: &BigUint, p: &BigUint) -> BigUint {
+    x.modpow(&(p - 2u8), p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_encoding::HEXLOWER;
+
+    // Test vectors of draft-irtf-cfrg-vrf-03, appendix A.4
+    const VECTORS: [(&str, &str, &str, &str); 3] = [
+        (
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "b6b4699f87d56126c9117a7da55bd0085246f4c56dbc95d20172612e9d38e8d7ca65e573a126ed88d4e30a46f80a666854d675cf3ba81de0de043c3774f061560f55edc256a787afe701677c0f602900",
+            "5b49b554d05c0cd5a5325376b3387de59d924fd1e13ded44648ab33c21349a603f25b84ec5ed887995b33da5e3bfcb87cd2f64521c4c62cf825cffabbe5d31cc",
+        ),
+        (
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "ae5b66bdf04b4c010bfe32b2fc126ead2107b697634f6f7337b9bff8785ee111200095ece87dde4dbe87343f6df3b107d91798c8a7eb1245d3bb9c5aafb093358c13e6ae1111a55717e895fd15f99f07",
+            "94f4487e1b2fec954309ef1289ecb2e15043a2461ecc7b2ae7d4470607ef82eb1cfa97d84991fe4a7bfdfd715606bc27e2967a6c557cfb5875879b671740b7d8",
+        ),
+        (
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "dfa2cba34b611cc8c833a6ea83b8eb1bb5e2ef2dd1b0c481bc42ff36ae7847f6ab52b976cfd5def172fa412defde270c8b8bdfbaae1c7ece17d9833b1bcf31064fff78ef493f820055b561ece45e1009",
+            "2031837f582cd17a9af9e0c7ef5a6540e3453ed894b62c293686ca3c1e319dde9d0aa489a4b59a9594fc2328bc3deff3c8a0929a369a72b1180a596e016b5ded",
+        ),
+    ];
+
+    fn decode(
+        (public_key, message, proof, output): (&str, &str, &str, &str),
+    ) -> ([u8; 32], Vec<u8>, VrfProof, VrfOutput) {
+        let mut pk = [0; 32];
+        pk.copy_from_slice(&HEXLOWER.decode(public_key.as_bytes()).unwrap());
+        let mut pi = [0; PROOF_LEN_BYTES];
+        pi.copy_from_slice(&HEXLOWER.decode(proof.as_bytes()).unwrap());
+        let mut beta = [0; OUTPUT_LEN_BYTES];
+        beta.copy_from_slice(&HEXLOWER.decode(output.as_bytes()).unwrap());
+        (
+            pk,
+            HEXLOWER.decode(message.as_bytes()).unwrap(),
+            VrfProof(pi),
+            VrfOutput(beta),
+        )
+    }
+
+    #[test]
+    fn test_verify_vectors() {
+        for vector in VECTORS.iter() {
+            let (public_key, message, proof, output) = decode(*vector);
+            assert_eq!(output, proof.verify(&public_key, &message).unwrap());
+            assert_eq!(output, proof.to_hash().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_prove_vectors() {
+        let secret_keys = [
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        ];
+        for (secret_key, vector) in secret_keys.iter().zip(VECTORS.iter()) {
+            let (public_key, message, proof, _) = decode(*vector);
+            let mut seed = [0; 32];
+            seed.copy_from_slice(&HEXLOWER.decode(secret_key.as_bytes()).unwrap());
+            let secret_key = VrfSecretKey::from_seed(SecretSeed::new(seed));
+
+            assert_eq!(public_key, secret_key.public_key());
+            assert_eq!(proof, secret_key.prove(&message));
+        }
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let secret_key = VrfSecretKey::generate();
+        let proof = secret_key.prove(b"message");
+        assert!(proof.verify(&secret_key.public_key(), b"message").is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_message() {
+        let (public_key, _, proof, _) = decode(VECTORS[1]);
+        assert!(matches!(
+            proof.verify(&public_key, &[0x73]),
+            Err(CryptoError::InvalidVrfProof)
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_proof() {
+        let (public_key, message, proof, _) = decode(VECTORS[2]);
+        for index in &[0, 40, 60] {
+            let mut tampered = proof;
+            tampered.0[*index] ^= 1;
+            assert!(tampered.verify(&public_key, &message).is_err());
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_small_order_public_key() {
+        let (_, message, proof, _) = decode(VECTORS[0]);
+        let mut identity = [0; 32];
+        identity[0] = 1;
+        assert!(matches!(
+            proof.verify(&identity, &message),
+            Err(CryptoError::InvalidVrfPublicKey)
+        ));
+    }
+}
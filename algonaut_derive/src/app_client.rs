@@ -0,0 +1,404 @@
+use algonaut_encoding::split_abi_tuple;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_quote, Ident, LitStr, Path, Token, Visibility};
+
+/// Input of `app_client!`: `<visibility> <name>, "<path of the contract JSON>"`, optionally
+/// followed by `crate = "<path of algonaut>"`.
+pub struct AppClientInput {
+    vis: Visibility,
+    name: Ident,
+    path: LitStr,
+    krate: Path,
+}
+
+impl Parse for AppClientInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        let mut krate = parse_quote!(::algonaut);
+        if input.parse::<Option<Token![,]>>()?.is_some() && input.peek(Token![crate]) {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            krate = input.parse::<LitStr>()?.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(AppClientInput {
+            vis,
+            name,
+            path,
+            krate,
+        })
+    }
+}
+
+/// Functions of the generated client, which the methods of the contract can't be named as.
+const CLIENT_FNS: &[&str] = &["new", "with_options", "contract", "client"];
+
+/// A method of the contract description, with the Rust names and types of its arguments.
+struct Method {
+    name: String,
+    signature: String,
+    desc: Option<String>,
+    args: Vec<(Ident, Arg)>,
+    returns: TokenStream2,
+}
+
+enum Arg {
+    Value(TokenStream2),
+    Account,
+    Asset,
+    Application,
+    Transaction,
+}
+
+pub fn app_client_impl(input: &AppClientInput) -> syn::Result<TokenStream2> {
+    let error = |message: String| syn::Error::new_spanned(&input.path, message);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| error("CARGO_MANIFEST_DIR is not set".to_owned()))?;
+    let path = PathBuf::from(manifest_dir).join(input.path.value());
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| error(format!("Can't read {}: {}", path.display(), e)))?;
+    let contract: Value = serde_json::from_str(&json)
+        .map_err(|e| error(format!("Invalid contract description: {}", e)))?;
+    let krate = &input.krate;
+    let methods = parse_methods(&contract, krate).map_err(error)?;
+
+    check_fn_names(&methods).map_err(error)?;
+    let method_fns: Vec<TokenStream2> = methods.iter().map(|m| method_fn(m, krate)).collect();
+
+    let vis = &input.vis;
+    let name = &input.name;
+    let path = path.to_string_lossy();
+    let doc = format!(
+        "Client of the ARC-4 contract described in `{}`.",
+        input.path.value()
+    );
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #name<'a> {
+            client: #krate::app_client::AppClient<'a>,
+            contract: #krate::transaction::abi::AbiContract,
+            options: #krate::app_client::CallOptions,
+        }
+
+        impl<'a> #name<'a> {
+            /// The contract description.
+            pub const CONTRACT_JSON: &'static str = include_str!(#path);
+
+            /// Fails if the contract description isn't a valid ARC-4 contract.
+            pub fn new(
+                algod: &'a #krate::algod::v2::Algod,
+                app_id: u64,
+                sender: #krate::core::Address,
+                signer: &'a (dyn #krate::signer::Signer + Sync),
+            ) -> ::std::result::Result<Self, #krate::error::AlgonautError> {
+                Ok(#name {
+                    client: #krate::app_client::AppClient::new(algod, app_id, sender, signer),
+                    contract: #krate::transaction::abi::AbiContract::from_json(Self::CONTRACT_JSON)?,
+                    options: ::std::default::Default::default(),
+                })
+            }
+
+            /// Sets the options of the calls.
+            pub fn with_options(mut self, options: #krate::app_client::CallOptions) -> Self {
+                self.options = options;
+                self
+            }
+
+            pub fn contract(&self) -> &#krate::transaction::abi::AbiContract {
+                &self.contract
+            }
+
+            pub fn client(&self) -> &#krate::app_client::AppClient<'a> {
+                &self.client
+            }
+
+            #(#method_fns)*
+        }
+    })
+}
+
+/// Checks that each method has a function of its own in the client.
+fn check_fn_names(methods: &[Method]) -> Result<(), String> {
+    let mut names = HashSet::new();
+    let mut fn_names = HashMap::new();
+    for method in methods {
+        if !names.insert(method.name.as_str()) {
+            return Err(format!(
+                "Overloaded method {} is not supported, call it with AppClient::call",
+                method.name
+            ));
+        }
+        let fn_name = to_snake_case(&method.name);
+        if CLIENT_FNS.contains(&fn_name.as_str()) {
+            return Err(format!(
+                "Method {} collides with the client's function {}, call it with AppClient::call",
+                method.name, fn_name
+            ));
+        }
+        if let Some(other) = fn_names.insert(fn_name.clone(), method.name.as_str()) {
+            return Err(format!(
+                "Methods {} and {} are both named {} in Rust, call one of them with AppClient::call",
+                other, method.name, fn_name
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn method_fn(method: &Method, krate: &Path) -> TokenStream2 {
+    let fn_name = ident(&to_snake_case(&method.name));
+    let signature = &method.signature;
+    let doc = match &method.desc {
+        Some(desc) => format!("{}\n\nCalls `{}`.", desc, signature),
+        None => format!("Calls `{}`.", signature),
+    };
+    let returns = &method.returns;
+
+    let mut params = vec![];
+    let mut values = vec![];
+    for (name, arg) in &method.args {
+        let (ty, value) = match arg {
+            Arg::Value(ty) => (
+                ty.clone(),
+                quote!(#krate::transaction::abi::AbiArgValue::Value(
+                    #krate::transaction::abi::ToAbiValue::to_abi_value(&#name)
+                )),
+            ),
+            Arg::Account => (
+                quote!(#krate::core::Address),
+                quote!(#krate::transaction::abi::AbiArgValue::Account(#name)),
+            ),
+            Arg::Asset => (
+                quote!(u64),
+                quote!(#krate::transaction::abi::AbiArgValue::Asset(#name)),
+            ),
+            Arg::Application => (
+                quote!(u64),
+                quote!(#krate::transaction::abi::AbiArgValue::Application(#name)),
+            ),
+            Arg::Transaction => (
+                quote!(#krate::transaction::Transaction),
+                quote!(#krate::transaction::abi::AbiArgValue::Transaction(::std::boxed::Box::new(#name))),
+            ),
+        };
+        params.push(quote!(#name: #ty));
+        values.push(value);
+    }
+
+    quote! {
+        #[doc = #doc]
+        pub async fn #fn_name(
+            &self,
+            #(#params),*
+        ) -> ::std::result::Result<
+            #krate::app_client::AppCallResult<#returns>,
+            #krate::error::AlgonautError,
+        > {
+            let args = vec![#(#values),*];
+            let method = self.contract.method(#signature)?;
+            self.client
+                .call(method, args, &self.options)
+                .await?
+                .decode()
+        }
+    }
+}
+
+fn parse_methods(contract: &Value, krate: &Path) -> Result<Vec<Method>, String> {
+    if !contract["name"].is_string() {
+        return Err("The contract has no name".to_owned());
+    }
+    let methods = contract["methods"]
+        .as_array()
+        .ok_or_else(|| "The contract has no methods".to_owned())?;
+
+    let mut parsed = vec![];
+    for method in methods {
+        let name = method["name"]
+            .as_str()
+            .ok_or_else(|| "A method has no name".to_owned())?;
+        let args = method["args"]
+            .as_array()
+            .ok_or_else(|| format!("Method {} has no args", name))?;
+        let return_type = method["returns"]["type"]
+            .as_str()
+            .ok_or_else(|| format!("Method {} has no return type", name))?;
+
+        let mut arg_types = vec![];
+        let mut rust_args = vec![];
+        for (i, arg) in args.iter().enumerate() {
+            let arg_type = arg["type"]
+                .as_str()
+                .ok_or_else(|| format!("Argument {} of method {} has no type", i, name))?;
+            let arg_name = match arg["name"].as_str() {
+                Some(arg_name) => to_snake_case(arg_name),
+                None => format!("arg{}", i),
+            };
+            arg_types.push(arg_type);
+            rust_args.push((ident(&arg_name), rust_arg(arg_type, krate)?));
+        }
+
+        parsed.push(Method {
+            name: name.to_owned(),
+            signature: format!("{}({}){}", name, arg_types.join(","), return_type),
+            desc: method["desc"].as_str().map(|desc| desc.to_owned()),
+            args: rust_args,
+            returns: match return_type {
+                "void" => quote!(()),
+                t => rust_type(t, krate)?,
+            },
+        });
+    }
+    Ok(parsed)
+}
+
+fn rust_arg(arg_type: &str, krate: &Path) -> Result<Arg, String> {
+    Ok(match arg_type {
+        "account" => Arg::Account,
+        "asset" => Arg::Asset,
+        "application" => Arg::Application,
+        "txn" | "pay" | "keyreg" | "acfg" | "axfer" | "afrz" | "appl" => Arg::Transaction,
+        t => Arg::Value(rust_type(t, krate)?),
+    })
+}
+
+/// The Rust type of an ABI type. Types without a Rust equivalent (ufixed, tuples with more than
+/// 8 elements) are mapped to `AbiValue`.
+fn rust_type(abi_type: &str, krate: &Path) -> Result<TokenStream2, String> {
+    let invalid = || format!("Invalid ABI type: {}", abi_type);
+
+    if let Some(element) = abi_type.strip_suffix("[]") {
+        let element = rust_type(element, krate)?;
+        return Ok(quote!(::std::vec::Vec<#element>));
+    }
+    if let Some(rest) = abi_type.strip_suffix(']') {
+        let open = rest.rfind('[').ok_or_else(invalid)?;
+        let len: usize = rest[open + 1..].parse().map_err(|_| invalid())?;
+        let element = rust_type(&rest[..open], krate)?;
+        return Ok(quote!([#element; #len]));
+    }
+    if let Some(inner) = abi_type.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        let elements = split_abi_tuple(inner).ok_or_else(invalid)?;
+        if elements.len() > 8 {
+            return Ok(quote!(#krate::transaction::abi::AbiValue));
+        }
+        let elements = elements
+            .into_iter()
+            .map(|t| rust_type(t, krate))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(quote!((#(#elements,)*)));
+    }
+    Ok(match abi_type {
+        "bool" => quote!(bool),
+        "byte" | "uint8" => quote!(u8),
+        "uint16" => quote!(u16),
+        "uint32" => quote!(u32),
+        "uint64" => quote!(u64),
+        "uint128" => quote!(u128),
+        "address" => quote!(#krate::core::Address),
+        "string" => quote!(::std::string::String),
+        t if t.starts_with("uint") => quote!(#krate::transaction::abi::BigUint),
+        t if t.starts_with("ufixed") => quote!(#krate::transaction::abi::AbiValue),
+        _ => return Err(invalid()),
+    })
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else if c.is_alphanumeric() {
+            snake.push(c);
+        } else {
+            snake.push('_');
+        }
+    }
+    snake
+}
+
+/// An identifier for the name, using a raw identifier for keywords.
+fn ident(name: &str) -> Ident {
+    match name {
+        // Can't be raw identifiers
+        "self" | "Self" | "super" | "crate" | "_" => format_ident!("{}_", name),
+        _ if name.starts_with(|c: char| c.is_ascii_digit()) => format_ident!("_{}", name),
+        _ => syn::parse_str::<Ident>(name)
+            .unwrap_or_else(|_| Ident::new_raw(name, Span::call_site())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use serde_json::json;
+
+    fn methods(names: &[&str]) -> Vec<Method> {
+        let methods: Vec<Value> = names
+            .iter()
+            .map(|name| json!({ "name": name, "args": [], "returns": { "type": "void" } }))
+            .collect();
+        parse_methods(
+            &json!({ "name": "Contract", "methods": methods }),
+            &parse_quote!(::algonaut),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fn_names() {
+        assert!(check_fn_names(&methods(&["add", "getBalance", "withdraw"])).is_ok());
+        assert!(check_fn_names(&methods(&["add", "add"])).is_err());
+        assert!(check_fn_names(&methods(&["getBalance", "get_balance"])).is_err());
+        assert!(check_fn_names(&methods(&["withOptions"])).is_err());
+        assert!(check_fn_names(&methods(&["new"])).is_err());
+    }
+
+    #[test]
+    fn test_rust_type() {
+        let krate = parse_quote!(::algonaut);
+        assert_eq!(
+            quote!((u64, (bool, u8,),)).to_string(),
+            rust_type("(uint64,(bool,byte))", &krate)
+                .unwrap()
+                .to_string()
+        );
+        assert!(rust_type("(uint64,(bool)", &krate).is_err());
+
+        let krate = parse_quote!(my_algonaut);
+        assert_eq!(
+            quote!(::std::vec::Vec<my_algonaut::core::Address>).to_string(),
+            rust_type("address[]", &krate).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let krate = |input: AppClientInput| input.krate.to_token_stream().to_string();
+        assert_eq!(
+            quote!(::algonaut).to_string(),
+            krate(parse_quote!(pub Calculator, "calculator.json",))
+        );
+        assert_eq!(
+            quote!(my_algonaut).to_string(),
+            krate(parse_quote!(
+                Calculator,
+                "calculator.json",
+                crate = "my_algonaut"
+            ))
+        );
+    }
+}
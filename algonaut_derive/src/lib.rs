@@ -0,0 +1,249 @@
+//! Procedural macros for algonaut.
+
+mod app_client;
+
+use app_client::AppClientInput;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use std::collections::HashSet;
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields, Lit, Meta, NestedMeta, Path};
+
+/// Maximum length of an application state key, in bytes.
+const MAX_KEY_LEN: usize = 64;
+
+/// Derives `algonaut_transaction::app_state::TypedAppState` for a struct with named fields,
+/// mapping each field to a key of the application state.
+///
+/// The key is the name of the field, unless overridden with `#[app_state(key = "...")]`.
+/// Keys must be unique and at most 64 bytes long.
+/// Field types implement `algonaut_transaction::app_state::AppStateValue`: `u64` is stored as a
+/// uint, `Address`, `Vec<u8>` and byte arrays as byte slices.
+///
+/// The generated code refers to `::algonaut_transaction`, so the crate has to be a dependency.
+/// When it's only used through another crate, e.g. the `algonaut` facade, its path is set with
+/// `#[app_state(crate = "...")]` on the struct.
+///
+/// ```ignore
+/// #[derive(AppState)]
+/// #[app_state(crate = "algonaut::transaction")]
+/// struct Counter {
+///     count: u64,
+///     #[app_state(key = "own")]
+///     owner: Address,
+/// }
+/// ```
+#[proc_macro_derive(AppState, attributes(app_state))]
+pub fn derive_app_state(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    app_state_impl(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn app_state_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "AppState can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "AppState can only be derived for structs",
+            ))
+        }
+    };
+
+    let krate = crate_path(input)?;
+    let mut decoders = vec![];
+    let mut types = vec![];
+    let mut keys = HashSet::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("Named field");
+        let key = field_key(field)?.unwrap_or_else(|| ident.to_string());
+        if key.len() > MAX_KEY_LEN {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("The key `{}` is longer than {} bytes", key, MAX_KEY_LEN),
+            ));
+        }
+        if !keys.insert(key.clone()) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("The key `{}` is used by another field", key),
+            ));
+        }
+        decoders.push(quote! {
+            #ident: #krate::app_state::decode_value(state, #key)?
+        });
+        types.push(&field.ty);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::app_state::TypedAppState for #name #ty_generics #where_clause {
+            fn state_schema() -> #krate::transaction::StateSchema {
+                #krate::app_state::state_schema(&[
+                    #(<#types as #krate::app_state::AppStateValue>::IS_UINT),*
+                ])
+            }
+
+            fn from_app_state(
+                state: &#krate::app_state::AppState,
+            ) -> ::std::result::Result<Self, #krate::error::TransactionError> {
+                Ok(#name {
+                    #(#decoders),*
+                })
+            }
+        }
+    })
+}
+
+/// The path of `algonaut_transaction` set with `#[app_state(crate = "...")]` on the struct,
+/// by default `::algonaut_transaction`.
+fn crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut path = None;
+    for attr in input.attrs.iter().filter(|a| a.path.is_ident("app_state")) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected #[app_state(crate = \"...\")]",
+                ))
+            }
+        };
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("crate") => {
+                    match nv.lit {
+                        Lit::Str(lit) => path = Some(lit.parse()?),
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "The crate path must be a string",
+                            ))
+                        }
+                    }
+                }
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "Unknown app_state attribute, expected `crate`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(path.unwrap_or_else(|| syn::parse_quote!(::algonaut_transaction)))
+}
+
+/// The key set with `#[app_state(key = "...")]`, if any.
+fn field_key(field: &Field) -> syn::Result<Option<String>> {
+    let mut key = None;
+    for attr in field.attrs.iter().filter(|a| a.path.is_ident("app_state")) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected #[app_state(key = \"...\")]",
+                ))
+            }
+        };
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("key") => match nv.lit {
+                    Lit::Str(lit) => key = Some(lit.value()),
+                    lit => return Err(syn::Error::new_spanned(lit, "The key must be a string")),
+                },
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "Unknown app_state attribute, expected `key`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(key)
+}
+
+/// Generates a client for an ARC-4 contract, from its JSON description.
+///
+/// The path of the description is relative to the directory of the crate's `Cargo.toml`.
+/// The client has an async method for each method of the contract, in snake case, with the
+/// arguments and return value mapped to Rust types: `uint8` to `uint128` to the integer
+/// of the same size, larger uints to `BigUint`, `byte` to `u8`, `address` and `account` to
+/// `Address`, `asset` and `application` to their `u64` id, transactions to `Transaction`,
+/// arrays to `Vec` and arrays of fixed length, tuples to tuples and `void` to `()`.
+/// Other types are passed as `AbiValue`.
+///
+/// Overloaded methods are not supported, nor methods whose snake case names are the same or
+/// collide with the functions of the client (`new`, `with_options`, `contract` and `client`).
+/// They can be called with `AppClient::call`.
+///
+/// The generated code refers to `::algonaut`. When it's used through another path, e.g. a crate
+/// re-exporting it, the path is set with `crate = "..."` after the description's path.
+///
+/// ```ignore
+/// app_client!(pub Calculator, "contracts/calculator.json");
+/// app_client!(Counter, "contracts/counter.json", crate = "my_sdk::algonaut");
+///
+/// let calculator = Calculator::new(&algod, app_id, account.address(), &account)?;
+/// let sum: u128 = calculator.add(1, 2).await?.value;
+/// ```
+#[proc_macro]
+pub fn app_client(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as AppClientInput);
+    app_client::app_client_impl(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_duplicate_key() {
+        let input: DeriveInput = parse_quote! {
+            struct State {
+                a: u64,
+                #[app_state(key = "a")]
+                b: u64,
+            }
+        };
+        let error = app_state_impl(&input).unwrap_err();
+        assert_eq!("The key `a` is used by another field", error.to_string());
+    }
+
+    #[test]
+    fn test_key_too_long() {
+        let key = "k".repeat(65);
+        let input: DeriveInput = parse_quote! {
+            struct State {
+                #[app_state(key = #key)]
+                a: u64,
+            }
+        };
+        assert!(app_state_impl(&input).is_err());
+
+        let key = "k".repeat(64);
+        let input: DeriveInput = parse_quote! {
+            struct State {
+                #[app_state(key = #key)]
+                a: u64,
+            }
+        };
+        assert!(app_state_impl(&input).is_ok());
+    }
+}
@@ -1,6 +1,7 @@
 use data_encoding::BASE64;
 use serde::de::Visitor;
 use serde::{Deserialize, Deserializer, Serializer};
+use std::convert::TryInto;
 
 pub struct SignatureVisitor;
 
@@ -48,14 +49,35 @@ impl<'de> Visitor<'de> for U8_32Visitor {
     }
 }
 
+pub struct U8_64Visitor;
+
+impl<'de> Visitor<'de> for U8_64Visitor {
+    type Value = [u8; 64];
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a 64 byte array")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        v.try_into()
+            .map_err(|_| E::custom(format!("Invalid byte array length: {}", v.len())))
+    }
+}
+
 pub fn deserialize_bytes32<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
 where
     D: Deserializer<'de>,
 {
+    use serde::de::Error;
     let s = <&str>::deserialize(deserializer)?;
-    let mut decoded = [0; 32];
-    decoded.copy_from_slice(&BASE64.decode(s.as_bytes()).unwrap());
-    Ok(decoded)
+    BASE64
+        .decode(s.as_bytes())
+        .map_err(D::Error::custom)?
+        .try_into()
+        .map_err(|_| D::Error::custom("Expected 32 bytes"))
 }
 
 pub fn deserialize_bytes64<'de, D>(deserializer: D) -> Result<[u8; 64], D::Error>
@@ -64,18 +86,20 @@ where
 {
     use serde::de::Error;
     let s = <&str>::deserialize(deserializer)?;
-    let mut decoded = [0; 64];
-    let bytes = BASE64.decode(s.as_bytes()).map_err(D::Error::custom)?;
-    decoded.copy_from_slice(&bytes);
-    Ok(decoded)
+    BASE64
+        .decode(s.as_bytes())
+        .map_err(D::Error::custom)?
+        .try_into()
+        .map_err(|_| D::Error::custom("Expected 64 bytes"))
 }
 
 pub fn deserialize_bytes<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
 {
+    use serde::de::Error;
     let s = <&str>::deserialize(deserializer)?;
-    Ok(BASE64.decode(s.as_bytes()).unwrap())
+    BASE64.decode(s.as_bytes()).map_err(D::Error::custom)
 }
 
 pub fn serialize_bytes<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
@@ -84,3 +108,70 @@ where
 {
     serializer.serialize_str(&BASE64.encode(bytes))
 }
+
+pub fn deserialize_opt_bytes<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Error;
+    <Option<&str>>::deserialize(deserializer)?
+        .map(|s| BASE64.decode(s.as_bytes()).map_err(D::Error::custom))
+        .transpose()
+}
+
+pub fn serialize_opt_bytes<S>(bytes: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match bytes {
+        Some(bytes) => serialize_bytes(bytes, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize_bytes_vec<'de, D>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Error;
+    <Vec<&str>>::deserialize(deserializer)?
+        .iter()
+        .map(|s| BASE64.decode(s.as_bytes()).map_err(D::Error::custom))
+        .collect()
+}
+
+pub fn serialize_bytes_vec<S>(bytes: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(bytes.iter().map(|b| BASE64.encode(b)))
+}
+
+/// Splits the element types of an ABI tuple (without its parentheses) at the top level commas. None if the parentheses are unbalanced.
+pub fn split_abi_tuple(s: &str) -> Option<Vec<&str>> {
+    if s.is_empty() {
+        return Some(vec![]);
+    }
+    let mut types = vec![];
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                types.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        if depth < 0 {
+            return None;
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    types.push(&s[start..]);
+    Some(types)
+}
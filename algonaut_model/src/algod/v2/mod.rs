@@ -1,10 +1,14 @@
 use algonaut_core::{Address, CompiledTealBytes, MicroAlgos, Round};
 use algonaut_crypto::{deserialize_hash, HashDigest};
-use algonaut_encoding::deserialize_bytes;
+use algonaut_encoding::{
+    deserialize_bytes, deserialize_bytes_vec, serialize_bytes, serialize_bytes_vec,
+};
 use data_encoding::BASE64;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 
+pub use crate::state::{EvalDelta, EvalDeltaKeyValue, TealKeyValue, TealValue};
+
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Account {
@@ -142,6 +146,88 @@ pub struct AccountParticipation {
         deserialize_with = "deserialize_bytes"
     )]
     pub vote_participation_key: Vec<u8>,
+
+    /// Root of the state proof key (if any)
+    #[serde(
+        rename = "state-proof-key",
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub state_proof_key: Vec<u8>,
+}
+
+/// A participation key installed on the node.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ParticipationKey {
+    /// Address the key was generated for.
+    #[serde_as(as = "DisplayFromStr")]
+    pub address: Address,
+
+    /// When registered, this is the first round it may be used.
+    #[serde(rename = "effective-first-valid", default)]
+    pub effective_first_valid: Option<Round>,
+
+    /// When registered, this is the last round it may be used.
+    #[serde(rename = "effective-last-valid", default)]
+    pub effective_last_valid: Option<Round>,
+
+    /// The key's participation id.
+    pub id: String,
+
+    /// Key information stored on the account.
+    pub key: AccountParticipation,
+
+    /// Round when this key was last used to propose a block.
+    #[serde(rename = "last-block-proposal", default)]
+    pub last_block_proposal: Option<Round>,
+
+    /// Round when this key was last used to generate a state proof.
+    #[serde(rename = "last-state-proof", default)]
+    pub last_state_proof: Option<Round>,
+
+    /// Round when this key was last used to vote.
+    #[serde(rename = "last-vote", default)]
+    pub last_vote: Option<Round>,
+}
+
+/// Id of a participation key added to the node.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ParticipationKeyId {
+    /// The participation id of the added key.
+    #[serde(rename = "partId")]
+    pub part_id: String,
+}
+
+/// An account's holding of an asset and, if the account created it, the asset's parameters.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountAssetInformation {
+    /// The round for which this information is relevant.
+    pub round: Round,
+
+    /// The account's holding of the asset, if opted in.
+    #[serde(rename = "asset-holding", default)]
+    pub asset_holding: Option<AssetHolding>,
+
+    /// The parameters of the asset, if the account created it.
+    #[serde(rename = "created-asset", default)]
+    pub created_asset: Option<AssetParams>,
+}
+
+/// An account's local state of an application and, if the account created it, the application's parameters.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountApplicationInformation {
+    /// The round for which this information is relevant.
+    pub round: Round,
+
+    /// The account's local state of the application, if opted in.
+    #[serde(rename = "app-local-state", default)]
+    pub app_local_state: Option<ApplicationLocalState>,
+
+    /// The parameters of the application, if the account created it.
+    #[serde(rename = "created-app", default)]
+    pub created_app: Option<ApplicationParams>,
 }
 
 /// Application state delta.
@@ -167,6 +253,45 @@ pub struct Application {
     pub params: ApplicationParams,
 }
 
+/// Box name and its content.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApplicationBox {
+    /// The box name.
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub name: Vec<u8>,
+
+    /// The round for which this information is relevant.
+    pub round: Option<Round>,
+
+    /// The box value.
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub value: Vec<u8>,
+}
+
+/// Box names of an application.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoxesResponse {
+    /// Box names.
+    pub boxes: Vec<BoxDescriptor>,
+}
+
+/// Box descriptor describes a box without its value.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoxDescriptor {
+    /// The box name.
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub name: Vec<u8>,
+}
+
 /// Stores local state associated with an application.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ApplicationLocalState {
@@ -468,52 +593,6 @@ pub struct ErrorResponse<T> {
     pub message: String,
 }
 
-/// Represents a TEAL value delta.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
-pub struct EvalDelta {
-    /// `at` delta action.
-    pub action: u64,
-
-    /// `bs` bytes value.
-    pub bytes: Option<String>,
-
-    /// `ui` uint value.
-    pub uint: Option<u64>,
-}
-
-/// Key-value pairs for StateDelta.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
-pub struct EvalDeltaKeyValue {
-    pub key: String,
-    pub value: EvalDelta,
-}
-
-/// Represents a key-value pair in an application store.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
-pub struct TealKeyValue {
-    pub key: String,
-    pub value: TealValue,
-}
-
-/// Represents a TEAL value.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
-pub struct TealValue {
-    /// `tb` bytes value.
-    #[serde(
-        default,
-        skip_serializing_if = "Vec::is_empty",
-        deserialize_with = "deserialize_bytes"
-    )]
-    pub bytes: Vec<u8>,
-
-    /// `tt` value type.
-    #[serde(rename = "type")]
-    pub value_type: u64,
-
-    /// `ui` uint value.
-    pub uint: u64,
-}
-
 /// Version contains the current algod version.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Version {
@@ -535,9 +614,21 @@ pub struct GenesisBlock {
     pub addr: Option<String>,
 }
 
-/// A transaction.
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Transaction {}
+/// A signed transaction. Only the fields used by the SDK are decoded.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Transaction {
+    /// The transaction.
+    #[serde(default)]
+    pub txn: TransactionFields,
+}
+
+/// Fields of a transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TransactionFields {
+    /// `apid` ID of the called application, absent when creating one.
+    #[serde(rename = "apid", skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<u64>,
+}
 
 /// A potentially truncated list of transactions currently in the node's transaction pool.
 /// You can compute whether or not the list is truncated if the number of elements in the
@@ -585,6 +676,10 @@ pub struct PendingTransaction {
     )]
     pub global_state_delta: Vec<EvalDeltaKeyValue>,
 
+    /// Inner transactions produced by application execution.
+    #[serde(default, rename = "inner-txns", skip_serializing_if = "Vec::is_empty")]
+    pub inner_txns: Vec<PendingTransaction>,
+
     /// `ld` Local state key/value changes for the application being executed by this
     /// transaction.
     #[serde(
@@ -594,6 +689,15 @@ pub struct PendingTransaction {
     )]
     pub local_state_delta: Vec<AccountStateDelta>,
 
+    /// Logs for the application being executed by this transaction.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_bytes_vec",
+        serialize_with = "serialize_bytes_vec"
+    )]
+    pub logs: Vec<Vec<u8>>,
+
     /// Indicates that the transaction was kicked out of this node's transaction pool
     /// (and specifies why that happened). An empty string indicates the transaction
     /// wasn't kicked out of this node's txpool due to an error.
@@ -612,6 +716,18 @@ pub struct PendingTransaction {
     pub txn: Transaction,
 }
 
+impl PendingTransaction {
+    /// This transaction and its inner transactions, recursively, in execution order.
+    ///
+    /// Each transaction is paired with its path in the tree: the indexes in the `inner_txns`
+    /// of each ancestor. The path of this transaction is empty.
+    pub fn flatten(&self) -> Vec<(Vec<usize>, &PendingTransaction)> {
+        let mut transactions = vec![];
+        crate::flatten_into(self, vec![], &mut transactions, |t| &t.inner_txns);
+        transactions
+    }
+}
+
 /// Information about the status of a node
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NodeStatus {
@@ -707,6 +823,65 @@ pub struct BlockHeader {
     pub txn: Option<String>,
 }
 
+/// Hash of a block.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BlockHash {
+    /// Block header hash.
+    #[serde(rename = "blockHash")]
+    pub block_hash: String,
+}
+
+/// Hash function used to create a transaction proof.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HashType {
+    #[serde(rename = "sha512_256")]
+    Sha512_256,
+    #[serde(rename = "sha256")]
+    Sha256,
+}
+
+/// Proof of the membership of a transaction in a block.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransactionProof {
+    /// The type of hash function used to create the proof.
+    pub hashtype: HashType,
+
+    /// Index of the transaction in the block's payset.
+    pub idx: u64,
+
+    /// Proof of transaction membership.
+    #[serde(default, deserialize_with = "deserialize_bytes")]
+    pub proof: Vec<u8>,
+
+    /// Hash of the signed transaction in block, for verifying the proof.
+    #[serde(deserialize_with = "deserialize_bytes")]
+    pub stibhash: Vec<u8>,
+
+    /// Depth of the tree being proven, i.e. the number of edges from a leaf to the root.
+    pub treedepth: u64,
+}
+
+/// Proof of a light block header, in the vector commitment of the state proof interval.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LightBlockHeaderProof {
+    /// Index of the light block header in the vector commitment tree.
+    pub index: u64,
+
+    /// The encoded proof.
+    #[serde(default, deserialize_with = "deserialize_bytes")]
+    pub proof: Vec<u8>,
+
+    /// Depth of the tree being proven, i.e. the number of edges from a leaf to the root.
+    pub treedepth: u64,
+}
+
+/// Minimum sync round of the ledger.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncRound {
+    /// The minimum sync round for the ledger.
+    pub round: Round,
+}
+
 /// Catchup
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Catchup {
@@ -757,6 +932,13 @@ pub struct SourceTeal {
     pub source: String,
 }
 
+/// Disassembled TEAL program.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DisassembledTeal {
+    /// Disassembled TEAL source code.
+    pub result: String,
+}
+
 /// Compiled TEAL program.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct ApiCompiledTeal {
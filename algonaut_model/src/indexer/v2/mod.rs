@@ -1,10 +1,17 @@
-use algonaut_core::{Address, MicroAlgos, Round};
+use algonaut_core::{Address, CompiledTealBytes, MicroAlgos, Round};
 use algonaut_crypto::{deserialize_hash, HashDigest};
-use algonaut_encoding::deserialize_bytes;
-use serde::{Deserialize, Serialize};
+use algonaut_encoding::{
+    deserialize_bytes, deserialize_bytes_vec, deserialize_opt_bytes, serialize_bytes,
+    serialize_bytes_vec, serialize_opt_bytes,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::{serde_as, DisplayFromStr};
 
+pub use crate::state::{EvalDelta, EvalDeltaKeyValue, TealKeyValue, TealValue};
+
 ///
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct QueryAccount {
     /// Application ID.
@@ -16,8 +23,9 @@ pub struct QueryAccount {
     pub asset_id: Option<u64>,
 
     /// Include accounts configured to use this spending key.
-    #[serde(rename = "auth-addr")]
-    pub auth_addr: Option<String>,
+    #[serde(rename = "auth-addr", default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub auth_addr: Option<Address>,
 
     /// Results should have an amount greater than this value. MicroAlgos are the default currency
     /// unless an asset-id is provided, in which case the asset will be used.
@@ -82,17 +90,17 @@ pub struct AccountInfoResponse {
 /// Query account transactions.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct QueryAccountTransaction {
-    /// Include results after the given time. Must be an RFC 3339 formatted string.
+    /// Include results after the given time. Sent as an RFC 3339 formatted string.
     #[serde(rename = "after-time", skip_serializing_if = "Option::is_none")]
-    pub after_time: Option<String>,
+    pub after_time: Option<DateTime<Utc>>,
 
     /// Asset ID
     #[serde(rename = "asset-id", skip_serializing_if = "Option::is_none")]
     pub asset_id: Option<u64>,
 
-    /// Include results before the given time. Must be an RFC 3339 formatted string.
+    /// Include results before the given time. Sent as an RFC 3339 formatted string.
     #[serde(rename = "before-time", skip_serializing_if = "Option::is_none")]
-    pub before_time: Option<String>,
+    pub before_time: Option<DateTime<Utc>>,
 
     /// Results should have an amount greater than this value. MicroAlgos are the default currency
     /// unless an asset-id is provided, in which case the asset will be used.
@@ -123,9 +131,15 @@ pub struct QueryAccountTransaction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next: Option<String>,
 
-    /// Specifies a prefix which must be contained in the note field.
-    #[serde(rename = "note-prefix", skip_serializing_if = "Option::is_none")]
-    pub note_prefix: Option<String>,
+    /// Specifies a prefix which must be contained in the note field. Sent base64 encoded.
+    #[serde(
+        rename = "note-prefix",
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_opt_bytes",
+        deserialize_with = "deserialize_opt_bytes"
+    )]
+    pub note_prefix: Option<Vec<u8>>,
 
     /// Include results which include the rekey-to field.
     #[serde(rename = "rekey-to", skip_serializing_if = "Option::is_none")]
@@ -218,6 +232,7 @@ pub struct ApplicationInfoResponse {
 }
 
 /// Query assets.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct QueryAssets {
     /// Asset ID.
@@ -225,8 +240,9 @@ pub struct QueryAssets {
     pub asset_id: Option<u64>,
 
     /// Filter just assets with the given creator address.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub creator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub creator: Option<Address>,
 
     /// Maximum number of results to return.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -324,23 +340,25 @@ pub struct BalancesResponse {
 }
 
 /// Query assets transactions.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct QueryAssetTransaction {
     /// Only include transactions with this address in one of the transaction fields.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub address: Option<Address>,
 
     /// Combine with the address parameter to define what type of address to search for.
     #[serde(rename = "address-role", skip_serializing_if = "Option::is_none")]
     pub address_role: Option<Role>,
 
-    /// Include results after the given time. Must be an RFC 3339 formatted string.
+    /// Include results after the given time. Sent as an RFC 3339 formatted string.
     #[serde(rename = "after-time", skip_serializing_if = "Option::is_none")]
-    pub after_time: Option<String>,
+    pub after_time: Option<DateTime<Utc>>,
 
-    /// Include results before the given time. Must be an RFC 3339 formatted string.
+    /// Include results before the given time. Sent as an RFC 3339 formatted string.
     #[serde(rename = "before-time", skip_serializing_if = "Option::is_none")]
-    pub before_time: Option<String>,
+    pub before_time: Option<DateTime<Utc>>,
 
     /// Results should have an amount greater than this value. MicroAlgos are the default currency
     /// unless an asset-id is provided, in which case the asset will be used.
@@ -377,9 +395,15 @@ pub struct QueryAssetTransaction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next: Option<String>,
 
-    /// Specifies a prefix which must be contained in the note field.
-    #[serde(rename = "note-prefix", skip_serializing_if = "Option::is_none")]
-    pub note_prefix: Option<String>,
+    /// Specifies a prefix which must be contained in the note field. Sent base64 encoded.
+    #[serde(
+        rename = "note-prefix",
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_opt_bytes",
+        deserialize_with = "deserialize_opt_bytes"
+    )]
+    pub note_prefix: Option<Vec<u8>>,
 
     /// Include results which include the rekey-to field.
     #[serde(rename = "rekey-to", skip_serializing_if = "Option::is_none")]
@@ -401,7 +425,7 @@ pub struct QueryAssetTransaction {
     pub tx_type: Option<TransactionType>,
 
     /// Lookup the specific transaction by ID.
-    #[serde(rename = "tx-type", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "txid", skip_serializing_if = "Option::is_none")]
     pub txid: Option<String>,
 }
 
@@ -422,19 +446,21 @@ pub struct AssetTransactionResponse {
 }
 
 /// Query transactions.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct QueryTransaction {
     /// Only include transactions with this address in one of the transaction fields.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub address: Option<Address>,
 
     /// Combine with the address parameter to define what type of address to search for.
     #[serde(rename = "address-role", skip_serializing_if = "Option::is_none")]
     pub address_role: Option<Role>,
 
-    /// Include results after the given time. Must be an RFC 3339 formatted string.
+    /// Include results after the given time. Sent as an RFC 3339 formatted string.
     #[serde(rename = "after-time", skip_serializing_if = "Option::is_none")]
-    pub after_time: Option<String>,
+    pub after_time: Option<DateTime<Utc>>,
 
     /// Application ID.
     #[serde(rename = "application-id", skip_serializing_if = "Option::is_none")]
@@ -444,9 +470,9 @@ pub struct QueryTransaction {
     #[serde(rename = "asset-id", skip_serializing_if = "Option::is_none")]
     pub asset_id: Option<u64>,
 
-    /// Include results before the given time. Must be an RFC 3339 formatted string.
+    /// Include results before the given time. Sent as an RFC 3339 formatted string.
     #[serde(rename = "before-time", skip_serializing_if = "Option::is_none")]
-    pub before_time: Option<String>,
+    pub before_time: Option<DateTime<Utc>>,
 
     /// Results should have an amount greater than this value. MicroAlgos are the default currency
     /// unless an asset-id is provided, in which case the asset will be used.
@@ -483,9 +509,15 @@ pub struct QueryTransaction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next: Option<String>,
 
-    /// Specifies a prefix which must be contained in the note field.
-    #[serde(rename = "note-prefix", skip_serializing_if = "Option::is_none")]
-    pub note_prefix: Option<String>,
+    /// Specifies a prefix which must be contained in the note field. Sent base64 encoded.
+    #[serde(
+        rename = "note-prefix",
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_opt_bytes",
+        deserialize_with = "deserialize_opt_bytes"
+    )]
+    pub note_prefix: Option<Vec<u8>>,
 
     /// Include results which include the rekey-to field.
     #[serde(rename = "rekey-to", skip_serializing_if = "Option::is_none")]
@@ -507,7 +539,7 @@ pub struct QueryTransaction {
     pub tx_type: Option<TransactionType>,
 
     /// Lookup the specific transaction by ID.
-    #[serde(rename = "tx-type", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "txid", skip_serializing_if = "Option::is_none")]
     pub txid: Option<String>,
 }
 
@@ -537,6 +569,276 @@ pub struct TransactionInfoResponse {
     pub transaction: Transaction,
 }
 
+/// Query assets of an account: the assets it created or the asset holdings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct QueryAccountAssets {
+    /// Asset ID.
+    #[serde(rename = "asset-id", skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<u64>,
+
+    /// Include all items including closed accounts, deleted applications, destroyed assets,
+    /// opted-out asset holdings, and closed-out application localstates.
+    #[serde(rename = "include-all", skip_serializing_if = "Option::is_none")]
+    pub include_all: Option<bool>,
+
+    /// Maximum number of results to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// The next page of results. Use the next token provided by the previous results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+}
+
+/// Response to accounts/id/assets endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AssetHoldingsResponse {
+    /// Asset holdings.
+    pub assets: Vec<AssetHolding>,
+
+    /// Round at which the results were computed.
+    #[serde(rename = "current-round")]
+    pub current_round: Round,
+
+    /// Used for pagination, when making another request provide this token with the next parameter.
+    #[serde(rename = "next-token", skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+}
+
+/// Query applications of an account: the applications it created or its local states.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct QueryAccountApplications {
+    /// Application ID.
+    #[serde(rename = "application-id", skip_serializing_if = "Option::is_none")]
+    pub application_id: Option<u64>,
+
+    /// Include all items including closed accounts, deleted applications, destroyed assets,
+    /// opted-out asset holdings, and closed-out application localstates.
+    #[serde(rename = "include-all", skip_serializing_if = "Option::is_none")]
+    pub include_all: Option<bool>,
+
+    /// Maximum number of results to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// The next page of results. Use the next token provided by the previous results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+}
+
+/// Response to accounts/id/apps-local-state endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationLocalStatesResponse {
+    /// Application local states.
+    #[serde(rename = "apps-local-states")]
+    pub apps_local_states: Vec<ApplicationLocalState>,
+
+    /// Round at which the results were computed.
+    #[serde(rename = "current-round")]
+    pub current_round: Round,
+
+    /// Used for pagination, when making another request provide this token with the next parameter.
+    #[serde(rename = "next-token", skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+}
+
+/// Query application logs.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct QueryApplicationLogs {
+    /// Maximum number of results to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// Include results at or before the specified max-round.
+    #[serde(rename = "max-round", skip_serializing_if = "Option::is_none")]
+    pub max_round: Option<Round>,
+
+    /// Include results at or after the specified min-round.
+    #[serde(rename = "min-round", skip_serializing_if = "Option::is_none")]
+    pub min_round: Option<Round>,
+
+    /// The next page of results. Use the next token provided by the previous results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+
+    /// Only include transactions with this sender address.
+    #[serde(
+        rename = "sender-address",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub sender_address: Option<Address>,
+
+    /// Lookup the specific transaction by ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txid: Option<String>,
+}
+
+/// Response to applications/id/logs endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationLogsResponse {
+    /// Application index.
+    #[serde(rename = "application-id")]
+    pub application_id: u64,
+
+    /// Round at which the results were computed.
+    #[serde(rename = "current-round")]
+    pub current_round: Round,
+
+    /// Logs, grouped by transaction.
+    #[serde(rename = "log-data", default)]
+    pub log_data: Vec<ApplicationLogData>,
+
+    /// Used for pagination, when making another request provide this token with the next parameter.
+    #[serde(rename = "next-token", skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+}
+
+/// Logs emitted by an application call transaction.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationLogData {
+    /// `lg` Logs for the application being executed by the transaction.
+    #[serde(
+        serialize_with = "serialize_bytes_vec",
+        deserialize_with = "deserialize_bytes_vec"
+    )]
+    pub logs: Vec<Vec<u8>>,
+
+    /// Transaction ID.
+    pub txid: String,
+}
+
+/// Query application boxes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct QueryApplicationBoxes {
+    /// Maximum number of results to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// The next page of results. Use the next token provided by the previous results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+}
+
+/// Response to applications/id/boxes endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoxesResponse {
+    /// Application index.
+    #[serde(rename = "application-id")]
+    pub application_id: u64,
+
+    /// Box names.
+    pub boxes: Vec<BoxDescriptor>,
+
+    /// Used for pagination, when making another request provide this token with the next parameter.
+    #[serde(rename = "next-token", skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+}
+
+/// Box descriptor describes an app box without a value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoxDescriptor {
+    /// Base64 encoded box name.
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub name: Vec<u8>,
+}
+
+/// Box name and its content.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationBox {
+    /// Base64 encoded box name.
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub name: Vec<u8>,
+
+    /// The round for which this information is relevant.
+    pub round: Option<Round>,
+
+    /// Base64 encoded box value.
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub value: Vec<u8>,
+}
+
+/// Query block headers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct QueryBlockHeaders {
+    /// Include results after the given time. Sent as an RFC 3339 formatted string.
+    #[serde(rename = "after-time", skip_serializing_if = "Option::is_none")]
+    pub after_time: Option<DateTime<Utc>>,
+
+    /// Include results before the given time. Sent as an RFC 3339 formatted string.
+    #[serde(rename = "before-time", skip_serializing_if = "Option::is_none")]
+    pub before_time: Option<DateTime<Utc>>,
+
+    /// Include results for blocks where the given accounts were absent and suspended.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_opt_addresses",
+        deserialize_with = "deserialize_opt_addresses"
+    )]
+    pub absent: Option<Vec<Address>>,
+
+    /// Include results for blocks where the given accounts' participation keys expired.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_opt_addresses",
+        deserialize_with = "deserialize_opt_addresses"
+    )]
+    pub expired: Option<Vec<Address>>,
+
+    /// Maximum number of results to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// Include results at or before the specified max-round.
+    #[serde(rename = "max-round", skip_serializing_if = "Option::is_none")]
+    pub max_round: Option<Round>,
+
+    /// Include results at or after the specified min-round.
+    #[serde(rename = "min-round", skip_serializing_if = "Option::is_none")]
+    pub min_round: Option<Round>,
+
+    /// The next page of results. Use the next token provided by the previous results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+
+    /// Include results for blocks proposed by the given accounts.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_opt_addresses",
+        deserialize_with = "deserialize_opt_addresses"
+    )]
+    pub proposers: Option<Vec<Address>>,
+}
+
+/// Response to block-headers endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeadersResponse {
+    /// Block headers. The blocks have no transactions.
+    pub blocks: Vec<Block>,
+
+    /// Round at which the results were computed.
+    #[serde(rename = "current-round")]
+    pub current_round: Round,
+
+    /// Used for pagination, when making another request provide this token with the next parameter.
+    #[serde(rename = "next-token", skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+}
+
 #[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Account {
@@ -676,10 +978,12 @@ pub struct AccountParticipation {
 }
 
 /// Application state delta.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AccountStateDelta {
     /// Address
-    pub address: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub address: Address,
 
     /// Delta
     pub delta: StateDelta,
@@ -742,20 +1046,20 @@ pub struct ApplicationParams {
     #[serde(
         rename = "approval-program",
         default,
-        skip_serializing_if = "Vec::is_empty",
-        deserialize_with = "deserialize_bytes"
+        serialize_with = "serialize_program",
+        deserialize_with = "deserialize_program"
     )]
-    pub approval_program: Vec<u8>,
+    pub approval_program: CompiledTealBytes,
 
     /// `clearp` approval program.
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
     #[serde(
         rename = "clear-state-program",
         default,
-        skip_serializing_if = "Vec::is_empty",
-        deserialize_with = "deserialize_bytes"
+        serialize_with = "serialize_program",
+        deserialize_with = "deserialize_program"
     )]
-    pub clear_state_program: Vec<u8>,
+    pub clear_state_program: CompiledTealBytes,
 
     /// The address that created this application. This is the address where the parameters and
     /// global state for this application can be found.
@@ -811,6 +1115,7 @@ pub struct Asset {
 
 /// Describes an asset held by an account.
 /// Definition: data/basics/userBalance.go : AssetHolding
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AssetHolding {
     /// `a` number of units held.
@@ -822,7 +1127,8 @@ pub struct AssetHolding {
 
     /// Address that created this asset. This is the address where the parameters for this asset can
     /// be found, and also the address where unwanted asset units can be sent in the worst case.
-    pub creator: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub creator: Address,
 
     /// Whether or not the asset holding is currently deleted from its account.
     pub deleted: Option<bool>,
@@ -896,7 +1202,9 @@ pub struct AssetParams {
     pub name: Option<String>,
 
     /// `r` Address of account holding reserve (non-minted) units of this asset.
-    pub reserve: Option<String>,
+    #[serde(default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub reserve: Option<Address>,
 
     /// `t` The total number of units of this asset.
     pub total: u64,
@@ -975,11 +1283,13 @@ pub struct Block {
 }
 
 /// Fields relating to rewards.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BlockRewards {
     /// `fees` accepts transaction fees, it can only spend to the incentive pool.
     #[serde(rename = "fee-sink")]
-    pub fee_sink: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee_sink: Address,
 
     /// `rwcalr` number of leftover MicroAlgos after the distribution of rewards-rate MicroAlgos
     /// for every reward unit in the next round.
@@ -994,7 +1304,8 @@ pub struct BlockRewards {
     /// `rwd` accepts periodic injections from the fee-sink and continually redistributes them as
     /// rewards.
     #[serde(rename = "rewards-pool")]
-    pub rewards_pool: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub rewards_pool: Address,
 
     /// `rate` Number of new MicroAlgos added to the participation stake from rewards at the next
     /// round.
@@ -1058,27 +1369,6 @@ pub struct ErrorResponse<T> {
     pub message: String,
 }
 
-/// Represents a TEAL value delta.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct EvalDelta {
-    /// `at` delta action.
-    pub action: u64,
-
-    /// `bs` bytes value.
-    pub bytes: Option<String>,
-
-    /// `ui` uint value.
-    pub uint: Option<u64>,
-}
-
-/// Key-value pairs for StateDelta.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct EvalDeltaKeyValue {
-    pub key: String,
-
-    pub value: EvalDelta,
-}
-
 /// A health check response.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct HealthCheck<T> {
@@ -1096,10 +1386,12 @@ pub struct HealthCheck<T> {
 }
 
 /// A simplified version of AssetHolding
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MiniAssetHolding {
-    ///
-    pub address: String,
+    /// Address of the account holding the asset.
+    #[serde_as(as = "DisplayFromStr")]
+    pub address: Address,
 
     /// `a` number of units held.
     pub amount: u64,
@@ -1162,37 +1454,12 @@ pub struct StateSchema {
     pub num_uint: u64,
 }
 
-/// Represents a key-value pair in an application store.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct TealKeyValue {
-    pub key: String,
-    pub value: TealValue,
-}
-
 /// Represents a key-value store for use in an application.
 pub type TealKeyValueStore = Vec<TealKeyValue>;
 
-/// Represents a TEAL value.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct TealValue {
-    /// `tb` bytes value.
-    #[serde(
-        default,
-        skip_serializing_if = "Vec::is_empty",
-        deserialize_with = "deserialize_bytes"
-    )]
-    pub bytes: Vec<u8>,
-
-    /// `tt` value type.
-    #[serde(rename = "type")]
-    pub value_type: u64,
-
-    /// `ui` uint value.
-    pub uint: u64,
-}
-
 /// Contains all fields common to all transactions and serves as an envelope to all transactions
 /// type..
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Transaction {
     /// Application transaction.
@@ -1213,8 +1480,9 @@ pub struct Transaction {
 
     /// `sgnr` The address used to sign the transaction. This is used for rekeyed accounts to
     /// indicate that the sender address did not sign the transaction.
-    #[serde(rename = "auth-addr")]
-    pub auth_addr: Option<String>,
+    #[serde(rename = "auth-addr", default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub auth_addr: Option<Address>,
 
     /// `rc` rewards applied to close-remainder-to account.
     #[serde(rename = "close-rewards")]
@@ -1243,10 +1511,14 @@ pub struct Transaction {
     #[serde(rename = "first-valid")]
     pub first_valid: u64,
 
-    /// `gh` Hash of genesis block.
+    /// `gh` Hash of genesis block. Zero for inner transactions.
     ///
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
-    #[serde(rename = "genesis-hash", deserialize_with = "deserialize_hash")]
+    #[serde(
+        rename = "genesis-hash",
+        default,
+        deserialize_with = "deserialize_hash"
+    )]
     pub genesis_hash: HashDigest,
 
     /// `gen` genesis block ID.
@@ -1264,9 +1536,14 @@ pub struct Transaction {
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
     pub group: Option<String>,
 
-    /// Transaction ID
+    /// Transaction ID. Empty for inner transactions.
+    #[serde(default)]
     pub id: String,
 
+    /// Inner transactions produced by application execution.
+    #[serde(rename = "inner-txns", default, skip_serializing_if = "Vec::is_empty")]
+    pub inner_txns: Vec<Transaction>,
+
     /// Offset into the round where this transaction was confirmed.
     #[serde(rename = "intra-round-offset")]
     pub intra_round_offset: Option<u64>,
@@ -1286,12 +1563,21 @@ pub struct Transaction {
     /// be confirmed.
     ///
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
-    pub lease: Option<HashDigest>,
+    pub lease: Option<String>,
 
     /// `ld` Local state key/value changes for the application being executed by this transaction.
     #[serde(rename = "local-state-delta")]
     pub local_state_delta: Option<Vec<AccountStateDelta>>,
 
+    /// `lg` Logs for the application being executed by this transaction.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_bytes_vec",
+        serialize_with = "serialize_bytes_vec"
+    )]
+    pub logs: Vec<Vec<u8>>,
+
     /// `note` Free form data.
     ///
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
@@ -1307,15 +1593,17 @@ pub struct Transaction {
 
     /// `rekey` when included in a valid transaction, the accounts auth addr will be updated with
     /// this value and future signatures must be signed with the key represented by this address.
-    #[serde(rename = "rekey-to")]
-    pub rekey_to: Option<String>,
+    #[serde(rename = "rekey-to", default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub rekey_to: Option<Address>,
 
     /// Time when the block this transaction is in was confirmed.
     #[serde(rename = "round-time")]
     pub round_time: Option<u64>,
 
     /// `snd` Sender's address.
-    pub sender: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub sender: Address,
 
     /// `rs` rewards applied to sender account.
     #[serde(rename = "sender-rewards")]
@@ -1336,6 +1624,18 @@ pub struct Transaction {
     pub tx_type: TransactionType,
 }
 
+impl Transaction {
+    /// This transaction and its inner transactions, recursively, in execution order.
+    ///
+    /// Each transaction is paired with its path in the tree: the indexes in the `inner_txns`
+    /// of each ancestor. The path of this transaction is empty.
+    pub fn flatten(&self) -> Vec<(Vec<usize>, &Transaction)> {
+        let mut transactions = vec![];
+        crate::flatten_into(self, vec![], &mut transactions, |t| &t.inner_txns);
+        transactions
+    }
+}
+
 /// All the possible types of transactions.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum TransactionType {
@@ -1347,18 +1647,21 @@ pub enum TransactionType {
     AssetConfigurationTransaction,
     #[serde(rename = "axfer")]
     AssetTransferTransaction,
-    #[serde(rename = "axfrz")]
+    #[serde(rename = "afrz")]
     AssetFreezeTransaction,
     #[serde(rename = "appl")]
     ApplicationTransaction,
 }
 
 /// Fields for application transactions.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TransactionApplication {
     /// `apat` List of accounts in addition to the sender that may be accessed from the application's
     /// approval-program and clear-state-program.
-    pub accounts: Option<Vec<String>>,
+    #[serde(default)]
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    pub accounts: Option<Vec<Address>>,
 
     /// `apaa` transaction specific arguments accessed from the application's approval-program and
     /// clear-state-program.
@@ -1369,21 +1672,39 @@ pub struct TransactionApplication {
     #[serde(rename = "application-id")]
     pub application_id: u64,
 
+    /// `apbx` the boxes that can be accessed by this transaction (and others in the same group).
+    #[serde(rename = "box-references")]
+    pub box_references: Option<Vec<BoxReference>>,
+
     /// `apap` Logic executed for every application transaction, except when on-completion is set
     /// to "clear". It can read and write global state for the application, as well as
     /// account-specific local state. Approval programs may reject the transaction.
     ///
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
-    #[serde(rename = "approval-program")]
-    pub approval_program: Option<String>,
+    #[serde(
+        rename = "approval-program",
+        default,
+        serialize_with = "serialize_opt_program",
+        deserialize_with = "deserialize_opt_program"
+    )]
+    pub approval_program: Option<CompiledTealBytes>,
 
     /// `apsu` Logic executed for application transactions with on-completion set to "clear".
     /// It can read and write global state for the application, as well as account-specific local
     /// state. Clear state programs cannot reject the transaction.
     ///
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
-    #[serde(rename = "clear-state-program")]
-    pub clear_state_program: Option<String>,
+    #[serde(
+        rename = "clear-state-program",
+        default,
+        serialize_with = "serialize_opt_program",
+        deserialize_with = "deserialize_opt_program"
+    )]
+    pub clear_state_program: Option<CompiledTealBytes>,
+
+    /// `epp` specifies the additional app program len requested in pages.
+    #[serde(rename = "extra-program-pages")]
+    pub extra_program_pages: Option<u64>,
 
     /// `apfa` Lists the applications in addition to the application-id whose global states may be
     /// accessed by this application's approval-program and clear-state-program. The access is read-only.
@@ -1408,6 +1729,21 @@ pub struct TransactionApplication {
     pub on_completion: OnCompletion,
 }
 
+/// A box which may be accessed by an application transaction.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoxReference {
+    /// Application ID which this box belongs to.
+    pub app: u64,
+
+    /// Base64 encoded box name.
+    #[serde(
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub name: Vec<u8>,
+}
+
 /// Fields for asset allocation, re-configuration, and destruction.
 ///
 /// A zero value for asset-id indicates asset creation. A zero value for the params indicates asset
@@ -1423,10 +1759,12 @@ pub struct TransactionAssetConfig {
 }
 
 /// Fields for an asset freeze transaction.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TransactionAssetFreeze {
     /// `fadd` Address of the account whose asset is being frozen or thawed.
-    pub address: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub address: Address,
 
     /// `faid` ID of the asset being frozen or thawed.
     #[serde(rename = "asset-id")]
@@ -1438,6 +1776,7 @@ pub struct TransactionAssetFreeze {
 }
 
 /// Fields for an asset transfer transaction.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TransactionAssetTransfer {
     /// `aamt` Amount of asset to transfer. A zero amount transferred to self allocates that asset
@@ -1455,15 +1794,19 @@ pub struct TransactionAssetTransfer {
     /// `aclose` Indicates that the asset should be removed from the account's Assets map,
     /// and specifies where the remaining asset holdings should be transferred. It's always valid
     /// to transfer remaining asset holdings to the creator account.
-    #[serde(rename = "close-to")]
-    pub close_to: Option<String>,
+    #[serde(rename = "close-to", default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub close_to: Option<Address>,
 
     /// `arcv` Recipient address of the transfer.
-    pub receiver: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub receiver: Address,
 
     /// `asnd` The effective sender during a clawback transactions. If this is not a zero value,
     /// the real transaction sender must be the Clawback address from the AssetParams.
-    pub sender: Option<String>,
+    #[serde(default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub sender: Option<Address>,
 }
 
 /// Fields for a keyreg transaction.
@@ -1480,6 +1823,12 @@ pub struct TransactionKeyreg {
     #[serde(rename = "selection-participation-key")]
     pub selection_participation_key: Option<String>,
 
+    /// `sprfkey` State proof key used in key registration transactions.
+    ///
+    /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
+    #[serde(rename = "state-proof-key")]
+    pub state_proof_key: Option<String>,
+
     /// `votefst` First round this participation key is valid.
     #[serde(rename = "vote-first-valid")]
     pub vote_first_valid: Option<u64>,
@@ -1489,7 +1838,7 @@ pub struct TransactionKeyreg {
     pub vote_key_dilution: Option<u64>,
 
     /// `votelst` Last round this participation key is valid.
-    #[serde(rename = "vote-key-dilution")]
+    #[serde(rename = "vote-last-valid")]
     pub vote_last_valid: Option<u64>,
 
     /// `votekey` Participation public key used in key registration transactions.
@@ -1500,6 +1849,7 @@ pub struct TransactionKeyreg {
 }
 
 /// Fields for a payment transaction.
+#[serde_as]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TransactionPayment {
     /// `amt` number of MicroAlgos intended to be transferred.
@@ -1512,11 +1862,13 @@ pub struct TransactionPayment {
 
     /// `close` when set, indicates that the sending account should be closed and all remaining
     /// funds be transferred to this address.
-    #[serde(rename = "close-remainder-to")]
-    pub close_remainder_to: Option<String>,
+    #[serde(rename = "close-remainder-to", default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub close_remainder_to: Option<Address>,
 
     /// `rcv` receiver's address.
-    pub receiver: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub receiver: Address,
 }
 
 /// Validation signature associated with some data. Only one of the signatures should be provided.
@@ -1544,7 +1896,11 @@ pub struct TransactionSignatureLogicsig {
     /// account. Base64 encoded TEAL program.
     ///
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
-    pub logic: String,
+    #[serde(
+        serialize_with = "serialize_program",
+        deserialize_with = "deserialize_program"
+    )]
+    pub logic: CompiledTealBytes,
 
     ///
     #[serde(rename = "multisig-signature")]
@@ -1594,3 +1950,71 @@ pub enum Role {
     #[serde(rename = "freeze-target")]
     FreezeTarget,
 }
+
+fn deserialize_program<'de, D>(deserializer: D) -> Result<CompiledTealBytes, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(CompiledTealBytes(deserialize_bytes(deserializer)?))
+}
+
+fn serialize_program<S>(program: &CompiledTealBytes, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serialize_bytes(&program.0, serializer)
+}
+
+fn deserialize_opt_program<'de, D>(deserializer: D) -> Result<Option<CompiledTealBytes>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(deserialize_opt_bytes(deserializer)?.map(CompiledTealBytes))
+}
+
+fn serialize_opt_program<S>(
+    program: &Option<CompiledTealBytes>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match program {
+        Some(program) => serialize_program(program, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+// Address lists are sent as comma separated query parameters
+fn serialize_opt_addresses<S>(
+    addresses: &Option<Vec<Address>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match addresses {
+        Some(addresses) => serializer.serialize_str(
+            &addresses
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_opt_addresses<'de, D>(deserializer: D) -> Result<Option<Vec<Address>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Error;
+    <Option<String>>::deserialize(deserializer)?
+        .map(|s| {
+            s.split(',')
+                .map(|a| a.parse().map_err(D::Error::custom))
+                .collect()
+        })
+        .transpose()
+}
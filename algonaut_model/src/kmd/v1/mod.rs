@@ -1,6 +1,6 @@
 use algonaut_core::MultisigSignature;
 use algonaut_crypto::{
-    deserialize_mdk, deserialize_public_keys, Ed25519PublicKey, MasterDerivationKey,
+    deserialize_mdk, deserialize_public_keys, serialize_mdk, Ed25519PublicKey, MasterDerivationKey,
 };
 use algonaut_encoding::{deserialize_bytes, deserialize_bytes64, serialize_bytes};
 use serde::{Deserialize, Serialize};
@@ -46,6 +46,7 @@ pub struct ListWalletsResponse {
 
 #[derive(Serialize, Clone)]
 pub struct CreateWalletRequest {
+    #[serde(serialize_with = "serialize_mdk")]
     pub master_derivation_key: MasterDerivationKey,
     pub wallet_driver_name: String,
     pub wallet_name: String,
@@ -4,3 +4,111 @@ pub mod algod;
 pub mod indexer;
 /// Key management daemon
 pub mod kmd;
+/// Application state
+pub mod state;
+
+/// Pre-order walk of a transaction tree, pushing each transaction with its path.
+pub(crate) fn flatten_into<'a, T>(
+    transaction: &'a T,
+    path: Vec<usize>,
+    transactions: &mut Vec<(Vec<usize>, &'a T)>,
+    inner: fn(&T) -> &[T],
+) {
+    transactions.push((path.clone(), transaction));
+    for (index, inner_transaction) in inner(transaction).iter().enumerate() {
+        let mut inner_path = path.clone();
+        inner_path.push(index);
+        flatten_into(inner_transaction, inner_path, transactions, inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{algod, indexer};
+    use algonaut_core::Address;
+    use serde_json::json;
+
+    #[test]
+    fn test_flatten_indexer_transaction() {
+        let sender = Address([1; 32]).to_string();
+        let inner = |intra: u64, inner_txns: serde_json::Value| {
+            json!({
+                "fee": 0,
+                "first-valid": 10,
+                "last-valid": 1010,
+                "intra-round-offset": intra,
+                "sender": sender,
+                "tx-type": "appl",
+                "logs": ["AQI="],
+                "inner-txns": inner_txns,
+            })
+        };
+        let json = json!({
+            "fee": 2000,
+            "first-valid": 10,
+            "last-valid": 1010,
+            "genesis-hash": "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=",
+            "id": "TXID",
+            "sender": sender,
+            "tx-type": "appl",
+            "logs": ["aGVsbG8="],
+            "inner-txns": [inner(1, json!([inner(2, json!([]))])), inner(3, json!([]))],
+        });
+        let transaction: indexer::v2::Transaction =
+            serde_json::from_str(&json.to_string()).unwrap();
+
+        assert_eq!(vec![b"hello".to_vec()], transaction.logs);
+        let flattened = transaction.flatten();
+        let paths: Vec<Vec<usize>> = flattened.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(vec![vec![], vec![0], vec![0, 0], vec![1]], paths);
+        let offsets: Vec<Option<u64>> = flattened
+            .iter()
+            .map(|(_, t)| t.intra_round_offset)
+            .collect();
+        assert_eq!(vec![None, Some(1), Some(2), Some(3)], offsets);
+        assert_eq!("", flattened[1].1.id);
+        assert_eq!(vec![vec![1, 2]], flattened[2].1.logs);
+    }
+
+    #[test]
+    fn test_flatten_pending_transaction() {
+        let json = json!({
+            "pool-error": "",
+            "confirmed-round": 10,
+            "logs": ["aGVsbG8="],
+            "txn": {},
+            "inner-txns": [
+                {"pool-error": "", "txn": {}, "asset-index": 5},
+                {
+                    "pool-error": "",
+                    "txn": {},
+                    "logs": ["AQI="],
+                    "inner-txns": [{"pool-error": "", "txn": {}, "application-index": 6}]
+                }
+            ]
+        });
+        let transaction: algod::v2::PendingTransaction =
+            serde_json::from_str(&json.to_string()).unwrap();
+
+        assert_eq!(vec![b"hello".to_vec()], transaction.logs);
+        let flattened = transaction.flatten();
+        let paths: Vec<Vec<usize>> = flattened.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(vec![vec![], vec![0], vec![1], vec![1, 0]], paths);
+        assert_eq!(Some(5), flattened[1].1.asset_index);
+        assert_eq!(vec![vec![1, 2]], flattened[2].1.logs);
+        assert_eq!(Some(6), flattened[3].1.application_index);
+    }
+
+    #[test]
+    fn test_invalid_base64_program_is_an_error() {
+        let params = |program: &str| {
+            let json = json!({
+                "approval-program": program,
+                "clear-state-program": "AQ==",
+            });
+            serde_json::from_str::<indexer::v2::ApplicationParams>(&json.to_string())
+        };
+        assert_eq!(vec![1], params("AQ==").unwrap().approval_program.0);
+        assert!(params("not base64!").is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use algonaut_core::Address;
+use algonaut_encoding::{deserialize_bytes, serialize_bytes};
+use data_encoding::BASE64;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+
+/// `tt` value type of bytes.
+const TEAL_BYTES_TYPE: u64 = 1;
+/// `tt` value type of uints.
+const TEAL_UINT_TYPE: u64 = 2;
+
+/// `at` delta action which sets a bytes value.
+const SET_BYTES_ACTION: u64 = 1;
+/// `at` delta action which sets a uint value.
+const SET_UINT_ACTION: u64 = 2;
+/// `at` delta action which deletes a value.
+const DELETE_ACTION: u64 = 3;
+
+/// Represents a TEAL value.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(try_from = "ApiTealValue", into = "ApiTealValue")]
+pub enum TealValue {
+    /// `tb` bytes value.
+    Bytes(Vec<u8>),
+    /// `ui` uint value.
+    Uint(u64),
+}
+
+/// Represents a key-value pair in an application store.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct TealKeyValue {
+    /// The key, base64 encoded in the API.
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub key: Vec<u8>,
+
+    pub value: TealValue,
+}
+
+/// Represents a TEAL value delta.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(try_from = "ApiEvalDelta", into = "ApiEvalDelta")]
+pub enum EvalDelta {
+    /// Sets a bytes value.
+    SetBytes(Vec<u8>),
+    /// Sets a uint value.
+    SetUint(u64),
+    /// Deletes the value.
+    Delete,
+}
+
+/// Key-value pairs for StateDelta.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct EvalDeltaKeyValue {
+    /// The key, base64 encoded in the API.
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub key: Vec<u8>,
+
+    pub value: EvalDelta,
+}
+
+/// Global or local state of an application, by key.
+///
+/// Built from the key-values returned by algod or the indexer, e.g. `ApplicationParams::global_state`
+/// or `ApplicationLocalState::key_value`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct AppState(BTreeMap<Vec<u8>, TealValue>);
+
+impl AppState {
+    pub fn new() -> Self {
+        AppState::default()
+    }
+
+    pub fn get(&self, key: impl AsRef<[u8]>) -> Option<&TealValue> {
+        self.0.get(key.as_ref())
+    }
+
+    /// The uint value of the key. None if the key doesn't exist or is not a uint.
+    pub fn get_uint(&self, key: impl AsRef<[u8]>) -> Option<u64> {
+        match self.get(key)? {
+            TealValue::Uint(uint) => Some(*uint),
+            TealValue::Bytes(_) => None,
+        }
+    }
+
+    /// The bytes value of the key. None if the key doesn't exist or is not a byte slice.
+    pub fn get_bytes(&self, key: impl AsRef<[u8]>) -> Option<&[u8]> {
+        match self.get(key)? {
+            TealValue::Bytes(bytes) => Some(bytes),
+            TealValue::Uint(_) => None,
+        }
+    }
+
+    /// The address stored in the key. None if the key doesn't exist or is not a 32 bytes slice.
+    pub fn get_address(&self, key: impl AsRef<[u8]>) -> Option<Address> {
+        let bytes = <[u8; 32]>::try_from(self.get_bytes(key)?).ok()?;
+        Some(Address(bytes))
+    }
+
+    pub fn insert(&mut self, key: Vec<u8>, value: TealValue) -> Option<TealValue> {
+        self.0.insert(key, value)
+    }
+
+    pub fn remove(&mut self, key: impl AsRef<[u8]>) -> Option<TealValue> {
+        self.0.remove(key.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Key-values, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&Vec<u8>, &TealValue)> {
+        self.0.iter()
+    }
+
+    /// Applies the state delta of a transaction, e.g. `PendingTransaction::global_state_delta`.
+    pub fn apply(&mut self, delta: &[EvalDeltaKeyValue]) {
+        for kv in delta {
+            match &kv.value {
+                EvalDelta::SetBytes(bytes) => {
+                    self.insert(kv.key.clone(), TealValue::Bytes(bytes.clone()))
+                }
+                EvalDelta::SetUint(uint) => self.insert(kv.key.clone(), TealValue::Uint(*uint)),
+                EvalDelta::Delete => self.remove(&kv.key),
+            };
+        }
+    }
+
+    /// The delta which turns this state into `other`, sorted by key.
+    ///
+    /// Applying it to this state with [AppState::apply] results in `other`.
+    pub fn diff(&self, other: &AppState) -> Vec<EvalDeltaKeyValue> {
+        let deleted = self
+            .0
+            .keys()
+            .filter(|key| !other.0.contains_key(*key))
+            .map(|key| (key, EvalDelta::Delete));
+        let set = other
+            .0
+            .iter()
+            .filter(|(key, value)| self.get(key) != Some(*value))
+            .map(|(key, value)| (key, value.clone().into()));
+
+        let mut delta: Vec<EvalDeltaKeyValue> = deleted
+            .chain(set)
+            .map(|(key, value)| EvalDeltaKeyValue {
+                key: key.clone(),
+                value,
+            })
+            .collect();
+        delta.sort_by(|a, b| a.key.cmp(&b.key));
+        delta
+    }
+}
+
+impl From<&[TealKeyValue]> for AppState {
+    fn from(key_values: &[TealKeyValue]) -> Self {
+        key_values
+            .iter()
+            .map(|kv| (kv.key.clone(), kv.value.clone()))
+            .collect()
+    }
+}
+
+impl FromIterator<(Vec<u8>, TealValue)> for AppState {
+    fn from_iter<I: IntoIterator<Item = (Vec<u8>, TealValue)>>(iter: I) -> Self {
+        AppState(iter.into_iter().collect())
+    }
+}
+
+impl From<TealValue> for EvalDelta {
+    fn from(value: TealValue) -> Self {
+        match value {
+            TealValue::Bytes(bytes) => EvalDelta::SetBytes(bytes),
+            TealValue::Uint(uint) => EvalDelta::SetUint(uint),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ApiTealValue {
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    bytes: Vec<u8>,
+
+    #[serde(rename = "type")]
+    value_type: u64,
+
+    #[serde(default)]
+    uint: u64,
+}
+
+impl TryFrom<ApiTealValue> for TealValue {
+    type Error = String;
+
+    fn try_from(api_value: ApiTealValue) -> Result<Self, Self::Error> {
+        match api_value.value_type {
+            TEAL_BYTES_TYPE => Ok(TealValue::Bytes(api_value.bytes)),
+            TEAL_UINT_TYPE => Ok(TealValue::Uint(api_value.uint)),
+            value_type => Err(format!("Unknown TEAL value type: {}", value_type)),
+        }
+    }
+}
+
+impl From<TealValue> for ApiTealValue {
+    fn from(value: TealValue) -> Self {
+        match value {
+            TealValue::Bytes(bytes) => ApiTealValue {
+                bytes,
+                value_type: TEAL_BYTES_TYPE,
+                uint: 0,
+            },
+            TealValue::Uint(uint) => ApiTealValue {
+                bytes: vec![],
+                value_type: TEAL_UINT_TYPE,
+                uint,
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ApiEvalDelta {
+    action: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bytes: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uint: Option<u64>,
+}
+
+impl TryFrom<ApiEvalDelta> for EvalDelta {
+    type Error = String;
+
+    fn try_from(api_delta: ApiEvalDelta) -> Result<Self, Self::Error> {
+        match api_delta.action {
+            SET_BYTES_ACTION => Ok(EvalDelta::SetBytes(
+                BASE64
+                    .decode(api_delta.bytes.unwrap_or_default().as_bytes())
+                    .map_err(|e| e.to_string())?,
+            )),
+            SET_UINT_ACTION => Ok(EvalDelta::SetUint(api_delta.uint.unwrap_or_default())),
+            DELETE_ACTION => Ok(EvalDelta::Delete),
+            action => Err(format!("Unknown delta action: {}", action)),
+        }
+    }
+}
+
+impl From<EvalDelta> for ApiEvalDelta {
+    fn from(delta: EvalDelta) -> Self {
+        match delta {
+            EvalDelta::SetBytes(bytes) => ApiEvalDelta {
+                action: SET_BYTES_ACTION,
+                bytes: Some(BASE64.encode(&bytes)),
+                uint: None,
+            },
+            EvalDelta::SetUint(uint) => ApiEvalDelta {
+                action: SET_UINT_ACTION,
+                bytes: None,
+                uint: Some(uint),
+            },
+            EvalDelta::Delete => ApiEvalDelta {
+                action: DELETE_ACTION,
+                bytes: None,
+                uint: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_key_values() {
+        let json = json!([
+            {"key": "Y291bnRlcg==", "value": {"type": 2, "uint": 5}},
+            {"key": "b3duZXI=", "value": {"type": 1, "bytes": BASE64.encode(&[1; 32]), "uint": 0}},
+        ]);
+        let key_values: Vec<TealKeyValue> = serde_json::from_str(&json.to_string()).unwrap();
+        let state = AppState::from(key_values.as_slice());
+
+        assert_eq!(Some(5), state.get_uint("counter"));
+        assert_eq!(None, state.get_uint("owner"));
+        assert_eq!(Some(Address([1; 32])), state.get_address("owner"));
+        assert_eq!(None, state.get_address("counter"));
+        assert_eq!(None, state.get_bytes("missing"));
+
+        let serialized = serde_json::to_string(&key_values).unwrap();
+        let deserialized: Vec<TealKeyValue> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(key_values, deserialized);
+    }
+
+    #[test]
+    fn test_deserialize_eval_delta() {
+        let json = json!([
+            {"key": "YQ==", "value": {"action": 1, "bytes": "AQI="}},
+            {"key": "Yg==", "value": {"action": 2, "uint": 7}},
+            {"key": "Yw==", "value": {"action": 3}},
+        ]);
+        let delta: Vec<EvalDeltaKeyValue> = serde_json::from_str(&json.to_string()).unwrap();
+
+        assert_eq!(EvalDelta::SetBytes(vec![1, 2]), delta[0].value);
+        assert_eq!(EvalDelta::SetUint(7), delta[1].value);
+        assert_eq!(EvalDelta::Delete, delta[2].value);
+        assert!(serde_json::from_str::<EvalDelta>(r#"{"action": 4}"#).is_err());
+    }
+
+    #[test]
+    fn test_diff_and_apply() {
+        let old: AppState = vec![
+            (b"a".to_vec(), TealValue::Uint(1)),
+            (b"b".to_vec(), TealValue::Bytes(vec![1])),
+            (b"c".to_vec(), TealValue::Uint(3)),
+        ]
+        .into_iter()
+        .collect();
+        let new: AppState = vec![
+            (b"a".to_vec(), TealValue::Uint(1)),
+            (b"b".to_vec(), TealValue::Bytes(vec![2])),
+            (b"d".to_vec(), TealValue::Uint(4)),
+        ]
+        .into_iter()
+        .collect();
+
+        let delta = old.diff(&new);
+        assert_eq!(
+            vec![
+                EvalDeltaKeyValue {
+                    key: b"b".to_vec(),
+                    value: EvalDelta::SetBytes(vec![2])
+                },
+                EvalDeltaKeyValue {
+                    key: b"c".to_vec(),
+                    value: EvalDelta::Delete
+                },
+                EvalDeltaKeyValue {
+                    key: b"d".to_vec(),
+                    value: EvalDelta::SetUint(4)
+                },
+            ],
+            delta
+        );
+
+        let mut applied = old.clone();
+        applied.apply(&delta);
+        assert_eq!(new, applied);
+        assert!(new.diff(&new).is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+use algonaut_core::Address;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Outcome of a signing request.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Signed,
+    Rejected { reason: String },
+}
+
+/// An entry of the audit log.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct AuditEntry {
+    /// Seconds since the unix epoch
+    pub time: u64,
+    pub address: String,
+    pub transaction_id: String,
+    pub outcome: Outcome,
+}
+
+impl AuditEntry {
+    pub fn new(address: &Address, transaction_id: &str, outcome: Outcome) -> AuditEntry {
+        AuditEntry {
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            address: address.to_string(),
+            transaction_id: transaction_id.to_owned(),
+            outcome,
+        }
+    }
+}
+
+/// Append-only log of the signing requests, one JSON entry per line.
+#[derive(Debug)]
+pub struct AuditLog {
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    /// Opens the log at `path`, creating it if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<AuditLog> {
+        Ok(AuditLog {
+            file: Mutex::new(OpenOptions::new().create(true).append(true).open(path)?),
+        })
+    }
+
+    pub fn record(&self, entry: &AuditEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let mut file = self.file.lock().expect("Audit log lock poisoned");
+        file.write_all(&line)?;
+        file.flush()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::error::SignerError;
+use crate::policy::Policy;
+use algonaut_core::Address;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Daemon configuration, read from a JSON file. Unknown fields are rejected.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Address to listen on. Defaults to 127.0.0.1:4100.
+    #[serde(default = "default_listen")]
+    pub listen: SocketAddr,
+    /// Directory with the account keystore files
+    pub keystore_dir: PathBuf,
+    pub audit_log: PathBuf,
+    /// If set, requests have to send it in the `X-Signer-API-Token` header.
+    #[serde(default)]
+    pub api_token: Option<String>,
+    /// Signing policies by address. The daemon doesn't sign for addresses without policy.
+    #[serde(default)]
+    pub policies: HashMap<String, Policy>,
+}
+
+impl Config {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Config, SignerError> {
+        serde_json::from_slice(&fs::read(path)?).map_err(|e| SignerError::Config(e.to_string()))
+    }
+
+    pub fn policies(&self) -> Result<HashMap<Address, Policy>, SignerError> {
+        self.policies
+            .iter()
+            .map(|(address, policy)| {
+                Ok((
+                    Address::from_str(address).map_err(SignerError::Config)?,
+                    policy.clone(),
+                ))
+            })
+            .collect()
+    }
+}
+
+fn default_listen() -> SocketAddr {
+    ([127, 0, 0, 1], 4100).into()
+}
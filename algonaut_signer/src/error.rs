@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SignerError {
+    #[error("Config error: {0}")]
+    Config(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] algonaut_transaction::error::TransactionError),
+    #[error("Server error: {0}")]
+    Server(#[from] hyper::Error),
+}
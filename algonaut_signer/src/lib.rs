@@ -0,0 +1,39 @@
+//! # algonaut_signer
+//!
+//! Local signing daemon: keeps the accounts of a keystore directory (see [algonaut_transaction::keystore])
+//! in memory and signs transactions sent to its HTTP API, enforcing per-address [policy::Policy]s.
+//! Every signing request is recorded in an [audit::AuditLog].
+
+pub mod audit;
+pub mod config;
+pub mod error;
+pub mod policy;
+pub mod server;
+
+use algonaut_transaction::account::Account;
+use algonaut_transaction::keystore::KeystoreDir;
+use config::Config;
+use error::SignerError;
+use std::path::Path;
+
+/// Decrypts all the accounts of the keystore directory with `password`.
+pub fn load_accounts<P: AsRef<Path>>(
+    keystore_dir: P,
+    password: &str,
+) -> Result<Vec<Account>, SignerError> {
+    let dir = KeystoreDir::new(keystore_dir)?;
+    dir.list()?
+        .iter()
+        .map(|address| Ok(dir.load(address, password)?))
+        .collect()
+}
+
+/// Creates the signing service described by the config.
+pub fn service(config: &Config, password: &str) -> Result<server::SigningService, SignerError> {
+    Ok(server::SigningService::new(
+        load_accounts(&config.keystore_dir, password)?,
+        config.policies()?,
+        audit::AuditLog::open(&config.audit_log)?,
+        config.api_token.clone(),
+    ))
+}
@@ -0,0 +1,37 @@
+use algonaut_signer::config::Config;
+use algonaut_signer::server::serve;
+use std::env;
+use std::error::Error;
+use std::net::TcpListener;
+use std::sync::Arc;
+
+/// Environment variable with the password of the keystore files
+const PASSWORD_ENV_VAR: &str = "ALGONAUT_SIGNER_PASSWORD";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let config_path = env::args()
+        .nth(1)
+        .ok_or("Usage: algonaut-signer <config.json>")?;
+    let config = Config::from_file(config_path)?;
+    let password = env::var(PASSWORD_ENV_VAR)
+        .map_err(|_| format!("Set the keystore password in {}", PASSWORD_ENV_VAR))?;
+
+    let service = algonaut_signer::service(&config, &password)?;
+    drop(password);
+
+    if !config.listen.ip().is_loopback() {
+        eprintln!(
+            "Warning: listening on non loopback address {}",
+            config.listen
+        );
+    }
+    let listener = TcpListener::bind(config.listen)?;
+    println!("Listening on {}", listener.local_addr()?);
+
+    serve(Arc::new(service), listener, async {
+        tokio::signal::ctrl_c().await.ok();
+    })
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,356 @@
+use algonaut_core::Address;
+use algonaut_transaction::{Transaction, TransactionType};
+use serde::{Deserialize, Deserializer};
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Transaction types, named like the `type` field of Algorand transactions.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize)]
+pub enum TxKind {
+    #[serde(rename = "pay")]
+    Payment,
+    #[serde(rename = "keyreg")]
+    KeyRegistration,
+    #[serde(rename = "acfg")]
+    AssetConfiguration,
+    /// Asset transfers, opt-ins and clawbacks
+    #[serde(rename = "axfer")]
+    AssetTransfer,
+    #[serde(rename = "afrz")]
+    AssetFreeze,
+    #[serde(rename = "appl")]
+    ApplicationCall,
+}
+
+impl From<&TransactionType> for TxKind {
+    fn from(txn_type: &TransactionType) -> Self {
+        match txn_type {
+            TransactionType::Payment(_) => TxKind::Payment,
+            TransactionType::KeyRegistration(_) => TxKind::KeyRegistration,
+            TransactionType::AssetConfigurationTransaction(_) => TxKind::AssetConfiguration,
+            TransactionType::AssetTransferTransaction(_)
+            | TransactionType::AssetAcceptTransaction(_)
+            | TransactionType::AssetClawbackTransaction(_) => TxKind::AssetTransfer,
+            TransactionType::AssetFreezeTransaction(_) => TxKind::AssetFreeze,
+            TransactionType::ApplicationCallTransaction(_) => TxKind::ApplicationCall,
+        }
+    }
+}
+
+/// At most `max_requests` signatures every `per_seconds` seconds.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimit {
+    pub max_requests: usize,
+    pub per_seconds: u64,
+}
+
+/// What the daemon is allowed to sign for an address. Fields which are not set don't restrict signing.
+///
+/// Unknown fields are rejected, so a misspelled limit doesn't silently remove it.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    pub allowed_types: Option<Vec<TxKind>>,
+    /// Max fee of any transaction, in microAlgos
+    #[serde(default)]
+    pub max_fee: Option<u64>,
+    /// Max payment amount, in microAlgos
+    #[serde(default)]
+    pub max_amount: Option<u64>,
+    /// Max asset transfer amount, in base units
+    #[serde(default)]
+    pub max_asset_amount: Option<u64>,
+    /// Receivers of payments and asset transfers, including close-to addresses
+    #[serde(default, deserialize_with = "deserialize_addresses")]
+    pub allowed_receivers: Option<Vec<Address>>,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+    /// Rekey transactions are rejected unless this is set.
+    #[serde(default)]
+    pub allow_rekey: bool,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum PolicyViolation {
+    #[error("No signing policy for address {0}.")]
+    NoPolicy(Address),
+    #[error("Transaction type {0:?} is not allowed.")]
+    TypeNotAllowed(TxKind),
+    #[error("Fee {fee} exceeds the max fee {max}.")]
+    FeeExceeded { fee: u64, max: u64 },
+    #[error("Amount {amount} exceeds the max amount {max}.")]
+    AmountExceeded { amount: u64, max: u64 },
+    #[error("Receiver {0} is not allowed.")]
+    ReceiverNotAllowed(Address),
+    #[error("Closing an account is not allowed when the amount is limited.")]
+    CloseNotAllowed,
+    #[error("Rekeying is not allowed.")]
+    RekeyNotAllowed,
+    #[error("Rate limit of {max_requests} signatures per {per_seconds} seconds exceeded.")]
+    RateLimited {
+        max_requests: usize,
+        per_seconds: u64,
+    },
+}
+
+impl Policy {
+    /// Checks the transaction's content against the policy. The rate limit is checked by [RateLimiter].
+    pub fn check(&self, transaction: &Transaction) -> Result<(), PolicyViolation> {
+        let kind = TxKind::from(&transaction.txn_type);
+        if let Some(allowed_types) = &self.allowed_types {
+            if !allowed_types.contains(&kind) {
+                return Err(PolicyViolation::TypeNotAllowed(kind));
+            }
+        }
+        if transaction.rekey_to.is_some() && !self.allow_rekey {
+            return Err(PolicyViolation::RekeyNotAllowed);
+        }
+        match self.max_fee {
+            Some(max) if transaction.fee.0 > max => {
+                return Err(PolicyViolation::FeeExceeded {
+                    fee: transaction.fee.0,
+                    max,
+                })
+            }
+            _ => {}
+        }
+
+        match &transaction.txn_type {
+            TransactionType::Payment(p) => {
+                check_max(p.amount.0, self.max_amount)?;
+                self.check_receiver(&p.receiver)?;
+                self.check_close(&p.close_remainder_to, self.max_amount)?;
+            }
+            TransactionType::AssetTransferTransaction(t) => {
+                check_max(t.amount, self.max_asset_amount)?;
+                self.check_receiver(&t.receiver)?;
+                self.check_close(&t.close_to, self.max_asset_amount)?;
+            }
+            TransactionType::AssetClawbackTransaction(t) => {
+                check_max(t.asset_amount, self.max_asset_amount)?;
+                self.check_receiver(&t.asset_receiver)?;
+                self.check_close(&t.asset_close_to, self.max_asset_amount)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn check_receiver(&self, receiver: &Address) -> Result<(), PolicyViolation> {
+        match &self.allowed_receivers {
+            Some(allowed) if !allowed.contains(receiver) => {
+                Err(PolicyViolation::ReceiverNotAllowed(*receiver))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Closing transfers the whole remaining balance, so it's only allowed without max amount
+    fn check_close(
+        &self,
+        close_to: &Option<Address>,
+        max: Option<u64>,
+    ) -> Result<(), PolicyViolation> {
+        match close_to {
+            Some(_) if max.is_some() => Err(PolicyViolation::CloseNotAllowed),
+            Some(close_to) => self.check_receiver(close_to),
+            None => Ok(()),
+        }
+    }
+}
+
+fn check_max(amount: u64, max: Option<u64>) -> Result<(), PolicyViolation> {
+    match max {
+        Some(max) if amount > max => Err(PolicyViolation::AmountExceeded { amount, max }),
+        _ => Ok(()),
+    }
+}
+
+/// Sliding window rate limiter, per address.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    requests: Mutex<HashMap<Address, VecDeque<Instant>>>,
+}
+
+impl RateLimiter {
+    /// Records a request for `address`, failing if it exceeds the limit.
+    pub fn check(&self, address: &Address, limit: &RateLimit) -> Result<(), PolicyViolation> {
+        self.check_at(address, limit, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        address: &Address,
+        limit: &RateLimit,
+        now: Instant,
+    ) -> Result<(), PolicyViolation> {
+        let mut requests = self.requests.lock().expect("Rate limiter lock poisoned");
+        let times = requests.entry(*address).or_default();
+        let window = Duration::from_secs(limit.per_seconds);
+        while let Some(time) = times.front() {
+            if now.duration_since(*time) < window {
+                break;
+            }
+            times.pop_front();
+        }
+        if times.len() >= limit.max_requests {
+            return Err(PolicyViolation::RateLimited {
+                max_requests: limit.max_requests,
+                per_seconds: limit.per_seconds,
+            });
+        }
+        times.push_back(now);
+        Ok(())
+    }
+}
+
+fn deserialize_addresses<'de, D>(deserializer: D) -> Result<Option<Vec<Address>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Error;
+    <Option<Vec<String>>>::deserialize(deserializer)?
+        .map(|addresses| {
+            addresses
+                .iter()
+                .map(|a| Address::from_str(a).map_err(D::Error::custom))
+                .collect()
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algonaut_core::{MicroAlgos, Round};
+    use algonaut_crypto::HashDigest;
+    use algonaut_transaction::{Pay, TransferAsset, TxnBuilder};
+
+    fn txn(txn_type: TransactionType) -> Transaction {
+        TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1000),
+            HashDigest([0; 32]),
+            txn_type,
+        )
+        .build()
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let policy: Policy = serde_json::from_str(
+            r#"{
+                "allowed_types": ["pay", "axfer"],
+                "max_amount": 1000000,
+                "allowed_receivers": ["DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA"],
+                "rate_limit": {"max_requests": 10, "per_seconds": 60}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Some(vec![TxKind::Payment, TxKind::AssetTransfer]),
+            policy.allowed_types
+        );
+        assert_eq!(1, policy.allowed_receivers.unwrap().len());
+        assert!(!policy.allow_rekey);
+    }
+
+    #[test]
+    fn test_check_payment() {
+        let receiver = Address([2; 32]);
+        let policy = Policy {
+            allowed_types: Some(vec![TxKind::Payment]),
+            max_amount: Some(1000),
+            allowed_receivers: Some(vec![receiver]),
+            ..Policy::default()
+        };
+
+        let pay = |to, amount| txn(Pay::new(Address([1; 32]), to, MicroAlgos(amount)).build());
+        assert_eq!(Ok(()), policy.check(&pay(receiver, 1000)));
+        assert_eq!(
+            Err(PolicyViolation::AmountExceeded {
+                amount: 1001,
+                max: 1000
+            }),
+            policy.check(&pay(receiver, 1001))
+        );
+        assert_eq!(
+            Err(PolicyViolation::ReceiverNotAllowed(Address([3; 32]))),
+            policy.check(&pay(Address([3; 32]), 1))
+        );
+
+        let close = txn(Pay::new(Address([1; 32]), receiver, MicroAlgos(1))
+            .close_remainder_to(receiver)
+            .build());
+        assert_eq!(Err(PolicyViolation::CloseNotAllowed), policy.check(&close));
+
+        let asset = txn(TransferAsset::new(Address([1; 32]), 1, 1, receiver).build());
+        assert_eq!(
+            Err(PolicyViolation::TypeNotAllowed(TxKind::AssetTransfer)),
+            policy.check(&asset)
+        );
+    }
+
+    #[test]
+    fn test_parse_policy_unknown_field() {
+        assert!(serde_json::from_str::<Policy>(r#"{"max_amout": 1000}"#).is_err());
+    }
+
+    #[test]
+    fn test_check_fee() {
+        let policy = Policy {
+            max_fee: Some(2000),
+            ..Policy::default()
+        };
+        let mut t = txn(Pay::new(Address([1; 32]), Address([2; 32]), MicroAlgos(1)).build());
+        assert_eq!(Ok(()), policy.check(&t));
+
+        t.fee = MicroAlgos(1_000_000);
+        assert_eq!(
+            Err(PolicyViolation::FeeExceeded {
+                fee: 1_000_000,
+                max: 2000
+            }),
+            policy.check(&t)
+        );
+
+        let mut asset = txn(TransferAsset::new(Address([1; 32]), 1, 1, Address([2; 32])).build());
+        asset.fee = MicroAlgos(2001);
+        assert!(policy.check(&asset).is_err());
+    }
+
+    #[test]
+    fn test_rekey_not_allowed() {
+        let mut t = txn(Pay::new(Address([1; 32]), Address([2; 32]), MicroAlgos(1)).build());
+        t.rekey_to = Some(Address([3; 32]));
+        assert_eq!(
+            Err(PolicyViolation::RekeyNotAllowed),
+            Policy::default().check(&t)
+        );
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let limiter = RateLimiter::default();
+        let limit = RateLimit {
+            max_requests: 2,
+            per_seconds: 10,
+        };
+        let address = Address([1; 32]);
+        let start = Instant::now();
+
+        assert!(limiter.check_at(&address, &limit, start).is_ok());
+        assert!(limiter.check_at(&address, &limit, start).is_ok());
+        assert!(limiter.check_at(&address, &limit, start).is_err());
+        assert!(limiter.check_at(&Address([2; 32]), &limit, start).is_ok());
+        assert!(limiter
+            .check_at(&address, &limit, start + Duration::from_secs(10))
+            .is_ok());
+    }
+}
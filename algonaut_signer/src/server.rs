@@ -0,0 +1,435 @@
+use crate::audit::{AuditEntry, AuditLog, Outcome};
+use crate::error::SignerError;
+use crate::policy::{Policy, PolicyViolation, RateLimiter};
+use algonaut_core::{Address, ToMsgPack};
+use algonaut_transaction::account::Account;
+use algonaut_transaction::Transaction;
+use hyper::body::HttpBody;
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::TcpListener;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+/// Header with the API token, if the daemon is configured with one.
+pub const API_TOKEN_HEADER: &str = "X-Signer-API-Token";
+
+/// Max size of a request body. Transactions with the largest programs are well below it.
+pub const MAX_BODY_SIZE: usize = 64 * 1024;
+
+const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// Signs transactions with the loaded accounts, enforcing the policy of the sender address.
+///
+/// Endpoints:
+/// - `GET /v1/addresses`: JSON `{"addresses": [...]}` with the addresses the daemon can sign for.
+/// - `POST /v1/transaction/sign`: the body is a msgpack encoded transaction, the response
+///   the msgpack encoded signed transaction, ready to be broadcast.
+///
+/// Errors are returned as JSON `{"message": "..."}`.
+/// Every signing request is recorded in the audit log. If recording fails, the request fails.
+#[derive(Debug)]
+pub struct SigningService {
+    accounts: HashMap<Address, Account>,
+    policies: HashMap<Address, Policy>,
+    rate_limiter: RateLimiter,
+    audit_log: AuditLog,
+    api_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AddressesResponse {
+    addresses: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl ToString) -> ApiError {
+        ApiError {
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<PolicyViolation> for ApiError {
+    fn from(violation: PolicyViolation) -> Self {
+        let status = match violation {
+            PolicyViolation::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            _ => StatusCode::FORBIDDEN,
+        };
+        ApiError::new(status, violation)
+    }
+}
+
+impl SigningService {
+    pub fn new(
+        accounts: Vec<Account>,
+        policies: HashMap<Address, Policy>,
+        audit_log: AuditLog,
+        api_token: Option<String>,
+    ) -> SigningService {
+        SigningService {
+            accounts: accounts.into_iter().map(|a| (a.address(), a)).collect(),
+            policies,
+            rate_limiter: RateLimiter::default(),
+            audit_log,
+            api_token,
+        }
+    }
+
+    pub async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        match self.route(request).await {
+            Ok(response) => response,
+            Err(e) => json_response(e.status, &ErrorResponse { message: e.message }),
+        }
+    }
+
+    async fn route(&self, request: Request<Body>) -> Result<Response<Body>, ApiError> {
+        self.authorize(&request)?;
+        match (request.method(), request.uri().path()) {
+            (&Method::GET, "/v1/addresses") => {
+                let mut addresses: Vec<String> =
+                    self.accounts.keys().map(|a| a.to_string()).collect();
+                addresses.sort();
+                Ok(json_response(
+                    StatusCode::OK,
+                    &AddressesResponse { addresses },
+                ))
+            }
+            (&Method::POST, "/v1/transaction/sign") => {
+                let body = read_body(request.into_body(), MAX_BODY_SIZE).await?;
+                let signed = self.sign(&body)?;
+                let mut response = Response::new(Body::from(signed));
+                response
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static(MSGPACK_CONTENT_TYPE));
+                Ok(response)
+            }
+            _ => Err(ApiError::new(StatusCode::NOT_FOUND, "Not found")),
+        }
+    }
+
+    fn authorize(&self, request: &Request<Body>) -> Result<(), ApiError> {
+        if let Some(token) = &self.api_token {
+            let header = request.headers().get(API_TOKEN_HEADER);
+            let valid = header
+                .map(|h| bool::from(h.as_bytes().ct_eq(token.as_bytes())))
+                .unwrap_or(false);
+            if !valid {
+                return Err(ApiError::new(
+                    StatusCode::UNAUTHORIZED,
+                    "Missing or invalid API token",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn sign(&self, body: &[u8]) -> Result<Vec<u8>, ApiError> {
+        let transaction: Transaction = rmp_serde::from_slice(body).map_err(|e| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid transaction: {}", e),
+            )
+        })?;
+        let address = transaction.sender();
+        let transaction_id = transaction
+            .id()
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+
+        let result = self.check(&address, &transaction).and_then(|account| {
+            account
+                .sign_transaction(&transaction)
+                .and_then(|signed| Ok(signed.to_msg_pack()?))
+                .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e))
+        });
+
+        let outcome = match &result {
+            Ok(_) => Outcome::Signed,
+            Err(e) => Outcome::Rejected {
+                reason: e.message.clone(),
+            },
+        };
+        self.audit_log
+            .record(&AuditEntry::new(&address, &transaction_id, outcome))
+            .map_err(|e| {
+                ApiError::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Audit log error: {}", e),
+                )
+            })?;
+        result
+    }
+
+    fn check(&self, address: &Address, transaction: &Transaction) -> Result<&Account, ApiError> {
+        let account = self.accounts.get(address).ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                format!("Unknown address {}", address),
+            )
+        })?;
+        let policy = self
+            .policies
+            .get(address)
+            .ok_or(PolicyViolation::NoPolicy(*address))?;
+        policy.check(transaction)?;
+        if let Some(limit) = &policy.rate_limit {
+            self.rate_limiter.check(address, limit)?;
+        }
+        Ok(account)
+    }
+}
+
+/// Serves the API on `listener` until `shutdown` completes.
+pub async fn serve(
+    service: Arc<SigningService>,
+    listener: TcpListener,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), SignerError> {
+    let make_service = make_service_fn(move |_| {
+        let service = service.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let service = service.clone();
+                async move { Ok::<_, Infallible>(service.handle(request).await) }
+            }))
+        }
+    });
+    Server::from_tcp(listener)?
+        .serve(make_service)
+        .with_graceful_shutdown(shutdown)
+        .await?;
+    Ok(())
+}
+
+/// Reads the body, failing as soon as it exceeds `limit` bytes.
+async fn read_body(mut body: Body, limit: usize) -> Result<Vec<u8>, ApiError> {
+    let mut bytes = vec![];
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+        if bytes.len() + chunk.len() > limit {
+            return Err(ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("The request body exceeds {} bytes", limit),
+            ));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> Response<Body> {
+    let mut response = Response::new(Body::from(
+        serde_json::to_vec(body).expect("Response serialization can't fail"),
+    ));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(JSON_CONTENT_TYPE));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{RateLimit, TxKind};
+    use algonaut_core::{MicroAlgos, Round};
+    use algonaut_crypto::HashDigest;
+    use algonaut_transaction::{Pay, SignedTransaction, TxnBuilder};
+    use hyper::Client;
+    use tokio::sync::oneshot;
+
+    struct TestServer {
+        url: String,
+        audit_log_path: std::path::PathBuf,
+        shutdown: oneshot::Sender<()>,
+    }
+
+    async fn start(
+        account: &Account,
+        policy: Option<Policy>,
+        api_token: Option<&str>,
+    ) -> TestServer {
+        let audit_log_path = std::env::temp_dir().join(format!(
+            "algonaut-signer-audit-{}.log",
+            Account::generate().address()
+        ));
+        let policies = policy.into_iter().map(|p| (account.address(), p)).collect();
+        let service = SigningService::new(
+            vec![Account::from_seed(account.seed().unwrap().expose_secret())],
+            policies,
+            AuditLog::open(&audit_log_path).unwrap(),
+            api_token.map(|t| t.to_owned()),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (shutdown, receiver) = oneshot::channel::<()>();
+        tokio::spawn(serve(Arc::new(service), listener, async {
+            receiver.await.ok();
+        }));
+
+        TestServer {
+            url,
+            audit_log_path,
+            shutdown,
+        }
+    }
+
+    fn payment(sender: Address, amount: u64) -> Transaction {
+        TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1000),
+            HashDigest([0; 32]),
+            Pay::new(sender, Address([2; 32]), MicroAlgos(amount)).build(),
+        )
+        .build()
+    }
+
+    async fn post_sign(
+        server: &TestServer,
+        transaction: &Transaction,
+        token: Option<&str>,
+    ) -> (StatusCode, Vec<u8>) {
+        let mut request = Request::post(format!("{}/v1/transaction/sign", server.url));
+        if let Some(token) = token {
+            request = request.header(API_TOKEN_HEADER, token);
+        }
+        let response = Client::new()
+            .request(
+                request
+                    .body(Body::from(rmp_serde::to_vec_named(transaction).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_sign() {
+        let account = Account::generate();
+        let policy = Policy {
+            allowed_types: Some(vec![TxKind::Payment]),
+            max_amount: Some(1000),
+            ..Policy::default()
+        };
+        let server = start(&account, Some(policy), None).await;
+
+        let t = payment(account.address(), 1000);
+        let (status, body) = post_sign(&server, &t, None).await;
+        assert_eq!(StatusCode::OK, status);
+        let signed: SignedTransaction = rmp_serde::from_slice(&body).unwrap();
+        let expected = account.sign_transaction(&t).unwrap();
+        assert_eq!(expected.transaction, signed.transaction);
+        assert_eq!(expected.sig, signed.sig);
+
+        let (status, _) = post_sign(&server, &payment(account.address(), 1001), None).await;
+        assert_eq!(StatusCode::FORBIDDEN, status);
+
+        let (status, _) = post_sign(&server, &payment(Address([9; 32]), 1), None).await;
+        assert_eq!(StatusCode::NOT_FOUND, status);
+
+        let audit_log = std::fs::read_to_string(&server.audit_log_path).unwrap();
+        let lines: Vec<&str> = audit_log.lines().collect();
+        assert_eq!(3, lines.len());
+        assert!(lines[0].contains("\"signed\""));
+        assert!(lines[1].contains("\"rejected\""));
+
+        server.shutdown.send(()).unwrap();
+        std::fs::remove_file(server.audit_log_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_no_policy_and_rate_limit() {
+        let account = Account::generate();
+        let server = start(&account, None, None).await;
+        let (status, _) = post_sign(&server, &payment(account.address(), 1), None).await;
+        assert_eq!(StatusCode::FORBIDDEN, status);
+        server.shutdown.send(()).unwrap();
+        std::fs::remove_file(server.audit_log_path).unwrap();
+
+        let policy = Policy {
+            rate_limit: Some(RateLimit {
+                max_requests: 1,
+                per_seconds: 3600,
+            }),
+            ..Policy::default()
+        };
+        let server = start(&account, Some(policy), None).await;
+        let (status, _) = post_sign(&server, &payment(account.address(), 1), None).await;
+        assert_eq!(StatusCode::OK, status);
+        let (status, _) = post_sign(&server, &payment(account.address(), 2), None).await;
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, status);
+        server.shutdown.send(()).unwrap();
+        std::fs::remove_file(server.audit_log_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_api_token_and_addresses() {
+        let account = Account::generate();
+        let server = start(&account, Some(Policy::default()), Some("token")).await;
+
+        let (status, _) = post_sign(&server, &payment(account.address(), 1), None).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, status);
+        let (status, _) = post_sign(&server, &payment(account.address(), 1), Some("token")).await;
+        assert_eq!(StatusCode::OK, status);
+
+        let response = Client::new()
+            .request(
+                Request::get(format!("{}/v1/addresses", server.url))
+                    .header(API_TOKEN_HEADER, "token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(
+            format!("{{\"addresses\":[\"{}\"]}}", account.address()),
+            String::from_utf8(body.to_vec()).unwrap()
+        );
+
+        server.shutdown.send(()).unwrap();
+        std::fs::remove_file(server.audit_log_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_body_too_large() {
+        let account = Account::generate();
+        let server = start(&account, Some(Policy::default()), None).await;
+
+        let response = Client::new()
+            .request(
+                Request::post(format!("{}/v1/transaction/sign", server.url))
+                    .body(Body::from(vec![0; MAX_BODY_SIZE + 1]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+
+        server.shutdown.send(()).unwrap();
+        std::fs::remove_file(server.audit_log_path).unwrap();
+    }
+}
@@ -0,0 +1,508 @@
+use super::AbiValue;
+use crate::error::TransactionError;
+use algonaut_core::Address;
+use algonaut_encoding::split_abi_tuple;
+use num_bigint::BigUint;
+use std::convert::TryInto;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Max length of dynamic values and offsets, which are encoded as uint16.
+const MAX_DYNAMIC_LEN: usize = u16::MAX as usize;
+
+/// A type of the ARC-4 ABI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbiType {
+    /// `uint<N>`: unsigned integer of N bits, with N a multiple of 8 between 8 and 512.
+    Uint(u16),
+    /// `ufixed<N>x<M>`: unsigned fixed point decimal of N bits and precision M, between 1 and 160.
+    Ufixed(u16, u8),
+    Bool,
+    Byte,
+    Address,
+    /// Dynamic array of UTF-8 encoded bytes.
+    String,
+    /// `<type>[<N>]`
+    StaticArray(Box<AbiType>, usize),
+    /// `<type>[]`
+    DynamicArray(Box<AbiType>),
+    /// `(<type 1>,...,<type N>)`
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    /// Whether the encoded length of the type depends on the value.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiType::String | AbiType::DynamicArray(_) => true,
+            AbiType::StaticArray(t, _) => t.is_dynamic(),
+            AbiType::Tuple(types) => types.iter().any(|t| t.is_dynamic()),
+            _ => false,
+        }
+    }
+
+    /// Encoded length of a static type. None for dynamic types.
+    pub fn static_len(&self) -> Option<usize> {
+        match self {
+            AbiType::Uint(bits) | AbiType::Ufixed(bits, _) => Some(*bits as usize / 8),
+            AbiType::Bool | AbiType::Byte => Some(1),
+            AbiType::Address => Some(32),
+            AbiType::String | AbiType::DynamicArray(_) => None,
+            AbiType::StaticArray(t, len) => match **t {
+                AbiType::Bool => Some((len + 7) / 8),
+                _ => Some(t.static_len()? * len),
+            },
+            AbiType::Tuple(types) => {
+                let mut len = 0;
+                let mut i = 0;
+                while i < types.len() {
+                    if types[i] == AbiType::Bool {
+                        i += consecutive_bools(&types[i..]);
+                        len += 1;
+                    } else {
+                        len += types[i].static_len()?;
+                        i += 1;
+                    }
+                }
+                Some(len)
+            }
+        }
+    }
+
+    pub fn encode(&self, value: &AbiValue) -> Result<Vec<u8>, TransactionError> {
+        match (self, value) {
+            (AbiType::Uint(bits), AbiValue::Uint(uint))
+            | (AbiType::Ufixed(bits, _), AbiValue::Uint(uint)) => encode_uint(*bits, uint),
+            (AbiType::Bool, AbiValue::Bool(b)) => Ok(vec![if *b { 0x80 } else { 0 }]),
+            (AbiType::Byte, AbiValue::Byte(b)) => Ok(vec![*b]),
+            // Allows to pass u8 for both `byte` and `uint8`
+            (AbiType::Byte, AbiValue::Uint(uint)) => encode_uint(8, uint),
+            (AbiType::Address, AbiValue::Address(address)) => Ok(address.0.to_vec()),
+            (AbiType::String, AbiValue::String(string)) => {
+                let bytes = string.as_bytes();
+                Ok([encode_len(bytes.len())?.as_slice(), bytes].concat())
+            }
+            (AbiType::StaticArray(t, len), AbiValue::Array(values)) => {
+                if values.len() != *len {
+                    return Err(TransactionError::Abi(format!(
+                        "Expected {} values for {}, got {}",
+                        len,
+                        self,
+                        values.len()
+                    )));
+                }
+                encode_tuple(&vec![(**t).clone(); *len], values)
+            }
+            (AbiType::DynamicArray(t), AbiValue::Array(values)) => Ok([
+                encode_len(values.len())?,
+                encode_tuple(&vec![(**t).clone(); values.len()], values)?,
+            ]
+            .concat()),
+            (AbiType::Tuple(types), AbiValue::Tuple(values)) => {
+                if values.len() != types.len() {
+                    return Err(TransactionError::Abi(format!(
+                        "Expected {} values for {}, got {}",
+                        types.len(),
+                        self,
+                        values.len()
+                    )));
+                }
+                encode_tuple(types, values)
+            }
+            _ => Err(TransactionError::Abi(format!(
+                "Can't encode {:?} as {}",
+                value, self
+            ))),
+        }
+    }
+
+    /// Decodes a value of this type. The bytes have to contain exactly one encoded value.
+    pub fn decode(&self, bytes: &[u8]) -> Result<AbiValue, TransactionError> {
+        if let Some(len) = self.static_len() {
+            if bytes.len() != len {
+                return Err(TransactionError::Abi(format!(
+                    "Expected {} bytes for {}, got {}",
+                    len,
+                    self,
+                    bytes.len()
+                )));
+            }
+        }
+        match self {
+            AbiType::Uint(_) | AbiType::Ufixed(_, _) => {
+                Ok(AbiValue::Uint(BigUint::from_bytes_be(bytes)))
+            }
+            AbiType::Bool => match bytes[0] {
+                0x80 => Ok(AbiValue::Bool(true)),
+                0 => Ok(AbiValue::Bool(false)),
+                b => Err(TransactionError::Abi(format!("Invalid bool: {:#04x}", b))),
+            },
+            AbiType::Byte => Ok(AbiValue::Byte(bytes[0])),
+            AbiType::Address => Ok(AbiValue::Address(Address(
+                bytes.try_into().expect("Length checked above"),
+            ))),
+            AbiType::String => {
+                let (len, bytes) = decode_len(bytes)?;
+                if bytes.len() != len {
+                    return Err(TransactionError::Abi(format!(
+                        "Expected {} bytes for string, got {}",
+                        len,
+                        bytes.len()
+                    )));
+                }
+                Ok(AbiValue::String(
+                    String::from_utf8(bytes.to_vec())
+                        .map_err(|e| TransactionError::Abi(format!("Invalid string: {}", e)))?,
+                ))
+            }
+            AbiType::StaticArray(t, len) => Ok(AbiValue::Array(decode_tuple(
+                &vec![(**t).clone(); *len],
+                bytes,
+            )?)),
+            AbiType::DynamicArray(t) => {
+                let (len, bytes) = decode_len(bytes)?;
+                Ok(AbiValue::Array(decode_tuple(
+                    &vec![(**t).clone(); len],
+                    bytes,
+                )?))
+            }
+            AbiType::Tuple(types) => Ok(AbiValue::Tuple(decode_tuple(types, bytes)?)),
+        }
+    }
+}
+
+/// Number of bools starting at the beginning of `types`, which are packed in one byte (max 8).
+fn consecutive_bools(types: &[AbiType]) -> usize {
+    types
+        .iter()
+        .take(8)
+        .take_while(|t| **t == AbiType::Bool)
+        .count()
+}
+
+fn encode_uint(bits: u16, uint: &BigUint) -> Result<Vec<u8>, TransactionError> {
+    if uint.bits() > bits as u64 {
+        return Err(TransactionError::Abi(format!(
+            "{} doesn't fit in {} bits",
+            uint, bits
+        )));
+    }
+    let bytes = uint.to_bytes_be();
+    let len = bits as usize / 8;
+    // to_bytes_be returns [0] for zero
+    let bytes = if *uint == BigUint::default() {
+        vec![]
+    } else {
+        bytes
+    };
+    Ok([vec![0; len - bytes.len()], bytes].concat())
+}
+
+fn encode_len(len: usize) -> Result<Vec<u8>, TransactionError> {
+    if len > MAX_DYNAMIC_LEN {
+        return Err(TransactionError::Abi(format!(
+            "Length {} exceeds the max of {}",
+            len, MAX_DYNAMIC_LEN
+        )));
+    }
+    Ok((len as u16).to_be_bytes().to_vec())
+}
+
+fn decode_len(bytes: &[u8]) -> Result<(usize, &[u8]), TransactionError> {
+    if bytes.len() < 2 {
+        return Err(TransactionError::Abi(
+            "Missing length of dynamic value".to_owned(),
+        ));
+    }
+    Ok((
+        u16::from_be_bytes([bytes[0], bytes[1]]) as usize,
+        &bytes[2..],
+    ))
+}
+
+/// Encodes the values of a tuple or array: the static values and the offsets of the dynamic ones,
+/// followed by the dynamic values. Consecutive bools are packed in one byte.
+fn encode_tuple(types: &[AbiType], values: &[AbiValue]) -> Result<Vec<u8>, TransactionError> {
+    // Head of each element, None for the offset of dynamic values, which is set once the head length is known
+    let mut heads: Vec<Option<Vec<u8>>> = vec![];
+    let mut tails: Vec<Vec<u8>> = vec![];
+    let mut i = 0;
+    while i < types.len() {
+        if types[i] == AbiType::Bool {
+            let count = consecutive_bools(&types[i..]);
+            let mut byte = 0u8;
+            for (bit, value) in values[i..i + count].iter().enumerate() {
+                match value {
+                    AbiValue::Bool(true) => byte |= 0x80 >> bit,
+                    AbiValue::Bool(false) => {}
+                    _ => {
+                        return Err(TransactionError::Abi(format!(
+                            "Can't encode {:?} as bool",
+                            value
+                        )))
+                    }
+                }
+            }
+            heads.push(Some(vec![byte]));
+            tails.push(vec![]);
+            i += count;
+        } else if types[i].is_dynamic() {
+            heads.push(None);
+            tails.push(types[i].encode(&values[i])?);
+            i += 1;
+        } else {
+            heads.push(Some(types[i].encode(&values[i])?));
+            tails.push(vec![]);
+            i += 1;
+        }
+    }
+
+    let head_len: usize = heads
+        .iter()
+        .map(|head| head.as_ref().map(|h| h.len()).unwrap_or(2))
+        .sum();
+    let mut offset = head_len;
+    let mut encoded = vec![];
+    for (head, tail) in heads.iter().zip(&tails) {
+        match head {
+            Some(head) => encoded.extend_from_slice(head),
+            None => {
+                encoded.extend(encode_len(offset)?);
+                offset += tail.len();
+            }
+        }
+    }
+    for tail in tails {
+        encoded.extend(tail);
+    }
+    Ok(encoded)
+}
+
+fn decode_tuple(types: &[AbiType], bytes: &[u8]) -> Result<Vec<AbiValue>, TransactionError> {
+    let truncated = || TransactionError::Abi("Encoded tuple is truncated".to_owned());
+
+    let mut values: Vec<Option<AbiValue>> = vec![None; types.len()];
+    // Index and offset of the dynamic values
+    let mut dynamic: Vec<(usize, usize)> = vec![];
+    let mut pos = 0;
+    let mut i = 0;
+    while i < types.len() {
+        if types[i] == AbiType::Bool {
+            let count = consecutive_bools(&types[i..]);
+            let byte = *bytes.get(pos).ok_or_else(truncated)?;
+            for bit in 0..count {
+                values[i + bit] = Some(AbiValue::Bool(byte & (0x80 >> bit) != 0));
+            }
+            pos += 1;
+            i += count;
+        } else if types[i].is_dynamic() {
+            let (offset, _) = decode_len(bytes.get(pos..).ok_or_else(truncated)?)?;
+            dynamic.push((i, offset));
+            pos += 2;
+            i += 1;
+        } else {
+            let len = types[i].static_len().expect("Static type");
+            let value_bytes = bytes.get(pos..pos + len).ok_or_else(truncated)?;
+            values[i] = Some(types[i].decode(value_bytes)?);
+            pos += len;
+            i += 1;
+        }
+    }
+
+    let mut end_of_previous = pos;
+    for (n, (index, offset)) in dynamic.iter().enumerate() {
+        let end = dynamic
+            .get(n + 1)
+            .map(|(_, next)| *next)
+            .unwrap_or_else(|| bytes.len());
+        if *offset != end_of_previous || end < *offset || end > bytes.len() {
+            return Err(TransactionError::Abi(format!(
+                "Invalid offset of dynamic value: {}",
+                offset
+            )));
+        }
+        values[*index] = Some(types[*index].decode(&bytes[*offset..end])?);
+        end_of_previous = end;
+    }
+    if end_of_previous != bytes.len() {
+        return Err(TransactionError::Abi(format!(
+            "Unexpected {} bytes after the encoded tuple",
+            bytes.len() - end_of_previous
+        )));
+    }
+
+    Ok(values
+        .into_iter()
+        .map(|v| v.expect("All the values are decoded"))
+        .collect())
+}
+
+impl Display for AbiType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AbiType::Uint(bits) => write!(f, "uint{}", bits),
+            AbiType::Ufixed(bits, precision) => write!(f, "ufixed{}x{}", bits, precision),
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::Byte => write!(f, "byte"),
+            AbiType::Address => write!(f, "address"),
+            AbiType::String => write!(f, "string"),
+            AbiType::StaticArray(t, len) => write!(f, "{}[{}]", t, len),
+            AbiType::DynamicArray(t) => write!(f, "{}[]", t),
+            AbiType::Tuple(types) => write!(
+                f,
+                "({})",
+                types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl FromStr for AbiType {
+    type Err = TransactionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TransactionError::Abi(format!("Invalid ABI type: {}", s));
+
+        if let Some(element) = s.strip_suffix("[]") {
+            return Ok(AbiType::DynamicArray(Box::new(element.parse()?)));
+        }
+        if let Some(rest) = s.strip_suffix(']') {
+            let open = rest.rfind('[').ok_or_else(invalid)?;
+            let len = rest[open + 1..].parse().map_err(|_| invalid())?;
+            return Ok(AbiType::StaticArray(Box::new(rest[..open].parse()?), len));
+        }
+        if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+            return Ok(AbiType::Tuple(
+                split_abi_tuple(inner)
+                    .ok_or_else(invalid)?
+                    .into_iter()
+                    .map(|t| t.parse())
+                    .collect::<Result<_, _>>()?,
+            ));
+        }
+        match s {
+            "bool" => return Ok(AbiType::Bool),
+            "byte" => return Ok(AbiType::Byte),
+            "address" => return Ok(AbiType::Address),
+            "string" => return Ok(AbiType::String),
+            _ => {}
+        }
+        if let Some(bits) = s.strip_prefix("uint") {
+            let bits: u16 = bits.parse().map_err(|_| invalid())?;
+            if bits % 8 != 0 || !(8..=512).contains(&bits) {
+                return Err(invalid());
+            }
+            return Ok(AbiType::Uint(bits));
+        }
+        if let Some(params) = s.strip_prefix("ufixed") {
+            let (bits, precision) = params.split_once('x').ok_or_else(invalid)?;
+            let bits: u16 = bits.parse().map_err(|_| invalid())?;
+            let precision: u8 = precision.parse().map_err(|_| invalid())?;
+            if bits % 8 != 0 || !(8..=512).contains(&bits) || !(1..=160).contains(&precision) {
+                return Err(invalid());
+            }
+            return Ok(AbiType::Ufixed(bits, precision));
+        }
+        Err(invalid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uint(n: u64) -> AbiValue {
+        AbiValue::Uint(BigUint::from(n))
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for s in &[
+            "uint64",
+            "ufixed128x10",
+            "bool",
+            "byte[]",
+            "address[3]",
+            "string",
+            "(uint64,(bool,string)[],byte[32])",
+            "()",
+            "(uint8,bool[2])[][4]",
+        ] {
+            assert_eq!(*s, s.parse::<AbiType>().unwrap().to_string());
+        }
+        assert_eq!(
+            AbiType::StaticArray(Box::new(AbiType::DynamicArray(Box::new(AbiType::Byte))), 2),
+            "byte[][2]".parse().unwrap()
+        );
+        for s in &[
+            "uint7",
+            "uint0",
+            "uint520",
+            "ufixed64x0",
+            "(uint64",
+            "uint64,bool",
+            "foo",
+            "byte[x]",
+        ] {
+            assert!(s.parse::<AbiType>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_encode_static() {
+        let t: AbiType = "(uint16,bool,bool,byte,address)".parse().unwrap();
+        let value = AbiValue::Tuple(vec![
+            uint(258),
+            AbiValue::Bool(true),
+            AbiValue::Bool(true),
+            AbiValue::Byte(7),
+            AbiValue::Address(Address([9; 32])),
+        ]);
+        let encoded = t.encode(&value).unwrap();
+        assert_eq!([&[1, 2, 0xc0, 7][..], &[9; 32][..]].concat(), encoded);
+        assert_eq!(Some(encoded.len()), t.static_len());
+        assert_eq!(value, t.decode(&encoded).unwrap());
+
+        assert!(AbiType::Uint(8).encode(&uint(256)).is_err());
+        assert_eq!(vec![0, 0], AbiType::Uint(16).encode(&uint(0)).unwrap());
+    }
+
+    #[test]
+    fn test_encode_dynamic() {
+        // Example of the ARC-4 specification
+        let t: AbiType = "(uint16,string,bool[],bool)".parse().unwrap();
+        let value = AbiValue::Tuple(vec![
+            uint(3),
+            AbiValue::String("abc".to_owned()),
+            AbiValue::Array(vec![
+                AbiValue::Bool(true),
+                AbiValue::Bool(false),
+                AbiValue::Bool(true),
+            ]),
+            AbiValue::Bool(true),
+        ]);
+        let encoded = t.encode(&value).unwrap();
+        assert_eq!(
+            vec![0, 3, 0, 7, 0, 12, 0x80, 0, 3, b'a', b'b', b'c', 0, 3, 0xa0],
+            encoded
+        );
+        assert_eq!(value, t.decode(&encoded).unwrap());
+        assert_eq!(None, t.static_len());
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let t: AbiType = "(uint16,string)".parse().unwrap();
+        // Truncated string
+        assert!(t.decode(&[0, 3, 0, 4, 0, 3, b'a']).is_err());
+        // Wrong offset
+        assert!(t.decode(&[0, 3, 0, 5, 0, 1, b'a']).is_err());
+        // Trailing bytes
+        assert!(AbiType::String.decode(&[0, 1, b'a', b'b']).is_err());
+        assert!(AbiType::Bool.decode(&[1]).is_err());
+    }
+}
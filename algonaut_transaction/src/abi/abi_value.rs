@@ -0,0 +1,223 @@
+use crate::error::TransactionError;
+use algonaut_core::Address;
+use num_bigint::BigUint;
+use std::convert::{TryFrom, TryInto};
+
+/// A value of an [super::AbiType].
+///
+/// `ufixed` values are represented with their unscaled uint, like in the encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbiValue {
+    Uint(BigUint),
+    Bool(bool),
+    Byte(u8),
+    Address(Address),
+    String(String),
+    /// Elements of a static or dynamic array.
+    Array(Vec<AbiValue>),
+    Tuple(Vec<AbiValue>),
+}
+
+/// A Rust value which can be passed as an ABI argument.
+pub trait ToAbiValue {
+    fn to_abi_value(&self) -> AbiValue;
+}
+
+/// A Rust value which can be decoded from an ABI value, e.g. the return value of a method.
+pub trait FromAbiValue: Sized {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError>;
+}
+
+fn unexpected<T>(value: &AbiValue, expected: &str) -> Result<T, TransactionError> {
+    Err(TransactionError::Abi(format!(
+        "Expected {}, got {:?}",
+        expected, value
+    )))
+}
+
+impl ToAbiValue for AbiValue {
+    fn to_abi_value(&self) -> AbiValue {
+        self.clone()
+    }
+}
+
+impl FromAbiValue for AbiValue {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+        Ok(value)
+    }
+}
+
+macro_rules! impl_abi_value_uint {
+    ($($t:ty),*) => {
+        $(
+            impl ToAbiValue for $t {
+                fn to_abi_value(&self) -> AbiValue {
+                    AbiValue::Uint(BigUint::from(*self))
+                }
+            }
+
+            impl FromAbiValue for $t {
+                fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+                    match &value {
+                        AbiValue::Uint(uint) => uint.try_into().or_else(|_| unexpected(&value, stringify!($t))),
+                        AbiValue::Byte(byte) => Ok(*byte as $t),
+                        _ => unexpected(&value, stringify!($t)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_abi_value_uint!(u8, u16, u32, u64, u128);
+
+impl ToAbiValue for BigUint {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::Uint(self.clone())
+    }
+}
+
+impl FromAbiValue for BigUint {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+        match value {
+            AbiValue::Uint(uint) => Ok(uint),
+            AbiValue::Byte(byte) => Ok(BigUint::from(byte)),
+            _ => unexpected(&value, "uint"),
+        }
+    }
+}
+
+impl ToAbiValue for bool {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::Bool(*self)
+    }
+}
+
+impl FromAbiValue for bool {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+        match value {
+            AbiValue::Bool(b) => Ok(b),
+            _ => unexpected(&value, "bool"),
+        }
+    }
+}
+
+impl ToAbiValue for Address {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::Address(*self)
+    }
+}
+
+impl FromAbiValue for Address {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+        match value {
+            AbiValue::Address(address) => Ok(address),
+            _ => unexpected(&value, "address"),
+        }
+    }
+}
+
+impl ToAbiValue for String {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::String(self.clone())
+    }
+}
+
+impl ToAbiValue for &str {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::String((*self).to_owned())
+    }
+}
+
+impl FromAbiValue for String {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+        match value {
+            AbiValue::String(string) => Ok(string),
+            _ => unexpected(&value, "string"),
+        }
+    }
+}
+
+impl<T: ToAbiValue> ToAbiValue for Vec<T> {
+    fn to_abi_value(&self) -> AbiValue {
+        self.as_slice().to_abi_value()
+    }
+}
+
+impl<T: ToAbiValue> ToAbiValue for [T] {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::Array(self.iter().map(|v| v.to_abi_value()).collect())
+    }
+}
+
+impl<T: FromAbiValue> FromAbiValue for Vec<T> {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+        match value {
+            AbiValue::Array(values) => values.into_iter().map(T::from_abi_value).collect(),
+            _ => unexpected(&value, "array"),
+        }
+    }
+}
+
+impl<T: ToAbiValue, const N: usize> ToAbiValue for [T; N] {
+    fn to_abi_value(&self) -> AbiValue {
+        self.as_slice().to_abi_value()
+    }
+}
+
+impl<T: FromAbiValue, const N: usize> FromAbiValue for [T; N] {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+        let values = Vec::<T>::from_abi_value(value)?;
+        let len = values.len();
+        <[T; N]>::try_from(values).map_err(|_| {
+            TransactionError::Abi(format!("Expected an array of {} values, got {}", N, len))
+        })
+    }
+}
+
+/// The unit type is the empty tuple, which is also the value returned by `void` methods.
+impl ToAbiValue for () {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::Tuple(vec![])
+    }
+}
+
+impl FromAbiValue for () {
+    fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+        match value {
+            AbiValue::Tuple(values) if values.is_empty() => Ok(()),
+            _ => unexpected(&value, "empty tuple"),
+        }
+    }
+}
+
+macro_rules! impl_abi_value_tuple {
+    ($len:expr, $($t:ident $i:tt),+) => {
+        impl<$($t: ToAbiValue),+> ToAbiValue for ($($t,)+) {
+            fn to_abi_value(&self) -> AbiValue {
+                AbiValue::Tuple(vec![$(self.$i.to_abi_value()),+])
+            }
+        }
+
+        impl<$($t: FromAbiValue),+> FromAbiValue for ($($t,)+) {
+            fn from_abi_value(value: AbiValue) -> Result<Self, TransactionError> {
+                match value {
+                    AbiValue::Tuple(values) if values.len() == $len => {
+                        let mut values = values.into_iter();
+                        Ok(($($t::from_abi_value(values.next().expect(stringify!($i)))?,)+))
+                    }
+                    _ => unexpected(&value, concat!("tuple of ", $len, " values")),
+                }
+            }
+        }
+    };
+}
+
+impl_abi_value_tuple!(1, A 0);
+impl_abi_value_tuple!(2, A 0, B 1);
+impl_abi_value_tuple!(3, A 0, B 1, C 2);
+impl_abi_value_tuple!(4, A 0, B 1, C 2, D 3);
+impl_abi_value_tuple!(5, A 0, B 1, C 2, D 3, E 4);
+impl_abi_value_tuple!(6, A 0, B 1, C 2, D 3, E 4, F 5);
+impl_abi_value_tuple!(7, A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_abi_value_tuple!(8, A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
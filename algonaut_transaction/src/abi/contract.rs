@@ -0,0 +1,306 @@
+use super::{AbiEvent, AbiType, AbiValue};
+use crate::error::TransactionError;
+use crate::transaction::ApplicationCallOnComplete;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Prefix of the log with the return value of a method.
+pub const RETURN_PREFIX: [u8; 4] = [0x15, 0x1f, 0x7c, 0x75];
+
+/// Description of an ARC-4 contract: its methods and the ids of the application on each network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiContract {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+
+    /// The application on each network, keyed by the base64 genesis hash.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub networks: BTreeMap<String, AbiContractNetwork>,
+
+    pub methods: Vec<AbiMethod>,
+
+    /// ARC-28 events logged by the application.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<AbiEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiContractNetwork {
+    #[serde(rename = "appID")]
+    pub app_id: u64,
+}
+
+impl AbiContract {
+    pub fn from_json(json: &str) -> Result<Self, TransactionError> {
+        serde_json::from_str(json)
+            .map_err(|e| TransactionError::Abi(format!("Invalid contract description: {}", e)))
+    }
+
+    /// The method with the given name, or signature if the name is overloaded.
+    pub fn method(&self, name_or_signature: &str) -> Result<&AbiMethod, TransactionError> {
+        if name_or_signature.contains('(') {
+            return self
+                .methods
+                .iter()
+                .find(|m| m.signature() == name_or_signature)
+                .ok_or_else(|| {
+                    TransactionError::Abi(format!("Method not found: {}", name_or_signature))
+                });
+        }
+        let mut methods = self.methods.iter().filter(|m| m.name == name_or_signature);
+        match (methods.next(), methods.next()) {
+            (Some(method), None) => Ok(method),
+            (None, _) => Err(TransactionError::Abi(format!(
+                "Method not found: {}",
+                name_or_signature
+            ))),
+            (Some(_), Some(_)) => Err(TransactionError::Abi(format!(
+                "Method {} is overloaded, use its signature",
+                name_or_signature
+            ))),
+        }
+    }
+
+    /// Id of the application on the network with the given genesis hash (base64).
+    pub fn app_id(&self, genesis_hash: &str) -> Option<u64> {
+        self.networks.get(genesis_hash).map(|n| n.app_id)
+    }
+}
+
+/// A method of an ARC-4 contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiMethod {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+
+    pub args: Vec<AbiMethodArg>,
+
+    pub returns: AbiMethodReturn,
+
+    /// On completion actions allowed when calling the method, e.g. `["NoOp", "OptIn"]`.
+    /// The first one is used by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actions: Option<AbiMethodActions>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiMethodArg {
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiMethodReturn {
+    /// An ABI type or `void`.
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiMethodActions {
+    #[serde(default)]
+    pub call: Vec<String>,
+
+    #[serde(default)]
+    pub create: Vec<String>,
+}
+
+/// Type of a method argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiArgType {
+    /// Encoded in the application arguments.
+    Value(AbiType),
+    /// Passed in the foreign arrays of the call, and encoded as a uint8 index in them.
+    Reference(AbiReferenceType),
+    /// Passed as a transaction preceding the call in the group.
+    /// None for `txn`, which accepts any transaction type.
+    Transaction(Option<String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiReferenceType {
+    Account,
+    Asset,
+    Application,
+}
+
+impl FromStr for AbiArgType {
+    type Err = TransactionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "account" => AbiArgType::Reference(AbiReferenceType::Account),
+            "asset" => AbiArgType::Reference(AbiReferenceType::Asset),
+            "application" => AbiArgType::Reference(AbiReferenceType::Application),
+            "txn" => AbiArgType::Transaction(None),
+            "pay" | "keyreg" | "acfg" | "axfer" | "afrz" | "appl" => {
+                AbiArgType::Transaction(Some(s.to_owned()))
+            }
+            _ => AbiArgType::Value(s.parse()?),
+        })
+    }
+}
+
+impl AbiMethodArg {
+    pub fn arg_type(&self) -> Result<AbiArgType, TransactionError> {
+        self.type_.parse()
+    }
+}
+
+impl AbiMethodReturn {
+    /// None for `void`.
+    pub fn abi_type(&self) -> Result<Option<AbiType>, TransactionError> {
+        match self.type_.as_str() {
+            "void" => Ok(None),
+            t => Ok(Some(t.parse()?)),
+        }
+    }
+}
+
+impl AbiMethod {
+    /// E.g. `add(uint64,uint64)uint128`.
+    pub fn signature(&self) -> String {
+        format!(
+            "{}({}){}",
+            self.name,
+            self.args
+                .iter()
+                .map(|a| a.type_.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            self.returns.type_
+        )
+    }
+
+    /// First 4 bytes of the SHA-512/256 hash of the signature, passed as first application argument.
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// The default on completion action of calls.
+    pub fn on_complete(&self) -> Result<ApplicationCallOnComplete, TransactionError> {
+        match self.actions.as_ref().and_then(|a| a.call.first()) {
+            Some(action) => parse_on_complete(action),
+            None => Ok(ApplicationCallOnComplete::NoOp),
+        }
+    }
+
+    /// Decodes the return value from the logs of the call. `void` methods return an empty tuple.
+    pub fn decode_return(&self, logs: &[Vec<u8>]) -> Result<AbiValue, TransactionError> {
+        let abi_type = match self.returns.abi_type()? {
+            Some(abi_type) => abi_type,
+            None => return Ok(AbiValue::Tuple(vec![])),
+        };
+        match logs.last() {
+            Some(log) if log.starts_with(&RETURN_PREFIX) => {
+                abi_type.decode(&log[RETURN_PREFIX.len()..])
+            }
+            _ => Err(TransactionError::Abi(format!(
+                "No return value in the logs of {}",
+                self.name
+            ))),
+        }
+    }
+}
+
+/// First 4 bytes of the SHA-512/256 hash of a method or event signature.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hashed = sha2::Sha512Trunc256::digest(signature.as_bytes());
+    [hashed[0], hashed[1], hashed[2], hashed[3]]
+}
+
+fn parse_on_complete(action: &str) -> Result<ApplicationCallOnComplete, TransactionError> {
+    Ok(match action {
+        "NoOp" => ApplicationCallOnComplete::NoOp,
+        "OptIn" => ApplicationCallOnComplete::OptIn,
+        "CloseOut" => ApplicationCallOnComplete::CloseOut,
+        "ClearState" => ApplicationCallOnComplete::ClearState,
+        "UpdateApplication" => ApplicationCallOnComplete::UpdateApplication,
+        "DeleteApplication" => ApplicationCallOnComplete::DeleteApplication,
+        _ => {
+            return Err(TransactionError::Abi(format!(
+                "Invalid on completion action: {}",
+                action
+            )))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+    use serde_json::json;
+
+    fn contract() -> AbiContract {
+        AbiContract::from_json(
+            &json!({
+                "name": "Calculator",
+                "networks": { "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=": { "appID": 1234 } },
+                "methods": [
+                    {
+                        "name": "add",
+                        "args": [{ "type": "uint64", "name": "a" }, { "type": "uint64", "name": "b" }],
+                        "returns": { "type": "uint128" }
+                    },
+                    {
+                        "name": "opt_in",
+                        "args": [],
+                        "returns": { "type": "void" },
+                        "actions": { "call": ["OptIn"] }
+                    }
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_method_selector() {
+        let contract = contract();
+        let add = contract.method("add").unwrap();
+        assert_eq!("add(uint64,uint64)uint128", add.signature());
+        assert_eq!([0x8a, 0xa3, 0xb6, 0x1f], add.selector());
+        assert_eq!(add, contract.method("add(uint64,uint64)uint128").unwrap());
+        assert!(contract.method("sub").is_err());
+        assert_eq!(
+            Some(1234),
+            contract.app_id("SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=")
+        );
+    }
+
+    #[test]
+    fn test_decode_return() {
+        let contract = contract();
+        let add = contract.method("add").unwrap();
+        let log = [&RETURN_PREFIX[..], &[0; 15], &[3]].concat();
+        assert_eq!(
+            AbiValue::Uint(BigUint::from(3u8)),
+            add.decode_return(&[b"other".to_vec(), log]).unwrap()
+        );
+        assert!(add.decode_return(&[]).is_err());
+
+        let opt_in = contract.method("opt_in").unwrap();
+        assert_eq!(
+            ApplicationCallOnComplete::OptIn,
+            opt_in.on_complete().unwrap()
+        );
+        assert_eq!(AbiValue::Tuple(vec![]), opt_in.decode_return(&[]).unwrap());
+    }
+}
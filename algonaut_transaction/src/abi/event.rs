@@ -0,0 +1,437 @@
+use super::{selector, AbiContract, AbiType, AbiValue, FromAbiValue};
+use crate::error::TransactionError;
+use algonaut_model::{algod, indexer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An ARC-28 event, logged by an application as the selector of its signature followed by
+/// the ABI encoded tuple of its arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiEvent {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+
+    pub args: Vec<AbiEventArg>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiEventArg {
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+}
+
+impl AbiEvent {
+    /// E.g. `Swapped(uint64,uint64)`.
+    pub fn signature(&self) -> String {
+        format!(
+            "{}({})",
+            self.name,
+            self.args
+                .iter()
+                .map(|a| a.type_.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Decodes the event from a log. Fails if the log doesn't start with the selector of the event.
+    pub fn decode(&self, log: &[u8]) -> Result<DecodedEvent, TransactionError> {
+        if !log.starts_with(&self.selector()) {
+            return Err(TransactionError::Abi(format!(
+                "The log is not a {} event",
+                self.signature()
+            )));
+        }
+        let types = self
+            .args
+            .iter()
+            .map(|a| a.type_.parse())
+            .collect::<Result<Vec<AbiType>, _>>()?;
+        let args = match AbiType::Tuple(types).decode(&log[4..])? {
+            AbiValue::Tuple(args) => args,
+            _ => unreachable!("Decoded a tuple type"),
+        };
+        Ok(DecodedEvent {
+            event: self.clone(),
+            args,
+        })
+    }
+}
+
+/// An event decoded from a log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEvent {
+    pub event: AbiEvent,
+    /// The arguments, in the order of the event description.
+    pub args: Vec<AbiValue>,
+}
+
+impl DecodedEvent {
+    pub fn name(&self) -> &str {
+        &self.event.name
+    }
+
+    /// The value of the argument with the given name.
+    pub fn arg(&self, name: &str) -> Option<&AbiValue> {
+        self.event
+            .args
+            .iter()
+            .position(|a| a.name.as_deref() == Some(name))
+            .map(|i| &self.args[i])
+    }
+
+    /// Converts the arguments, as a tuple, to a Rust type, e.g. `(u64, Address)`.
+    pub fn decode<T: FromAbiValue>(&self) -> Result<T, TransactionError> {
+        T::from_abi_value(AbiValue::Tuple(self.args.clone()))
+    }
+}
+
+/// An event logged by a transaction or one of its inner transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionEvent {
+    /// Path of the transaction which logged the event in the transaction tree,
+    /// as returned by `flatten`. Empty for the top level transaction.
+    pub path: Vec<usize>,
+    /// ID of the application which logged the event: the one called by the transaction,
+    /// or created by it.
+    pub app_id: u64,
+    pub event: DecodedEvent,
+}
+
+/// A log starting with the selector of a registered event, which couldn't be decoded as the event,
+/// e.g. the log of another application which starts with the same bytes.
+#[derive(Debug)]
+pub struct UndecodableLog {
+    /// Path of the transaction which logged it, like [TransactionEvent::path].
+    pub path: Vec<usize>,
+    /// ID of the application which logged it, like [TransactionEvent::app_id].
+    pub app_id: u64,
+    /// Index of the log in the logs of the transaction.
+    pub index: usize,
+    pub error: TransactionError,
+}
+
+/// The events logged by a transaction and its inner transactions.
+#[derive(Debug, Default)]
+pub struct TransactionEvents {
+    /// The decoded events, in execution order.
+    pub events: Vec<TransactionEvent>,
+    /// The logs which look like events but couldn't be decoded, skipped in `events`.
+    pub undecodable: Vec<UndecodableLog>,
+}
+
+/// Known events by selector, to decode the logs of application calls.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventRegistry {
+    events: HashMap<[u8; 4], AbiEvent>,
+}
+
+impl EventRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with the events of the contract.
+    pub fn from_contract(contract: &AbiContract) -> Result<Self, TransactionError> {
+        let mut registry = Self::new();
+        registry.register_contract(contract)?;
+        Ok(registry)
+    }
+
+    pub fn register_contract(&mut self, contract: &AbiContract) -> Result<(), TransactionError> {
+        for event in &contract.events {
+            self.register(event.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Fails if an argument type is invalid, or if another event with the same selector is
+    /// registered already: the logs of the two events couldn't be told apart.
+    /// Registering the same event again has no effect.
+    pub fn register(&mut self, event: AbiEvent) -> Result<(), TransactionError> {
+        for arg in &event.args {
+            arg.type_.parse::<AbiType>()?;
+        }
+        match self.events.get(&event.selector()) {
+            Some(registered) if *registered == event => Ok(()),
+            Some(registered) => Err(TransactionError::Abi(format!(
+                "Event {} conflicts with the registered event {}",
+                event.signature(),
+                registered.signature()
+            ))),
+            None => {
+                self.events.insert(event.selector(), event);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn event(&self, selector: &[u8; 4]) -> Option<&AbiEvent> {
+        self.events.get(selector)
+    }
+
+    /// Decodes the log, if it starts with the selector of a registered event.
+    pub fn decode_log(&self, log: &[u8]) -> Option<Result<DecodedEvent, TransactionError>> {
+        if log.len() < 4 {
+            return None;
+        }
+        let selector = [log[0], log[1], log[2], log[3]];
+        self.event(&selector).map(|event| event.decode(log))
+    }
+
+    /// Decodes the events of the logs, skipping the logs of unknown events.
+    /// Each decoded log is returned with its index, and fails on its own if it's not a valid event.
+    pub fn decode_logs(
+        &self,
+        logs: &[Vec<u8>],
+    ) -> Vec<(usize, Result<DecodedEvent, TransactionError>)> {
+        logs.iter()
+            .enumerate()
+            .filter_map(|(index, log)| self.decode_log(log).map(|event| (index, event)))
+            .collect()
+    }
+
+    /// Events logged by a pending transaction and its inner transactions.
+    pub fn decode_pending_transaction(
+        &self,
+        transaction: &algod::v2::PendingTransaction,
+    ) -> TransactionEvents {
+        self.decode_tree(transaction.flatten().into_iter().map(|(path, t)| {
+            let app_id = t.txn.txn.app_id.or(t.application_index).unwrap_or(0);
+            (path, app_id, t.logs.as_slice())
+        }))
+    }
+
+    /// Events logged by an indexer transaction and its inner transactions.
+    pub fn decode_indexer_transaction(
+        &self,
+        transaction: &indexer::v2::Transaction,
+    ) -> TransactionEvents {
+        self.decode_tree(transaction.flatten().into_iter().map(|(path, t)| {
+            let app_id = match &t.application_transaction {
+                Some(app_call) if app_call.application_id != 0 => app_call.application_id,
+                _ => t.created_application_index.unwrap_or(0),
+            };
+            (path, app_id, t.logs.as_slice())
+        }))
+    }
+
+    /// Decodes the logs of each transaction of the tree, with its path and application ID.
+    fn decode_tree<'a>(
+        &self,
+        transactions: impl Iterator<Item = (Vec<usize>, u64, &'a [Vec<u8>])>,
+    ) -> TransactionEvents {
+        let mut events = TransactionEvents::default();
+        for (path, app_id, logs) in transactions {
+            for (index, event) in self.decode_logs(logs) {
+                match event {
+                    Ok(event) => events.events.push(TransactionEvent {
+                        path: path.clone(),
+                        app_id,
+                        event,
+                    }),
+                    Err(error) => events.undecodable.push(UndecodableLog {
+                        path: path.clone(),
+                        app_id,
+                        index,
+                        error,
+                    }),
+                }
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::{ToAbiValue, RETURN_PREFIX};
+    use algonaut_core::Address;
+    use data_encoding::BASE64;
+    use serde_json::json;
+
+    fn contract() -> AbiContract {
+        AbiContract::from_json(
+            &json!({
+                "name": "Exchange",
+                "methods": [],
+                "events": [
+                    {
+                        "name": "Swapped",
+                        "args": [
+                            { "type": "uint64", "name": "amount_in" },
+                            { "type": "uint64", "name": "amount_out" }
+                        ]
+                    },
+                    {
+                        "name": "Deposit",
+                        "args": [{ "type": "address", "name": "from" }, { "type": "string" }]
+                    }
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    fn log(registry: &EventRegistry, signature: &str, args: AbiValue) -> Vec<u8> {
+        let selector = selector(signature);
+        let event = registry.event(&selector).unwrap();
+        let types = event
+            .args
+            .iter()
+            .map(|a| a.type_.parse().unwrap())
+            .collect();
+        [
+            selector.to_vec(),
+            AbiType::Tuple(types).encode(&args).unwrap(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_decode_logs() {
+        let registry = EventRegistry::from_contract(&contract()).unwrap();
+        let swapped = log(
+            &registry,
+            "Swapped(uint64,uint64)",
+            (10u64, 20u64).to_abi_value(),
+        );
+        let deposit = log(
+            &registry,
+            "Deposit(address,string)",
+            (Address([1; 32]), "memo").to_abi_value(),
+        );
+        let logs = vec![
+            b"not an event".to_vec(),
+            swapped,
+            deposit,
+            [&RETURN_PREFIX[..], &[0; 8]].concat(),
+        ];
+
+        let events: Vec<DecodedEvent> = registry
+            .decode_logs(&logs)
+            .into_iter()
+            .map(|(_, event)| event.unwrap())
+            .collect();
+        assert_eq!(2, events.len());
+        assert_eq!("Swapped", events[0].name());
+        assert_eq!((10, 20), events[0].decode::<(u64, u64)>().unwrap());
+        assert_eq!(Some(&20u64.to_abi_value()), events[0].arg("amount_out"));
+        assert_eq!(
+            (Address([1; 32]), "memo".to_owned()),
+            events[1].decode().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_register_conflicting_event() {
+        let mut registry = EventRegistry::from_contract(&contract()).unwrap();
+        let swapped = contract().events[0].clone();
+        assert!(registry.register(swapped.clone()).is_ok());
+
+        let mut renamed_args = swapped;
+        renamed_args.args[0].name = Some("amount".to_owned());
+        assert!(registry.register(renamed_args).is_err());
+        assert_eq!(
+            &contract().events[0],
+            registry.event(&contract().events[0].selector()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_transaction_tree() {
+        let registry = EventRegistry::from_contract(&contract()).unwrap();
+        let swapped = log(
+            &registry,
+            "Swapped(uint64,uint64)",
+            (1u64, 2u64).to_abi_value(),
+        );
+        let sender = Address([1; 32]).to_string();
+        let json = json!({
+            "fee": 2000,
+            "first-valid": 10,
+            "last-valid": 1010,
+            "genesis-hash": "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=",
+            "id": "TXID",
+            "sender": sender,
+            "tx-type": "appl",
+            "application-transaction": { "application-id": 5, "on-completion": "noop" },
+            "inner-txns": [{
+                "fee": 0,
+                "first-valid": 10,
+                "last-valid": 1010,
+                "sender": sender,
+                "tx-type": "appl",
+                "application-transaction": { "application-id": 0, "on-completion": "noop" },
+                "created-application-index": 6,
+                "logs": [BASE64.encode(&swapped)],
+            }],
+        });
+        let transaction: indexer::v2::Transaction =
+            serde_json::from_str(&json.to_string()).unwrap();
+
+        let events = registry.decode_indexer_transaction(&transaction);
+        assert_eq!(1, events.events.len());
+        assert_eq!(vec![0], events.events[0].path);
+        assert_eq!(6, events.events[0].app_id);
+        assert_eq!("Swapped", events.events[0].event.name());
+        assert!(events.undecodable.is_empty());
+    }
+
+    #[test]
+    fn test_undecodable_logs() {
+        let registry = EventRegistry::from_contract(&contract()).unwrap();
+        let swapped = log(
+            &registry,
+            "Swapped(uint64,uint64)",
+            (1u64, 2u64).to_abi_value(),
+        );
+        // A log with a known selector but invalid arguments
+        let mut invalid = swapped.clone();
+        invalid.pop();
+        let logs = vec![invalid.clone(), b"not an event".to_vec(), swapped.clone()];
+
+        let decoded = registry.decode_logs(&logs);
+        assert_eq!(2, decoded.len());
+        assert_eq!(0, decoded[0].0);
+        assert!(decoded[0].1.is_err());
+        assert_eq!(2, decoded[1].0);
+        assert!(decoded[1].1.is_ok());
+
+        let json = json!({
+            "pool-error": "",
+            "txn": { "txn": { "apid": 5 } },
+            "logs": [BASE64.encode(&swapped)],
+            "inner-txns": [{
+                "pool-error": "",
+                "txn": { "txn": { "apid": 7 } },
+                "logs": [BASE64.encode(b"not an event"), BASE64.encode(&invalid)],
+            }],
+        });
+        let transaction: algod::v2::PendingTransaction =
+            serde_json::from_str(&json.to_string()).unwrap();
+
+        let events = registry.decode_pending_transaction(&transaction);
+        assert_eq!(1, events.events.len());
+        assert_eq!(Vec::<usize>::new(), events.events[0].path);
+        assert_eq!(5, events.events[0].app_id);
+        assert_eq!(1, events.undecodable.len());
+        assert_eq!(vec![0], events.undecodable[0].path);
+        assert_eq!(7, events.undecodable[0].app_id);
+        assert_eq!(1, events.undecodable[0].index);
+    }
+}
@@ -0,0 +1,401 @@
+use super::{AbiArgType, AbiMethod, AbiReferenceType, AbiType, AbiValue, ToAbiValue};
+use crate::error::TransactionError;
+use crate::transaction::{
+    ApplicationCallOnComplete, ApplicationCallTransaction, BoxReference, Transaction,
+};
+use crate::TransactionType;
+use algonaut_core::Address;
+
+/// Max number of application arguments. Method arguments beyond the 14th are encoded in a tuple
+/// passed as the last one.
+const MAX_APP_ARGS: usize = 16;
+
+/// Max number of accounts, assets and applications in the foreign arrays of an application call,
+/// and of all references, boxes included.
+const MAX_ACCOUNTS: usize = 4;
+const MAX_FOREIGN_ASSETS: usize = 8;
+const MAX_FOREIGN_APPS: usize = 8;
+const MAX_REFERENCES: usize = 8;
+
+/// Value of a method argument, according to its [AbiArgType].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiArgValue {
+    Value(AbiValue),
+    Account(Address),
+    Asset(u64),
+    Application(u64),
+    /// A transaction which is added to the group before the call.
+    Transaction(Box<Transaction>),
+}
+
+/// Builds the application call of an ARC-4 method: the selector and the encoded arguments,
+/// with the referenced accounts, assets and applications in the foreign arrays.
+pub struct AbiMethodCall {
+    sender: Address,
+    app_id: u64,
+    method: AbiMethod,
+    args: Vec<AbiArgValue>,
+    on_complete: Option<ApplicationCallOnComplete>,
+    boxes: Option<Vec<BoxReference>>,
+}
+
+impl AbiMethodCall {
+    pub fn new(sender: Address, app_id: u64, method: AbiMethod, args: Vec<AbiArgValue>) -> Self {
+        AbiMethodCall {
+            sender,
+            app_id,
+            method,
+            args,
+            on_complete: None,
+            boxes: None,
+        }
+    }
+
+    /// Overrides the default on completion action of the method.
+    pub fn on_complete(mut self, on_complete: ApplicationCallOnComplete) -> Self {
+        self.on_complete = Some(on_complete);
+        self
+    }
+
+    pub fn boxes(mut self, boxes: Vec<BoxReference>) -> Self {
+        self.boxes = Some(boxes);
+        self
+    }
+
+    /// The transaction arguments, to be grouped before the call in this order.
+    pub fn transaction_args(&self) -> Vec<Transaction> {
+        self.args
+            .iter()
+            .filter_map(|arg| match arg {
+                AbiArgValue::Transaction(t) => Some((**t).clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn build(self) -> Result<TransactionType, TransactionError> {
+        if self.args.len() != self.method.args.len() {
+            return Err(TransactionError::Abi(format!(
+                "{} expects {} arguments, got {}",
+                self.method.signature(),
+                self.method.args.len(),
+                self.args.len()
+            )));
+        }
+
+        let mut accounts = vec![];
+        let mut foreign_assets = vec![];
+        let mut foreign_apps = vec![];
+        // Types and values of the arguments encoded in the application arguments
+        let mut types = vec![];
+        let mut values = vec![];
+        for (arg, value) in self.method.args.iter().zip(self.args) {
+            match (arg.arg_type()?, value) {
+                (AbiArgType::Value(t), AbiArgValue::Value(v)) => {
+                    types.push(t);
+                    values.push(v);
+                }
+                (AbiArgType::Reference(AbiReferenceType::Account), AbiArgValue::Account(a)) => {
+                    // Index 0 is the sender
+                    let index = if a == self.sender {
+                        0
+                    } else {
+                        reference_index(&mut accounts, a, MAX_ACCOUNTS, "accounts")? + 1
+                    };
+                    types.push(AbiType::Uint(8));
+                    values.push(index.to_abi_value());
+                }
+                (AbiArgType::Reference(AbiReferenceType::Asset), AbiArgValue::Asset(id)) => {
+                    let index =
+                        reference_index(&mut foreign_assets, id, MAX_FOREIGN_ASSETS, "assets")?;
+                    types.push(AbiType::Uint(8));
+                    values.push(index.to_abi_value());
+                }
+                (
+                    AbiArgType::Reference(AbiReferenceType::Application),
+                    AbiArgValue::Application(id),
+                ) => {
+                    // Index 0 is the called application
+                    let index = if id == self.app_id {
+                        0
+                    } else {
+                        reference_index(&mut foreign_apps, id, MAX_FOREIGN_APPS, "applications")?
+                            + 1
+                    };
+                    types.push(AbiType::Uint(8));
+                    values.push(index.to_abi_value());
+                }
+                (AbiArgType::Transaction(expected), AbiArgValue::Transaction(t)) => {
+                    let actual = type_name(&t.txn_type);
+                    if matches!(&expected, Some(expected) if expected != actual) {
+                        return Err(TransactionError::Abi(format!(
+                            "Argument {} expects a {} transaction, got {}",
+                            arg.type_, arg.type_, actual
+                        )));
+                    }
+                }
+                (_, value) => {
+                    return Err(TransactionError::Abi(format!(
+                        "Invalid value for argument of type {}: {:?}",
+                        arg.type_, value
+                    )))
+                }
+            }
+        }
+
+        let references = accounts.len()
+            + foreign_assets.len()
+            + foreign_apps.len()
+            + self.boxes.as_ref().map_or(0, |boxes| boxes.len());
+        if references > MAX_REFERENCES {
+            return Err(TransactionError::Abi(format!(
+                "The call references {} accounts, assets, applications and boxes, the maximum is {}",
+                references, MAX_REFERENCES
+            )));
+        }
+
+        if types.len() > MAX_APP_ARGS - 1 {
+            let tuple_types = types.split_off(MAX_APP_ARGS - 2);
+            let tuple_values = values.split_off(MAX_APP_ARGS - 2);
+            types.push(AbiType::Tuple(tuple_types));
+            values.push(AbiValue::Tuple(tuple_values));
+        }
+
+        let mut app_arguments = vec![self.method.selector().to_vec()];
+        for (t, v) in types.iter().zip(&values) {
+            app_arguments.push(t.encode(v)?);
+        }
+
+        let on_complete = match self.on_complete {
+            Some(on_complete) => on_complete,
+            None => self.method.on_complete()?,
+        };
+
+        Ok(TransactionType::ApplicationCallTransaction(
+            ApplicationCallTransaction {
+                sender: self.sender,
+                app_id: Some(self.app_id),
+                on_complete,
+                accounts: non_empty(accounts),
+                approval_program: None,
+                app_arguments: Some(app_arguments),
+                clear_state_program: None,
+                foreign_apps: non_empty(foreign_apps),
+                foreign_assets: non_empty(foreign_assets),
+                boxes: self.boxes,
+                global_state_schema: None,
+                local_state_schema: None,
+                extra_pages: 0,
+            },
+        ))
+    }
+}
+
+/// Position of the reference in the array, adding it if not present yet.
+/// Fails if the array would exceed `max` references.
+fn reference_index<T: PartialEq>(
+    references: &mut Vec<T>,
+    reference: T,
+    max: usize,
+    name: &str,
+) -> Result<u8, TransactionError> {
+    let index = match references.iter().position(|r| *r == reference) {
+        Some(index) => index,
+        None if references.len() < max => {
+            references.push(reference);
+            references.len() - 1
+        }
+        None => {
+            return Err(TransactionError::Abi(format!(
+                "The call references more than {} {}",
+                max, name
+            )))
+        }
+    };
+    Ok(index as u8)
+}
+
+fn non_empty<T>(vec: Vec<T>) -> Option<Vec<T>> {
+    if vec.is_empty() {
+        None
+    } else {
+        Some(vec)
+    }
+}
+
+/// The ABI name of the transaction type.
+fn type_name(txn_type: &TransactionType) -> &'static str {
+    match txn_type {
+        TransactionType::Payment(_) => "pay",
+        TransactionType::KeyRegistration(_) => "keyreg",
+        TransactionType::AssetConfigurationTransaction(_) => "acfg",
+        TransactionType::AssetTransferTransaction(_)
+        | TransactionType::AssetAcceptTransaction(_)
+        | TransactionType::AssetClawbackTransaction(_) => "axfer",
+        TransactionType::AssetFreezeTransaction(_) => "afrz",
+        TransactionType::ApplicationCallTransaction(_) => "appl",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::AbiContract;
+    use crate::{Pay, TxnBuilder};
+    use algonaut_core::{MicroAlgos, Round};
+    use algonaut_crypto::HashDigest;
+    use serde_json::json;
+
+    fn method(args: &[&str]) -> AbiMethod {
+        let args: Vec<_> = args.iter().map(|t| json!({ "type": t })).collect();
+        AbiContract::from_json(
+            &json!({
+                "name": "Test",
+                "methods": [{ "name": "test", "args": args, "returns": { "type": "void" } }]
+            })
+            .to_string(),
+        )
+        .unwrap()
+        .methods
+        .remove(0)
+    }
+
+    fn app_call(txn_type: TransactionType) -> ApplicationCallTransaction {
+        match txn_type {
+            TransactionType::ApplicationCallTransaction(call) => call,
+            _ => panic!("Not an application call"),
+        }
+    }
+
+    #[test]
+    fn test_build_references() {
+        let sender = Address([1; 32]);
+        let other = Address([2; 32]);
+        let payment = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1000),
+            HashDigest([0; 32]),
+            Pay::new(sender, other, MicroAlgos(10)).build(),
+        )
+        .build();
+
+        let call = AbiMethodCall::new(
+            sender,
+            5,
+            method(&[
+                "account",
+                "account",
+                "asset",
+                "application",
+                "pay",
+                "uint64",
+            ]),
+            vec![
+                AbiArgValue::Account(sender),
+                AbiArgValue::Account(other),
+                AbiArgValue::Asset(10),
+                AbiArgValue::Application(7),
+                AbiArgValue::Transaction(Box::new(payment.clone())),
+                AbiArgValue::Value(3u64.to_abi_value()),
+            ],
+        );
+        assert_eq!(vec![payment], call.transaction_args());
+
+        let call = app_call(call.build().unwrap());
+        assert_eq!(Some(vec![other]), call.accounts);
+        assert_eq!(Some(vec![10]), call.foreign_assets);
+        assert_eq!(Some(vec![7]), call.foreign_apps);
+        assert_eq!(ApplicationCallOnComplete::NoOp, call.on_complete);
+        assert_eq!(
+            Some(vec![
+                method(&[
+                    "account",
+                    "account",
+                    "asset",
+                    "application",
+                    "pay",
+                    "uint64"
+                ])
+                .selector()
+                .to_vec(),
+                vec![0],
+                vec![1],
+                vec![0],
+                vec![1],
+                vec![0, 0, 0, 0, 0, 0, 0, 3],
+            ]),
+            call.app_arguments
+        );
+    }
+
+    #[test]
+    fn test_build_packs_args_beyond_limit() {
+        let method = method(&["uint8"; 17]);
+        let args = (0..17u8)
+            .map(|i| AbiArgValue::Value(i.to_abi_value()))
+            .collect();
+        let call = app_call(
+            AbiMethodCall::new(Address([1; 32]), 5, method, args)
+                .build()
+                .unwrap(),
+        );
+        let app_arguments = call.app_arguments.unwrap();
+        assert_eq!(MAX_APP_ARGS, app_arguments.len());
+        assert_eq!(vec![13], app_arguments[14]);
+        assert_eq!(vec![14, 15, 16], app_arguments[15]);
+    }
+
+    #[test]
+    fn test_build_too_many_references() {
+        let call = |args: Vec<AbiArgValue>| {
+            let types = args
+                .iter()
+                .map(|arg| match arg {
+                    AbiArgValue::Account(_) => "account",
+                    AbiArgValue::Asset(_) => "asset",
+                    _ => "application",
+                })
+                .collect::<Vec<_>>();
+            AbiMethodCall::new(Address([0; 32]), 5, method(&types), args)
+        };
+        let accounts = |n: u8| (1..=n).map(|i| AbiArgValue::Account(Address([i; 32])));
+        let assets = |n: u64| (1..=n).map(AbiArgValue::Asset);
+        let apps = |n: u64| (10..10 + n).map(AbiArgValue::Application);
+
+        assert!(call(accounts(4).collect()).build().is_ok());
+        assert!(call(accounts(5).collect()).build().is_err());
+        assert!(call(assets(8).collect()).build().is_ok());
+        assert!(call(assets(9).collect()).build().is_err());
+        assert!(call(apps(8).collect()).build().is_ok());
+        assert!(call(apps(9).collect()).build().is_err());
+        assert!(call(accounts(4).chain(assets(4)).collect()).build().is_ok());
+        assert!(call(accounts(4).chain(assets(4)).chain(apps(1)).collect())
+            .build()
+            .is_err());
+        assert!(call(assets(7).collect())
+            .boxes(vec![
+                BoxReference {
+                    app_index: 0,
+                    name: b"box".to_vec()
+                };
+                2
+            ])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_build_invalid_args() {
+        let call = AbiMethodCall::new(
+            Address([1; 32]),
+            5,
+            method(&["uint64"]),
+            vec![AbiArgValue::Asset(1)],
+        );
+        assert!(call.build().is_err());
+
+        let call = AbiMethodCall::new(Address([1; 32]), 5, method(&["uint64"]), vec![]);
+        assert!(call.build().is_err());
+    }
+}
@@ -0,0 +1,21 @@
+//! Encoding of the ARC-4 application binary interface, calls of contract methods and
+//! decoding of ARC-28 events.
+
+mod abi_type;
+mod abi_value;
+mod contract;
+mod event;
+mod method_call;
+
+pub use abi_type::AbiType;
+pub use abi_value::{AbiValue, FromAbiValue, ToAbiValue};
+pub use contract::{
+    selector, AbiArgType, AbiContract, AbiContractNetwork, AbiMethod, AbiMethodActions,
+    AbiMethodArg, AbiMethodReturn, AbiReferenceType, RETURN_PREFIX,
+};
+pub use event::{
+    AbiEvent, AbiEventArg, DecodedEvent, EventRegistry, TransactionEvent, TransactionEvents,
+    UndecodableLog,
+};
+pub use method_call::{AbiArgValue, AbiMethodCall};
+pub use num_bigint::BigUint;
@@ -1,51 +1,150 @@
 use crate::auction::{Bid, SignedBid};
 use crate::error::TransactionError;
+use crate::keystore::{Keystore, SCRYPT_LOG_N};
 use crate::transaction::{SignedTransaction, Transaction, TransactionSignature};
 use algonaut_core::{
     Address, CompiledTealBytes, LogicSignature, MultisigAddress, MultisigSignature, MultisigSubsig,
     SignedLogic, ToMsgPack,
 };
-use algonaut_crypto::{mnemonic, Signature};
+use algonaut_crypto::bip32_ed25519::{DerivationPath, DerivationScheme, ExtendedPrivateKey};
+use algonaut_crypto::error::CryptoError;
+use algonaut_crypto::secret::{Secret, SecretSeed};
+use algonaut_crypto::shamir::{self, Share};
+use algonaut_crypto::{mnemonic, MasterDerivationKey, Signature};
 use algonaut_model::algod::v2::CompiledTeal;
 use rand::rngs::OsRng;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::fmt::{self, Debug, Formatter};
 
-#[derive(Debug)]
+/// An account with its key pair. The secret key material is zeroized on drop.
 pub struct Account {
-    seed: [u8; 32],
+    seed: Option<SecretSeed>,
     address: Address,
-    key_pair: ed25519_dalek::Keypair,
+    public_key: ed25519_dalek::PublicKey,
+    secret_key: ed25519_dalek::ExpandedSecretKey,
 }
 
 impl Account {
     pub fn generate() -> Account {
-        let seed: [u8; 32] = OsRng.gen();
-        Self::from_seed(seed)
+        let mut seed = SecretSeed::new([0; 32]);
+        OsRng.fill(seed.expose_secret_mut());
+        Self::from_seed(seed.expose_secret())
     }
 
     /// Create account from human readable mnemonic of a 32 byte seed
     pub fn from_mnemonic(mnemonic: &str) -> Result<Account, TransactionError> {
         let seed = mnemonic::to_key(mnemonic)?;
-        Ok(Self::from_seed(seed))
+        Ok(Self::from_seed(seed.expose_secret()))
     }
 
-    /// Create account from 32 byte seed
-    pub fn from_seed(seed: [u8; 32]) -> Account {
-        let secret = ed25519_dalek::SecretKey::from_bytes(&seed).unwrap();
-        let public = ed25519_dalek::PublicKey::from(&secret);
+    /// Create account from 32 byte seed. The seed is borrowed, so the caller's copy
+    /// (e.g. a [SecretSeed]) remains the only one outside the account.
+    pub fn from_seed(seed: &[u8; 32]) -> Account {
+        let secret = ed25519_dalek::SecretKey::from_bytes(seed).unwrap();
+        let public_key = ed25519_dalek::PublicKey::from(&secret);
+        let mut account_seed = SecretSeed::new([0; 32]);
+        account_seed.expose_secret_mut().copy_from_slice(seed);
 
-        let address = Address::new(public.to_bytes());
         Account {
-            seed,
-            address,
-            key_pair: ed25519_dalek::Keypair { public, secret },
+            seed: Some(account_seed),
+            address: Address::new(public_key.to_bytes()),
+            public_key,
+            secret_key: ed25519_dalek::ExpandedSecretKey::from(&secret),
         }
     }
 
+    /// Create account from the shares of its seed (see [algonaut_crypto::shamir::split]).
+    /// Split the seed of an account with `shamir::split(account.seed()?.expose_secret(), threshold, shares)`.
+    pub fn from_shares(shares: &[Share]) -> Result<Account, TransactionError> {
+        let seed = shamir::combine(shares)?;
+        Ok(Self::from_seed(seed.expose_secret()))
+    }
+
+    /// Create account from the key derived at `path` from a BIP32-Ed25519 root key
+    /// (see [ExtendedPrivateKey::from_mnemonic]).
+    /// Derived accounts have no seed, so they can't be exported as mnemonic.
+    pub fn from_derivation_path(
+        root: &ExtendedPrivateKey,
+        path: &DerivationPath,
+        scheme: DerivationScheme,
+    ) -> Result<Account, TransactionError> {
+        let key = root.derive_path(path, scheme)?;
+        Self::from_expanded_secret_key(
+            key.expanded_secret_key().expose_secret(),
+            &key.public_key().0,
+        )
+    }
+
+    // Derived secret keys are not clamped, so the public key can't be recomputed with ed25519_dalek
+    fn from_expanded_secret_key(
+        secret_key: &[u8],
+        public_key: &[u8],
+    ) -> Result<Account, TransactionError> {
+        let secret_key = ed25519_dalek::ExpandedSecretKey::from_bytes(secret_key)
+            .map_err(|_| CryptoError::InvalidDerivedKey)?;
+        let public_key = ed25519_dalek::PublicKey::from_bytes(public_key)
+            .map_err(|_| CryptoError::InvalidDerivedKey)?;
+
+        Ok(Account {
+            seed: None,
+            address: Address::new(public_key.to_bytes()),
+            public_key,
+            secret_key,
+        })
+    }
+
+    /// Create the account kmd generates at `index` in a wallet with the master derivation key `mdk`
+    /// (see [MasterDerivationKey::derive_seed]), without connecting to kmd.
+    pub fn from_master_derivation_key(mdk: &MasterDerivationKey, index: u64) -> Account {
+        Self::from_seed(mdk.derive_seed(index).expose_secret())
+    }
+
+    /// Decrypt an account from a keystore file created with [Account::to_keystore]
+    pub fn from_keystore(bytes: &[u8], password: &str) -> Result<Account, TransactionError> {
+        let keystore = Keystore::from_json(bytes)?;
+        let address = keystore.address()?;
+        let secret = keystore.decrypt(password)?;
+        let secret = secret.expose_secret();
+        let account = match secret.len() {
+            32 => {
+                let mut seed = SecretSeed::new([0; 32]);
+                seed.expose_secret_mut().copy_from_slice(secret);
+                Self::from_seed(seed.expose_secret())
+            }
+            64 => Self::from_expanded_secret_key(secret, &address.0)?,
+            len => {
+                return Err(TransactionError::Keystore(format!(
+                    "Invalid secret length: {}",
+                    len
+                )))
+            }
+        };
+        if account.address != address {
+            return Err(TransactionError::Keystore(
+                "Decrypted key doesn't match address".to_owned(),
+            ));
+        }
+        Ok(account)
+    }
+
+    /// Encrypt the account with a key derived from `password` into a JSON keystore file
+    /// (see [Keystore]). Accounts created from a derivation path store their expanded secret key.
+    pub fn to_keystore(&self, password: &str) -> Result<Vec<u8>, TransactionError> {
+        self.keystore(SCRYPT_LOG_N, password)?.to_json()
+    }
+
+    pub(crate) fn keystore(&self, log_n: u8, password: &str) -> Result<Keystore, TransactionError> {
+        let secret = match &self.seed {
+            Some(seed) => Secret::new(seed.expose_secret().to_vec()),
+            None => Secret::new(self.secret_key.to_bytes().to_vec()),
+        };
+        Keystore::encrypt(self.address, secret.expose_secret(), password, log_n)
+    }
+
     #[cfg(test)]
     pub(crate) fn raw_public_key(&self) -> &[u8] {
-        self.key_pair.public.as_bytes()
+        self.public_key.as_bytes()
     }
 
     /// Get the public key address of the account
@@ -53,20 +152,21 @@ impl Account {
         self.address
     }
 
-    /// Get the human readable mnemonic of the 32 byte seed
-    pub fn mnemonic(&self) -> String {
-        mnemonic::from_key(&self.seed).unwrap()
+    /// Get the human readable mnemonic of the 32 byte seed, None if the account was derived
+    pub fn mnemonic(&self) -> Option<Secret<String>> {
+        self.seed
+            .as_ref()
+            .map(|seed| Secret::new(mnemonic::from_key(seed.expose_secret()).unwrap()))
     }
 
-    /// Get the 32 byte seed
-    pub fn seed(&self) -> [u8; 32] {
-        self.seed
+    /// Get a copy of the 32 byte seed, None if the account was derived
+    pub fn seed(&self) -> Option<SecretSeed> {
+        self.seed.clone()
     }
 
     /// Sign the given bytes, and wrap in Signature.
     fn generate_raw_sig(&self, bytes: &[u8]) -> Signature {
-        use ed25519_dalek::Signer;
-        let signature = self.key_pair.sign(bytes);
+        let signature = self.secret_key.sign(bytes, &self.public_key);
         Signature(signature.to_bytes())
     }
 
@@ -109,9 +209,33 @@ impl Account {
             transaction: transaction.clone(),
             transaction_id: transaction.id()?,
             sig: TransactionSignature::Single(self.generate_transaction_sig(transaction)?),
+            auth_address: None,
         })
     }
 
+    /// Sign transactions and generate single signature SignedTransactions, in the same order.
+    /// With the `parallel` feature the transactions are signed on multiple threads.
+    pub fn sign_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<Vec<SignedTransaction>, TransactionError> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            transactions
+                .par_iter()
+                .map(|t| self.sign_transaction(t))
+                .collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            transactions
+                .iter()
+                .map(|t| self.sign_transaction(t))
+                .collect()
+        }
+    }
+
     /// Creates transaction multi signature corresponding to multisign addresses, inserting own signature
     pub fn init_transaction_msig(
         &self,
@@ -219,6 +343,12 @@ impl Account {
     }
 }
 
+impl Debug for Account {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Account {{ address: {} }}", self.address)
+    }
+}
+
 /// Convenience CompiledTeal "view", used to sign as contract account.
 /// The program hash is interpreted as an address.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -248,6 +378,7 @@ impl ContractAccount {
                 args,
                 sig: LogicSignature::ContractAccount,
             }),
+            auth_address: None,
         })
     }
 }
@@ -256,6 +387,7 @@ impl ContractAccount {
 mod tests {
     use crate::account::Account;
     use algonaut_core::Address;
+    use algonaut_crypto::bip32_ed25519::{DerivationPath, DerivationScheme, ExtendedPrivateKey};
     use algonaut_crypto::{mnemonic, Signature};
     use data_encoding::BASE64;
     use rand::Rng;
@@ -288,7 +420,7 @@ mod tests {
     fn test_to_mnemonic() {
         let mnemonic = "actress tongue harbor tray suspect odor load topple vocal avoid ignore apple lunch unknown tissue museum once switch captain place lemon sail outdoor absent creek";
         let account = Account::from_mnemonic(mnemonic).unwrap();
-        assert_eq!(account.mnemonic(), mnemonic);
+        assert_eq!(account.mnemonic().unwrap().expose_secret(), mnemonic);
     }
 
     #[test]
@@ -326,7 +458,54 @@ mod tests {
     fn test_to_seed() {
         let mnemonic = "actress tongue harbor tray suspect odor load topple vocal avoid ignore apple lunch unknown tissue museum once switch captain place lemon sail outdoor absent creek";
         let seed = mnemonic::to_key(mnemonic).unwrap();
-        let account = Account::from_seed(seed);
-        assert_eq!(mnemonic::to_key(&account.mnemonic()).unwrap(), seed);
+        let account = Account::from_seed(seed.expose_secret());
+        assert_eq!(
+            mnemonic::to_key(account.mnemonic().unwrap().expose_secret())
+                .unwrap()
+                .expose_secret(),
+            seed.expose_secret()
+        );
+    }
+
+    #[test]
+    fn test_from_derivation_path() {
+        let mnemonic = "salon zoo engage submit smile frost later decide wing sight chaos renew lizard rely canal coral scene hobby scare step bus leaf tobacco slice";
+        let root = ExtendedPrivateKey::from_mnemonic(mnemonic, "").unwrap();
+
+        let account = Account::from_derivation_path(
+            &root,
+            &DerivationPath::algorand(0, 0),
+            DerivationScheme::Peikert,
+        )
+        .unwrap();
+        let same_account = Account::from_derivation_path(
+            &root,
+            &"m/44'/283'/0'/0/0".parse().unwrap(),
+            DerivationScheme::Peikert,
+        )
+        .unwrap();
+        let other_account = Account::from_derivation_path(
+            &root,
+            &DerivationPath::algorand(0, 1),
+            DerivationScheme::Peikert,
+        )
+        .unwrap();
+
+        assert_eq!(account.address(), same_account.address());
+        assert_ne!(account.address(), other_account.address());
+        assert!(account.mnemonic().is_none());
+
+        let message = b"message";
+        let signature = account.generate_sig(message);
+        assert!(account.address().verify_bytes(message, &signature));
+    }
+
+    #[test]
+    fn test_from_shares() {
+        let account = Account::generate();
+        let shares =
+            algonaut_crypto::shamir::split(account.seed().unwrap().expose_secret(), 2, 3).unwrap();
+        let recovered = Account::from_shares(&shares[1..]).unwrap();
+        assert_eq!(account.address(), recovered.address());
     }
 }
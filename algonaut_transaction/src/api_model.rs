@@ -1,10 +1,17 @@
 use std::convert::{TryFrom, TryInto};
 
 use algonaut_core::{
-    Address, CompiledTealBytes, LogicSignature, MicroAlgos, MultisigSignature, Round, SignedLogic,
-    ToMsgPack, VotePk, VrfPk,
+    Address, CompiledTealBytes, LogicSignature, MicroAlgos, MultisigSignature, MultisigSubsig,
+    Round, SignedLogic, StateProofPk, ToMsgPack, VotePk, VrfPk,
 };
-use algonaut_crypto::{HashDigest, Signature};
+use algonaut_crypto::{Ed25519PublicKey, HashDigest, Signature};
+use algonaut_model::indexer::v2::{
+    AssetParams as IndexerAssetParams, BoxReference as IndexerBoxReference, OnCompletion,
+    StateSchema as IndexerStateSchema, Transaction as IndexerTransaction,
+    TransactionApplication as IndexerTransactionApplication, TransactionSignatureLogicsig,
+    TransactionSignatureMultisig, TransactionType as IndexerTransactionType,
+};
+use data_encoding::BASE64;
 use num_traits::Num;
 use serde::{Deserialize, Serialize};
 
@@ -13,7 +20,7 @@ use crate::{
     transaction::{
         ApplicationCallOnComplete, ApplicationCallTransaction, AssetAcceptTransaction,
         AssetClawbackTransaction, AssetConfigurationTransaction, AssetFreezeTransaction,
-        AssetParams, AssetTransferTransaction, KeyRegistration, Payment, StateSchema,
+        AssetParams, AssetTransferTransaction, BoxReference, KeyRegistration, Payment, StateSchema,
         TransactionSignature,
     },
     tx_group::TxGroup,
@@ -70,6 +77,9 @@ pub struct ApiTransaction {
     #[serde(rename = "apat", skip_serializing_if = "Option::is_none")]
     pub accounts: Option<Vec<Address>>,
 
+    #[serde(rename = "apbx", skip_serializing_if = "Option::is_none")]
+    pub boxes: Option<Vec<ApiBoxReference>>,
+
     #[serde(rename = "apep", skip_serializing_if = "Option::is_none")]
     pub extra_pages: Option<u64>,
 
@@ -155,6 +165,9 @@ pub struct ApiTransaction {
     #[serde(rename = "snd")]
     pub sender: Address,
 
+    #[serde(rename = "sprfkey", skip_serializing_if = "Option::is_none")]
+    pub state_proof_pk: Option<StateProofPk>,
+
     #[serde(rename = "type")]
     pub type_: String,
 
@@ -177,6 +190,38 @@ pub struct ApiTransaction {
 #[derive(Default, Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct AppArgument(#[serde(with = "serde_bytes")] Vec<u8>);
 
+#[derive(Default, Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ApiBoxReference {
+    #[serde(rename = "i", skip_serializing_if = "Option::is_none")]
+    pub app_index: Option<u64>,
+
+    #[serde(
+        default,
+        rename = "n",
+        with = "serde_bytes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<Vec<u8>>,
+}
+
+impl From<BoxReference> for ApiBoxReference {
+    fn from(b: BoxReference) -> Self {
+        ApiBoxReference {
+            app_index: num_as_api_option(b.app_index),
+            name: vec_as_api_option(b.name),
+        }
+    }
+}
+
+impl From<ApiBoxReference> for BoxReference {
+    fn from(b: ApiBoxReference) -> Self {
+        BoxReference {
+            app_index: num_from_api_option(b.app_index),
+            name: b.name.unwrap_or_default(),
+        }
+    }
+}
+
 impl From<Transaction> for ApiTransaction {
     fn from(t: Transaction) -> Self {
         let mut api_t = ApiTransaction {
@@ -202,6 +247,7 @@ impl From<Transaction> for ApiTransaction {
             approval_program: None,
             asset_params: None,
             foreign_assets: None,
+            boxes: None,
             accounts: None,
             foreign_apps: None,
             global_state_schema: None,
@@ -222,6 +268,7 @@ impl From<Transaction> for ApiTransaction {
             vote_last: None,
             xfer: None,
             nonparticipating: None,
+            state_proof_pk: None,
             extra_pages: None,
         };
 
@@ -238,6 +285,7 @@ impl From<Transaction> for ApiTransaction {
                 api_t.vote_last = reg.vote_last;
                 api_t.vote_key_dilution = reg.vote_key_dilution.and_then(num_as_api_option);
                 api_t.nonparticipating = reg.nonparticipating.and_then(bool_as_api_option);
+                api_t.state_proof_pk = reg.state_proof_pk;
             }
             TransactionType::AssetConfigurationTransaction(config) => {
                 api_t.asset_params = config.to_owned().params.map(|p| p.into());
@@ -287,6 +335,11 @@ impl From<Transaction> for ApiTransaction {
                     .and_then(vec_as_api_option);
                 api_t.foreign_apps = call.foreign_apps.clone().and_then(vec_as_api_option);
                 api_t.foreign_assets = call.foreign_assets.clone().and_then(vec_as_api_option);
+                api_t.boxes = call
+                    .boxes
+                    .clone()
+                    .map(|boxes| boxes.into_iter().map(|b| b.into()).collect())
+                    .and_then(vec_as_api_option);
                 api_t.global_state_schema =
                     call.to_owned().global_state_schema.and_then(|s| s.into());
                 api_t.local_state_schema =
@@ -319,6 +372,7 @@ impl TryFrom<ApiTransaction> for Transaction {
                 vote_last: api_t.vote_last,
                 vote_key_dilution: Some(num_from_api_option(api_t.vote_key_dilution)),
                 nonparticipating: api_t.nonparticipating,
+                state_proof_pk: api_t.state_proof_pk,
             }),
             "acfg" => {
                 TransactionType::AssetConfigurationTransaction(AssetConfigurationTransaction {
@@ -354,6 +408,9 @@ impl TryFrom<ApiTransaction> for Transaction {
                     clear_state_program: api_t.clear_state_program.map(CompiledTealBytes),
                     foreign_apps: api_t.foreign_apps,
                     foreign_assets: api_t.foreign_assets,
+                    boxes: api_t
+                        .boxes
+                        .map(|boxes| boxes.into_iter().map(|b| b.into()).collect()),
 
                     global_state_schema: parse_state_schema(
                         on_complete.clone(),
@@ -464,6 +521,7 @@ impl TryFrom<ApiSignedTransaction> for SignedTransaction {
             transaction: api_t.transaction.clone().try_into()?,
             transaction_id: api_t.transaction_id.clone(),
             sig: transaction_signature(&api_t)?,
+            auth_address: api_t.auth_address,
         })
     }
 }
@@ -482,7 +540,7 @@ fn transaction_signature(
     }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ApiAssetParams {
     #[serde(rename = "am", skip_serializing_if = "Option::is_none")]
     pub meta_data_hash: Option<Vec<u8>>,
@@ -575,6 +633,9 @@ pub struct ApiSignedTransaction {
     #[serde(rename = "msig", skip_serializing_if = "Option::is_none")]
     pub msig: Option<MultisigSignature>,
 
+    #[serde(rename = "sgnr", skip_serializing_if = "Option::is_none")]
+    pub auth_address: Option<Address>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sig: Option<Signature>,
 
@@ -596,6 +657,7 @@ impl From<SignedTransaction> for ApiSignedTransaction {
             sig,
             msig,
             lsig: lsig.map(|l| l.into()),
+            auth_address: t.auth_address,
             transaction: t.transaction.into(),
             transaction_id: t.transaction_id,
         }
@@ -739,6 +801,394 @@ impl TryFrom<ApiSignedLogic> for SignedLogic {
     }
 }
 
+/// Converts a transaction record of the indexer, which has to include the signature.
+///
+/// The indexer's JSON is mapped to the encoding of the transaction that was signed, so the id is preserved:
+/// it's computed again and checked against the id of the record.
+impl TryFrom<&IndexerTransaction> for SignedTransaction {
+    type Error = TransactionError;
+
+    fn try_from(t: &IndexerTransaction) -> Result<Self, Self::Error> {
+        let signature = t.signature.as_ref().ok_or_else(|| {
+            TransactionError::Deserialization(format!("Transaction {} has no signature", t.id))
+        })?;
+        let api_t = ApiSignedTransaction {
+            lsig: signature
+                .logicsig
+                .as_ref()
+                .map(indexer_logic_signature)
+                .transpose()?,
+            msig: signature
+                .multisig
+                .as_ref()
+                .map(indexer_multisig_signature)
+                .transpose()?,
+            sig: signature
+                .sig
+                .as_deref()
+                .map(|sig| decode_base64_signature("sig", sig))
+                .transpose()?,
+            auth_address: t.auth_addr,
+            transaction: indexer_api_transaction(t)?,
+            transaction_id: t.id.clone(),
+        };
+
+        let signed_t: SignedTransaction = api_t.try_into()?;
+        let id = signed_t.transaction.id()?;
+        if id != t.id {
+            return Err(TransactionError::Deserialization(format!(
+                "Transaction id mismatch: indexer: {}, converted: {}",
+                t.id, id
+            )));
+        }
+        Ok(signed_t)
+    }
+}
+
+impl TryFrom<IndexerTransaction> for SignedTransaction {
+    type Error = TransactionError;
+
+    fn try_from(t: IndexerTransaction) -> Result<Self, Self::Error> {
+        (&t).try_into()
+    }
+}
+
+fn indexer_api_transaction(t: &IndexerTransaction) -> Result<ApiTransaction, TransactionError> {
+    let mut api_t = ApiTransaction {
+        asset_amount: None,
+        asset_close_to: None,
+        frozen: None,
+        amount: None,
+        app_arguments: None,
+        on_complete: None,
+        approval_program: None,
+        asset_params: None,
+        foreign_assets: None,
+        accounts: None,
+        boxes: None,
+        extra_pages: None,
+        foreign_apps: None,
+        global_state_schema: None,
+        app_id: None,
+        local_state_schema: None,
+        clear_state_program: None,
+        asset_receiver: None,
+        asset_sender: None,
+        config_asset: None,
+        close_reminder_to: None,
+        freeze_account: None,
+        asset_id: None,
+        fee: num_as_api_option(t.fee).map(MicroAlgos),
+        first_valid: num_as_api_option(t.first_valid).map(Round),
+        genesis_id: t.genesis_id.clone().and_then(str_as_api_option),
+        genesis_hash: t.genesis_hash,
+        group: t
+            .group
+            .as_deref()
+            .map(|group| decode_base64_hash("group", group))
+            .transpose()?,
+        last_valid: num_as_api_option(t.last_valid.0).map(Round),
+        lease: t
+            .lease
+            .as_deref()
+            .map(|lease| decode_base64_hash("lease", lease))
+            .transpose()?,
+        nonparticipating: None,
+        note: t
+            .note
+            .as_deref()
+            .map(|note| decode_base64("note", note))
+            .transpose()?
+            .and_then(vec_as_api_option),
+        receiver: None,
+        rekey_to: t.rekey_to,
+        selection_pk: None,
+        sender: t.sender,
+        state_proof_pk: None,
+        type_: String::new(),
+        vote_first: None,
+        vote_key_dilution: None,
+        vote_pk: None,
+        vote_last: None,
+        xfer: None,
+    };
+
+    match &t.tx_type {
+        IndexerTransactionType::Payment => {
+            let payment = indexer_transaction_fields(&t.payment_transaction, "payment")?;
+            api_t.type_ = "pay".to_owned();
+            api_t.receiver = Some(payment.receiver);
+            api_t.amount = num_as_api_option(payment.amount.0);
+            api_t.close_reminder_to = payment.close_remainder_to;
+        }
+        IndexerTransactionType::KeyRegistration => {
+            let keyreg = indexer_transaction_fields(&t.keyreg_transaction, "keyreg")?;
+            api_t.type_ = "keyreg".to_owned();
+            api_t.vote_pk = keyreg
+                .vote_participation_key
+                .as_deref()
+                .map(|key| decode_base64_32("vote-participation-key", key).map(VotePk))
+                .transpose()?;
+            api_t.selection_pk = keyreg
+                .selection_participation_key
+                .as_deref()
+                .map(|key| decode_base64_32("selection-participation-key", key).map(VrfPk))
+                .transpose()?;
+            api_t.vote_first = keyreg
+                .vote_first_valid
+                .and_then(num_as_api_option)
+                .map(Round);
+            api_t.vote_last = keyreg
+                .vote_last_valid
+                .and_then(num_as_api_option)
+                .map(Round);
+            api_t.vote_key_dilution = keyreg.vote_key_dilution.and_then(num_as_api_option);
+            api_t.nonparticipating = keyreg.non_participation.and_then(bool_as_api_option);
+            api_t.state_proof_pk = keyreg
+                .state_proof_key
+                .as_deref()
+                .map(|key| decode_base64_64("state-proof-key", key).map(StateProofPk))
+                .transpose()?;
+        }
+        IndexerTransactionType::AssetConfigurationTransaction => {
+            let config = indexer_transaction_fields(&t.asset_config_transaction, "asset config")?;
+            api_t.type_ = "acfg".to_owned();
+            api_t.config_asset = config.asset_id.and_then(num_as_api_option);
+            api_t.asset_params = config
+                .params
+                .as_ref()
+                .map(indexer_asset_params)
+                .transpose()?
+                // Destroy transactions have no params: the indexer returns them with zero values
+                .filter(|params| params != &ApiAssetParams::default());
+        }
+        IndexerTransactionType::AssetTransferTransaction => {
+            let transfer =
+                indexer_transaction_fields(&t.asset_transfer_transaction, "asset transfer")?;
+            api_t.type_ = "axfer".to_owned();
+            api_t.xfer = Some(transfer.asset_id);
+            api_t.asset_amount = num_as_api_option(transfer.amount);
+            api_t.asset_receiver = Some(transfer.receiver);
+            api_t.asset_sender = transfer.sender;
+            api_t.asset_close_to = transfer.close_to;
+        }
+        IndexerTransactionType::AssetFreezeTransaction => {
+            let freeze = indexer_transaction_fields(&t.asset_freeze_transaction, "asset freeze")?;
+            api_t.type_ = "afrz".to_owned();
+            api_t.freeze_account = Some(freeze.address);
+            api_t.asset_id = Some(freeze.asset_id);
+            api_t.frozen = bool_as_api_option(freeze.new_freeze_status);
+        }
+        IndexerTransactionType::ApplicationTransaction => {
+            let app = indexer_transaction_fields(&t.application_transaction, "application")?;
+            api_t.type_ = "appl".to_owned();
+            api_t.app_id = num_as_api_option(app.application_id);
+            api_t.on_complete = num_as_api_option(indexer_on_completion_to_int(&app.on_completion));
+            api_t.accounts = app.accounts.clone().and_then(vec_as_api_option);
+            api_t.app_arguments = app
+                .application_args
+                .as_ref()
+                .map(|args| {
+                    args.iter()
+                        .map(|arg| decode_base64("application-args", arg).map(AppArgument))
+                        .collect()
+                })
+                .transpose()?
+                .and_then(vec_as_api_option);
+            api_t.approval_program = app
+                .approval_program
+                .clone()
+                .and_then(|program| vec_as_api_option(program.0));
+            api_t.clear_state_program = app
+                .clear_state_program
+                .clone()
+                .and_then(|program| vec_as_api_option(program.0));
+            api_t.foreign_apps = app.foreign_apps.clone().and_then(vec_as_api_option);
+            api_t.foreign_assets = app.foreign_assets.clone().and_then(vec_as_api_option);
+            api_t.global_state_schema = app
+                .global_state_schema
+                .as_ref()
+                .and_then(indexer_state_schema);
+            api_t.local_state_schema = app
+                .local_state_schema
+                .as_ref()
+                .and_then(indexer_state_schema);
+            api_t.extra_pages = app.extra_program_pages.and_then(num_as_api_option);
+            api_t.boxes = app
+                .box_references
+                .as_ref()
+                .map(|boxes| {
+                    boxes
+                        .iter()
+                        .map(|b| indexer_box_reference(app, b))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?
+                .and_then(vec_as_api_option);
+        }
+    }
+    Ok(api_t)
+}
+
+fn indexer_transaction_fields<'a, T>(
+    fields: &'a Option<T>,
+    type_name: &str,
+) -> Result<&'a T, TransactionError> {
+    fields.as_ref().ok_or_else(|| {
+        TransactionError::Deserialization(format!("{} transaction fields missing", type_name))
+    })
+}
+
+/// The indexer returns the app id of the box, while transactions reference the app
+/// by its index: 0 for the called app, otherwise 1 + the position in the foreign apps.
+fn indexer_box_reference(
+    app: &IndexerTransactionApplication,
+    box_ref: &IndexerBoxReference,
+) -> Result<ApiBoxReference, TransactionError> {
+    let app_index = if box_ref.app == 0 || box_ref.app == app.application_id {
+        0
+    } else {
+        app.foreign_apps
+            .iter()
+            .flatten()
+            .position(|id| *id == box_ref.app)
+            .map(|position| position as u64 + 1)
+            .ok_or_else(|| {
+                TransactionError::Deserialization(format!(
+                    "Box reference to app {} not in the foreign apps",
+                    box_ref.app
+                ))
+            })?
+    };
+    Ok(BoxReference::new(app_index, box_ref.name.clone()).into())
+}
+
+fn indexer_asset_params(params: &IndexerAssetParams) -> Result<ApiAssetParams, TransactionError> {
+    Ok(ApiAssetParams {
+        meta_data_hash: vec_as_api_option(params.metadata_hash.clone()),
+        asset_name: params.name.clone().and_then(str_as_api_option),
+        url: params.url.clone().and_then(str_as_api_option),
+        clawback: params.clawback,
+        decimals: num_as_api_option(u32::try_from(params.decimals).map_err(|_| {
+            TransactionError::Deserialization(format!("Invalid decimals: {}", params.decimals))
+        })?),
+        default_frozen: params.default_frozen.and_then(bool_as_api_option),
+        freeze: params.freeze,
+        manager: params.manager,
+        reserve: params.reserve,
+        total: num_as_api_option(params.total),
+        unit_name: params.unit_name.clone().and_then(str_as_api_option),
+    })
+}
+
+fn indexer_state_schema(schema: &IndexerStateSchema) -> Option<ApiStateSchema> {
+    StateSchema {
+        number_ints: schema.num_uint,
+        number_byteslices: schema.num_byte_slice,
+    }
+    .into()
+}
+
+fn indexer_on_completion_to_int(on_completion: &OnCompletion) -> u32 {
+    application_call_on_complete_to_int(&match on_completion {
+        OnCompletion::Noop => ApplicationCallOnComplete::NoOp,
+        OnCompletion::Optin => ApplicationCallOnComplete::OptIn,
+        OnCompletion::Closeout => ApplicationCallOnComplete::CloseOut,
+        OnCompletion::Clear => ApplicationCallOnComplete::ClearState,
+        OnCompletion::Update => ApplicationCallOnComplete::UpdateApplication,
+        OnCompletion::Delete => ApplicationCallOnComplete::DeleteApplication,
+    })
+}
+
+fn indexer_logic_signature(
+    lsig: &TransactionSignatureLogicsig,
+) -> Result<ApiSignedLogic, TransactionError> {
+    Ok(ApiSignedLogic {
+        args: lsig
+            .args
+            .iter()
+            .flatten()
+            .map(|arg| decode_base64("args", arg).map(ApiSignedLogicArg))
+            .collect::<Result<_, _>>()?,
+        logic: lsig.logic.0.clone(),
+        msig: lsig
+            .multisig_signature
+            .as_ref()
+            .map(indexer_multisig_signature)
+            .transpose()?,
+        sig: lsig
+            .signature
+            .as_deref()
+            .map(|sig| decode_base64_signature("signature", sig))
+            .transpose()?,
+    })
+}
+
+fn indexer_multisig_signature(
+    msig: &TransactionSignatureMultisig,
+) -> Result<MultisigSignature, TransactionError> {
+    let subsigs = msig
+        .subsignature
+        .iter()
+        .flatten()
+        .map(|subsig| {
+            let key = subsig.public_key.as_deref().ok_or_else(|| {
+                TransactionError::Deserialization("Multisig public key missing".to_owned())
+            })?;
+            Ok(MultisigSubsig {
+                key: Ed25519PublicKey(decode_base64_32("public-key", key)?),
+                sig: subsig
+                    .signature
+                    .as_deref()
+                    .map(|sig| decode_base64_signature("signature", sig))
+                    .transpose()?,
+            })
+        })
+        .collect::<Result<_, TransactionError>>()?;
+    Ok(MultisigSignature {
+        subsigs,
+        threshold: multisig_u8("threshold", msig.threshold)?,
+        version: multisig_u8("version", msig.version)?,
+    })
+}
+
+fn multisig_u8(field: &str, value: Option<u64>) -> Result<u8, TransactionError> {
+    let value = num_from_api_option(value);
+    u8::try_from(value).map_err(|_| {
+        TransactionError::Deserialization(format!("Invalid multisig {}: {}", field, value))
+    })
+}
+
+fn decode_base64(field: &str, s: &str) -> Result<Vec<u8>, TransactionError> {
+    BASE64
+        .decode(s.as_bytes())
+        .map_err(|e| TransactionError::Deserialization(format!("Invalid {}: {}", field, e)))
+}
+
+fn decode_base64_32(field: &str, s: &str) -> Result<[u8; 32], TransactionError> {
+    decode_base64(field, s)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| {
+            TransactionError::Deserialization(format!("Invalid {} length: {}", field, bytes.len()))
+        })
+}
+
+fn decode_base64_hash(field: &str, s: &str) -> Result<HashDigest, TransactionError> {
+    Ok(HashDigest(decode_base64_32(field, s)?))
+}
+
+fn decode_base64_64(field: &str, s: &str) -> Result<[u8; 64], TransactionError> {
+    decode_base64(field, s)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| {
+            TransactionError::Deserialization(format!("Invalid {} length: {}", field, bytes.len()))
+        })
+}
+
+fn decode_base64_signature(field: &str, s: &str) -> Result<Signature, TransactionError> {
+    Ok(Signature(decode_base64_64(field, s)?))
+}
+
 /// See [ApiTransaction] doc
 fn num_as_api_option<T: Num>(n: T) -> Option<T> {
     if n.is_zero() {
@@ -860,4 +1310,293 @@ mod tests {
 
         assert_eq!(lsig, lsig_deserialized);
     }
+
+    #[test]
+    fn test_serialize_box_references() {
+        let t = crate::TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(10),
+            Round(1010),
+            HashDigest([3; 32]),
+            crate::builder::CallApplication::new(Address([1; 32]), 5)
+                .foreign_apps(vec![6])
+                .boxes(vec![
+                    BoxReference::new(0, b"name".to_vec()),
+                    BoxReference::new(1, vec![]),
+                ])
+                .build(),
+        )
+        .build();
+
+        let api_t: ApiTransaction = t.clone().into();
+        let boxes = api_t.boxes.clone().unwrap();
+        // Zero values are omitted
+        assert_eq!(None, boxes[0].app_index);
+        assert_eq!(None, boxes[1].name);
+
+        let serialized = rmp_serde::to_vec_named(&api_t).unwrap();
+        let deserialized: ApiTransaction = rmp_serde::from_slice(&serialized).unwrap();
+        let t_deserialized: Transaction = deserialized.try_into().unwrap();
+
+        assert_eq!(t, t_deserialized);
+    }
+
+    fn indexer_transaction(
+        signed_t: &SignedTransaction,
+        fields: serde_json::Value,
+    ) -> IndexerTransaction {
+        let t = &signed_t.transaction;
+        let mut json = serde_json::json!({
+            "fee": t.fee.0,
+            "first-valid": t.first_valid.0,
+            "genesis-hash": BASE64.encode(&t.genesis_hash.0),
+            "id": signed_t.transaction_id,
+            "last-valid": t.last_valid.0,
+            "sender": t.sender().to_string(),
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        // From a string, as the model's base64 fields are deserialized from borrowed strings
+        serde_json::from_str(&json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_indexer_payment_conversion() {
+        let account = crate::account::Account::generate();
+        let t = crate::TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(10),
+            Round(1010),
+            HashDigest([3; 32]),
+            crate::Pay::new(account.address(), Address([2; 32]), MicroAlgos(5)).build(),
+        )
+        .genesis_id("testnet-v1.0".to_owned())
+        .group(HashDigest([4; 32]))
+        .lease(HashDigest([5; 32]))
+        .note(vec![1, 2, 3])
+        .build();
+        let signed_t = account.sign_transaction(&t).unwrap();
+        let sig = match signed_t.sig {
+            TransactionSignature::Single(sig) => sig,
+            _ => panic!("Expected a single signature"),
+        };
+
+        let indexer_t = indexer_transaction(
+            &signed_t,
+            serde_json::json!({
+                "genesis-id": "testnet-v1.0",
+                "group": BASE64.encode(&[4; 32]),
+                "lease": BASE64.encode(&[5; 32]),
+                "note": BASE64.encode(&[1, 2, 3]),
+                "payment-transaction": {
+                    "amount": 5,
+                    "close-amount": 0,
+                    "receiver": Address([2; 32]).to_string(),
+                },
+                "signature": { "sig": BASE64.encode(&sig.0) },
+                "tx-type": "pay",
+            }),
+        );
+
+        assert_eq!(signed_t, (&indexer_t).try_into().unwrap());
+    }
+
+    #[test]
+    fn test_indexer_application_conversion() {
+        let program = CompiledTealBytes(vec![
+            0x01, 0x20, 0x01, 0x01, 0x22, // int 1
+        ]);
+        let contract_account = crate::account::ContractAccount {
+            address: Address([1; 32]),
+            program: program.clone(),
+        };
+        let t = crate::TxnBuilder::new(
+            MicroAlgos(2000),
+            Round(10),
+            Round(1010),
+            HashDigest([3; 32]),
+            crate::CreateApplication::new(
+                contract_account.address,
+                program.clone(),
+                program.clone(),
+                StateSchema {
+                    number_ints: 1,
+                    number_byteslices: 0,
+                },
+                StateSchema {
+                    number_ints: 0,
+                    number_byteslices: 0,
+                },
+            )
+            .app_arguments(vec![vec![7, 8], vec![]])
+            .extra_pages(1)
+            .build(),
+        )
+        .build();
+        let signed_t = contract_account.sign(&t, vec![vec![9]]).unwrap();
+
+        let indexer_t = indexer_transaction(
+            &signed_t,
+            serde_json::json!({
+                "application-transaction": {
+                    "application-args": [BASE64.encode(&[7, 8]), ""],
+                    "application-id": 0,
+                    "approval-program": BASE64.encode(&program.0),
+                    "clear-state-program": BASE64.encode(&program.0),
+                    "extra-program-pages": 1,
+                    "global-state-schema": { "num-byte-slice": 0, "num-uint": 1 },
+                    "local-state-schema": { "num-byte-slice": 0, "num-uint": 0 },
+                    "on-completion": "noop",
+                },
+                "signature": {
+                    "logicsig": {
+                        "args": [BASE64.encode(&[9])],
+                        "logic": BASE64.encode(&program.0),
+                    },
+                },
+                "tx-type": "appl",
+            }),
+        );
+
+        assert_eq!(signed_t, indexer_t.try_into().unwrap());
+    }
+
+    #[test]
+    fn test_indexer_rekeyed_keyreg_conversion() {
+        let sender = Address([1; 32]);
+        let auth_account = crate::account::Account::generate();
+        let t = crate::TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(10),
+            Round(1010),
+            HashDigest([3; 32]),
+            crate::RegisterKey::online(
+                sender,
+                VotePk([4; 32]),
+                VrfPk([5; 32]),
+                Round(100),
+                Round(200),
+                10,
+            )
+            .state_proof_pk(StateProofPk([6; 64]))
+            .build(),
+        )
+        .build();
+        let mut signed_t = auth_account.sign_transaction(&t).unwrap();
+        signed_t.auth_address = Some(auth_account.address());
+        let sig = match signed_t.sig {
+            TransactionSignature::Single(sig) => sig,
+            _ => panic!("Expected a single signature"),
+        };
+
+        let indexer_t = indexer_transaction(
+            &signed_t,
+            serde_json::json!({
+                "auth-addr": auth_account.address().to_string(),
+                "keyreg-transaction": {
+                    "selection-participation-key": BASE64.encode(&[5; 32]),
+                    "state-proof-key": BASE64.encode(&[6; 64]),
+                    "vote-first-valid": 100,
+                    "vote-key-dilution": 10,
+                    "vote-last-valid": 200,
+                    "vote-participation-key": BASE64.encode(&[4; 32]),
+                },
+                "signature": { "sig": BASE64.encode(&sig.0) },
+                "tx-type": "keyreg",
+            }),
+        );
+
+        let converted = SignedTransaction::try_from(&indexer_t).unwrap();
+        assert_eq!(signed_t, converted);
+        assert!(converted.verify().unwrap());
+
+        // Encoded as sgnr
+        let api_t: ApiSignedTransaction = converted.into();
+        let deserialized: ApiSignedTransaction =
+            rmp_serde::from_slice(&rmp_serde::to_vec_named(&api_t).unwrap()).unwrap();
+        assert_eq!(Some(auth_account.address()), deserialized.auth_address);
+    }
+
+    #[test]
+    fn test_indexer_box_references_conversion() {
+        let account = crate::account::Account::generate();
+        let t = crate::TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(10),
+            Round(1010),
+            HashDigest([3; 32]),
+            crate::builder::CallApplication::new(account.address(), 5)
+                .foreign_apps(vec![7, 8])
+                .boxes(vec![
+                    BoxReference::new(0, b"own".to_vec()),
+                    BoxReference::new(2, b"foreign".to_vec()),
+                ])
+                .build(),
+        )
+        .build();
+        let signed_t = account.sign_transaction(&t).unwrap();
+        let sig = match signed_t.sig {
+            TransactionSignature::Single(sig) => sig,
+            _ => panic!("Expected a single signature"),
+        };
+        let call = |foreign_box_app: u64| {
+            serde_json::json!({
+                "application-transaction": {
+                    "application-id": 5,
+                    "box-references": [
+                        { "app": 5, "name": BASE64.encode(b"own") },
+                        { "app": foreign_box_app, "name": BASE64.encode(b"foreign") },
+                    ],
+                    "foreign-apps": [7, 8],
+                    "on-completion": "noop",
+                },
+                "signature": { "sig": BASE64.encode(&sig.0) },
+                "tx-type": "appl",
+            })
+        };
+
+        let indexer_t = indexer_transaction(&signed_t, call(8));
+        assert_eq!(signed_t, indexer_t.try_into().unwrap());
+
+        let indexer_t = indexer_transaction(&signed_t, call(9));
+        assert!(SignedTransaction::try_from(indexer_t).is_err());
+    }
+
+    #[test]
+    fn test_indexer_conversion_checks_id() {
+        let account = crate::account::Account::generate();
+        let t = crate::TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(10),
+            Round(1010),
+            HashDigest([3; 32]),
+            crate::FreezeAsset::new(account.address(), Address([2; 32]), 12, true).build(),
+        )
+        .build();
+        let signed_t = account.sign_transaction(&t).unwrap();
+        let sig = match signed_t.sig {
+            TransactionSignature::Single(sig) => sig,
+            _ => panic!("Expected a single signature"),
+        };
+        let freeze = serde_json::json!({
+            "asset-freeze-transaction": {
+                "address": Address([2; 32]).to_string(),
+                "asset-id": 12,
+                "new-freeze-status": true,
+            },
+            "signature": { "sig": BASE64.encode(&sig.0) },
+            "tx-type": "afrz",
+        });
+
+        let mut indexer_t = indexer_transaction(&signed_t, freeze);
+        assert_eq!(signed_t, SignedTransaction::try_from(&indexer_t).unwrap());
+
+        indexer_t.fee += 1;
+        assert!(SignedTransaction::try_from(&indexer_t).is_err());
+
+        indexer_t.fee -= 1;
+        indexer_t.signature = None;
+        assert!(SignedTransaction::try_from(&indexer_t).is_err());
+    }
 }
@@ -0,0 +1,200 @@
+use crate::error::TransactionError;
+use crate::transaction::StateSchema;
+use algonaut_core::Address;
+pub use algonaut_derive::AppState;
+pub use algonaut_model::state::{AppState, TealKeyValue, TealValue};
+use std::convert::TryFrom;
+
+/// A struct mapped to the global or local state of an application.
+///
+/// Usually derived with `#[derive(AppState)]`, which maps each field to the key with its name
+/// (or the one set with `#[app_state(key = "...")]`), so the Rust model and the schema of the
+/// application are defined in one place. The derived code uses `::algonaut_transaction`: when
+/// depending only on the `algonaut` facade, add `#[app_state(crate = "algonaut::transaction")]`.
+pub trait TypedAppState: Sized {
+    /// Schema of the state, to be passed to [crate::CreateApplication].
+    fn state_schema() -> StateSchema;
+
+    /// Decodes the state. Fails if a key is missing, or its value has a different type.
+    fn from_app_state(state: &AppState) -> Result<Self, TransactionError>;
+
+    /// Decodes the state from the key-values returned by algod or the indexer,
+    /// e.g. `ApplicationParams::global_state` or `ApplicationLocalState::key_value`.
+    fn from_key_values(key_values: &[TealKeyValue]) -> Result<Self, TransactionError> {
+        Self::from_app_state(&AppState::from(key_values))
+    }
+}
+
+/// A value which can be stored in application state.
+pub trait AppStateValue: Sized {
+    /// Whether the value is stored as a uint. Otherwise it's stored as a byte slice.
+    const IS_UINT: bool;
+
+    /// None if the value has a different type or length.
+    fn from_teal_value(value: &TealValue) -> Option<Self>;
+}
+
+impl AppStateValue for u64 {
+    const IS_UINT: bool = true;
+
+    fn from_teal_value(value: &TealValue) -> Option<Self> {
+        match value {
+            TealValue::Uint(uint) => Some(*uint),
+            TealValue::Bytes(_) => None,
+        }
+    }
+}
+
+impl AppStateValue for Vec<u8> {
+    const IS_UINT: bool = false;
+
+    fn from_teal_value(value: &TealValue) -> Option<Self> {
+        match value {
+            TealValue::Bytes(bytes) => Some(bytes.clone()),
+            TealValue::Uint(_) => None,
+        }
+    }
+}
+
+impl<const N: usize> AppStateValue for [u8; N] {
+    const IS_UINT: bool = false;
+
+    fn from_teal_value(value: &TealValue) -> Option<Self> {
+        match value {
+            TealValue::Bytes(bytes) => <[u8; N]>::try_from(bytes.as_slice()).ok(),
+            TealValue::Uint(_) => None,
+        }
+    }
+}
+
+impl AppStateValue for Address {
+    const IS_UINT: bool = false;
+
+    fn from_teal_value(value: &TealValue) -> Option<Self> {
+        <[u8; 32]>::from_teal_value(value).map(Address)
+    }
+}
+
+/// Used by `#[derive(AppState)]`.
+#[doc(hidden)]
+pub fn decode_value<T: AppStateValue>(state: &AppState, key: &str) -> Result<T, TransactionError> {
+    let value = state
+        .get(key)
+        .ok_or_else(|| TransactionError::MissingAppStateKey {
+            key: key.to_owned(),
+        })?;
+    T::from_teal_value(value).ok_or_else(|| TransactionError::InvalidAppStateValue {
+        key: key.to_owned(),
+    })
+}
+
+/// Used by `#[derive(AppState)]`: the schema of fields with the given [AppStateValue::IS_UINT].
+#[doc(hidden)]
+pub fn state_schema(is_uint: &[bool]) -> StateSchema {
+    let number_ints = is_uint.iter().filter(|is_uint| **is_uint).count() as u64;
+    StateSchema {
+        number_ints,
+        number_byteslices: is_uint.len() as u64 - number_ints,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(AppState, Debug, PartialEq)]
+    struct Auction {
+        #[app_state(key = "own")]
+        owner: Address,
+        bid: u64,
+        rounds: u64,
+        id: [u8; 4],
+        memo: Vec<u8>,
+    }
+
+    #[derive(AppState, Debug, PartialEq)]
+    #[app_state(crate = "crate")]
+    struct Counter {
+        count: u64,
+    }
+
+    fn state() -> AppState {
+        vec![
+            (b"own".to_vec(), TealValue::Bytes(vec![1; 32])),
+            (b"bid".to_vec(), TealValue::Uint(10)),
+            (b"rounds".to_vec(), TealValue::Uint(1000)),
+            (b"id".to_vec(), TealValue::Bytes(vec![1, 2, 3, 4])),
+            (b"memo".to_vec(), TealValue::Bytes(vec![])),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_derive_state_schema() {
+        assert_eq!(
+            StateSchema {
+                number_ints: 2,
+                number_byteslices: 3
+            },
+            Auction::state_schema()
+        );
+    }
+
+    #[test]
+    fn test_derive_decode() {
+        let key_values: Vec<TealKeyValue> = state()
+            .iter()
+            .map(|(key, value)| TealKeyValue {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect();
+
+        assert_eq!(
+            Auction {
+                owner: Address([1; 32]),
+                bid: 10,
+                rounds: 1000,
+                id: [1, 2, 3, 4],
+                memo: vec![],
+            },
+            Auction::from_key_values(&key_values).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_derive_crate_path() {
+        let state = vec![(b"count".to_vec(), TealValue::Uint(3))]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            Counter { count: 3 },
+            Counter::from_app_state(&state).unwrap()
+        );
+        assert_eq!(
+            StateSchema {
+                number_ints: 1,
+                number_byteslices: 0
+            },
+            Counter::state_schema()
+        );
+    }
+
+    #[test]
+    fn test_derive_decode_errors() {
+        let mut state = state();
+        state.insert(b"id".to_vec(), TealValue::Bytes(vec![1, 2, 3]));
+        assert!(matches!(
+            Auction::from_app_state(&state),
+            Err(TransactionError::InvalidAppStateValue { key }) if key == "id"
+        ));
+
+        let mut state = self::state();
+        state.remove("own");
+        assert!(matches!(
+            Auction::from_app_state(&state),
+            Err(TransactionError::MissingAppStateKey { key }) if key == "own"
+        ));
+    }
+}
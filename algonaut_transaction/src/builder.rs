@@ -1,10 +1,12 @@
 use crate::transaction::{
     ApplicationCallOnComplete, ApplicationCallTransaction, AssetAcceptTransaction,
     AssetClawbackTransaction, AssetConfigurationTransaction, AssetFreezeTransaction, AssetParams,
-    AssetTransferTransaction, KeyRegistration, Payment, StateSchema, Transaction, TransactionType,
+    AssetTransferTransaction, BoxReference, KeyRegistration, Payment, StateSchema, Transaction,
+    TransactionType,
 };
 use algonaut_core::{
-    Address, CompiledTealBytes, MicroAlgos, Round, SuggestedTransactionParams, VotePk, VrfPk,
+    Address, CompiledTealBytes, MicroAlgos, Round, StateProofPk, SuggestedTransactionParams,
+    VotePk, VrfPk,
 };
 use algonaut_crypto::HashDigest;
 
@@ -140,6 +142,7 @@ pub struct RegisterKey {
     vote_last: Option<Round>,
     vote_key_dilution: Option<u64>,
     nonparticipating: Option<bool>,
+    state_proof_pk: Option<StateProofPk>,
 }
 
 impl RegisterKey {
@@ -159,6 +162,7 @@ impl RegisterKey {
             vote_last: Some(vote_last),
             vote_key_dilution: Some(vote_key_dilution),
             nonparticipating: None,
+            state_proof_pk: None,
         }
     }
 
@@ -171,6 +175,7 @@ impl RegisterKey {
             vote_last: None,
             vote_key_dilution: None,
             nonparticipating: None,
+            state_proof_pk: None,
         }
     }
 
@@ -183,9 +188,16 @@ impl RegisterKey {
             vote_last: None,
             vote_key_dilution: None,
             nonparticipating: Some(nonparticipating),
+            state_proof_pk: None,
         }
     }
 
+    /// Registers the root of the state proof keys, along with the online keys.
+    pub fn state_proof_pk(mut self, state_proof_pk: StateProofPk) -> Self {
+        self.state_proof_pk = Some(state_proof_pk);
+        self
+    }
+
     pub fn build(self) -> TransactionType {
         TransactionType::KeyRegistration(KeyRegistration {
             sender: self.sender,
@@ -195,6 +207,7 @@ impl RegisterKey {
             vote_last: self.vote_last,
             vote_key_dilution: self.vote_key_dilution,
             nonparticipating: self.nonparticipating,
+            state_proof_pk: self.state_proof_pk,
         })
     }
 }
@@ -563,6 +576,7 @@ pub struct CreateApplication {
     clear_state_program: Option<CompiledTealBytes>,
     foreign_apps: Option<Vec<u64>>,
     foreign_assets: Option<Vec<u64>>,
+    boxes: Option<Vec<BoxReference>>,
     global_state_schema: Option<StateSchema>,
     local_state_schema: Option<StateSchema>,
     extra_pages: u64,
@@ -584,6 +598,7 @@ impl CreateApplication {
             clear_state_program: Some(clear_state_program),
             foreign_apps: None,
             foreign_assets: None,
+            boxes: None,
             global_state_schema: Some(global_state_schema),
             local_state_schema: Some(local_state_schema),
             extra_pages: 0,
@@ -610,6 +625,11 @@ impl CreateApplication {
         self
     }
 
+    pub fn boxes(mut self, boxes: Vec<BoxReference>) -> Self {
+        self.boxes = Some(boxes);
+        self
+    }
+
     pub fn extra_pages(mut self, extra_pages: u64) -> Self {
         self.extra_pages = extra_pages;
         self
@@ -626,6 +646,7 @@ impl CreateApplication {
             clear_state_program: self.clear_state_program,
             foreign_apps: self.foreign_apps,
             foreign_assets: self.foreign_assets,
+            boxes: self.boxes,
             global_state_schema: self.global_state_schema,
             local_state_schema: self.local_state_schema,
             extra_pages: self.extra_pages,
@@ -643,6 +664,7 @@ pub struct UpdateApplication {
     clear_state_program: Option<CompiledTealBytes>,
     foreign_apps: Option<Vec<u64>>,
     foreign_assets: Option<Vec<u64>>,
+    boxes: Option<Vec<BoxReference>>,
 }
 
 impl UpdateApplication {
@@ -661,6 +683,7 @@ impl UpdateApplication {
             clear_state_program: Some(clear_state_program),
             foreign_apps: None,
             foreign_assets: None,
+            boxes: None,
         }
     }
 
@@ -684,6 +707,11 @@ impl UpdateApplication {
         self
     }
 
+    pub fn boxes(mut self, boxes: Vec<BoxReference>) -> Self {
+        self.boxes = Some(boxes);
+        self
+    }
+
     pub fn build(self) -> TransactionType {
         TransactionType::ApplicationCallTransaction(ApplicationCallTransaction {
             sender: self.sender,
@@ -695,6 +723,7 @@ impl UpdateApplication {
             clear_state_program: self.clear_state_program,
             foreign_apps: self.foreign_apps,
             foreign_assets: self.foreign_assets,
+            boxes: self.boxes,
             global_state_schema: None,
             local_state_schema: None,
             extra_pages: 0,
@@ -710,6 +739,7 @@ pub struct CallApplication {
     app_arguments: Option<Vec<Vec<u8>>>,
     foreign_apps: Option<Vec<u64>>,
     foreign_assets: Option<Vec<u64>>,
+    boxes: Option<Vec<BoxReference>>,
 }
 
 impl CallApplication {
@@ -721,6 +751,7 @@ impl CallApplication {
             app_arguments: None,
             foreign_apps: None,
             foreign_assets: None,
+            boxes: None,
         }
     }
 
@@ -744,6 +775,11 @@ impl CallApplication {
         self
     }
 
+    pub fn boxes(mut self, boxes: Vec<BoxReference>) -> Self {
+        self.boxes = Some(boxes);
+        self
+    }
+
     pub fn build(self) -> TransactionType {
         TransactionType::ApplicationCallTransaction(ApplicationCallTransaction {
             sender: self.sender,
@@ -755,6 +791,7 @@ impl CallApplication {
             clear_state_program: None,
             foreign_apps: self.foreign_apps,
             foreign_assets: self.foreign_assets,
+            boxes: self.boxes,
             global_state_schema: None,
             local_state_schema: None,
             extra_pages: 0,
@@ -770,6 +807,7 @@ pub struct ClearApplication {
     app_arguments: Option<Vec<Vec<u8>>>,
     foreign_apps: Option<Vec<u64>>,
     foreign_assets: Option<Vec<u64>>,
+    boxes: Option<Vec<BoxReference>>,
 }
 
 impl ClearApplication {
@@ -781,6 +819,7 @@ impl ClearApplication {
             app_arguments: None,
             foreign_apps: None,
             foreign_assets: None,
+            boxes: None,
         }
     }
 
@@ -804,6 +843,11 @@ impl ClearApplication {
         self
     }
 
+    pub fn boxes(mut self, boxes: Vec<BoxReference>) -> Self {
+        self.boxes = Some(boxes);
+        self
+    }
+
     pub fn build(self) -> TransactionType {
         TransactionType::ApplicationCallTransaction(ApplicationCallTransaction {
             sender: self.sender,
@@ -815,6 +859,7 @@ impl ClearApplication {
             clear_state_program: None,
             foreign_apps: self.foreign_apps,
             foreign_assets: self.foreign_assets,
+            boxes: self.boxes,
             global_state_schema: None,
             local_state_schema: None,
             extra_pages: 0,
@@ -830,6 +875,7 @@ pub struct CloseApplication {
     app_arguments: Option<Vec<Vec<u8>>>,
     foreign_apps: Option<Vec<u64>>,
     foreign_assets: Option<Vec<u64>>,
+    boxes: Option<Vec<BoxReference>>,
 }
 
 impl CloseApplication {
@@ -841,6 +887,7 @@ impl CloseApplication {
             app_arguments: None,
             foreign_apps: None,
             foreign_assets: None,
+            boxes: None,
         }
     }
 
@@ -864,6 +911,11 @@ impl CloseApplication {
         self
     }
 
+    pub fn boxes(mut self, boxes: Vec<BoxReference>) -> Self {
+        self.boxes = Some(boxes);
+        self
+    }
+
     pub fn build(self) -> TransactionType {
         TransactionType::ApplicationCallTransaction(ApplicationCallTransaction {
             sender: self.sender,
@@ -875,6 +927,7 @@ impl CloseApplication {
             clear_state_program: None,
             foreign_apps: self.foreign_apps,
             foreign_assets: self.foreign_assets,
+            boxes: self.boxes,
             global_state_schema: None,
             local_state_schema: None,
             extra_pages: 0,
@@ -890,6 +943,7 @@ pub struct DeleteApplication {
     app_arguments: Option<Vec<Vec<u8>>>,
     foreign_apps: Option<Vec<u64>>,
     foreign_assets: Option<Vec<u64>>,
+    boxes: Option<Vec<BoxReference>>,
 }
 
 impl DeleteApplication {
@@ -901,6 +955,7 @@ impl DeleteApplication {
             app_arguments: None,
             foreign_apps: None,
             foreign_assets: None,
+            boxes: None,
         }
     }
 
@@ -924,6 +979,11 @@ impl DeleteApplication {
         self
     }
 
+    pub fn boxes(mut self, boxes: Vec<BoxReference>) -> Self {
+        self.boxes = Some(boxes);
+        self
+    }
+
     pub fn build(self) -> TransactionType {
         TransactionType::ApplicationCallTransaction(ApplicationCallTransaction {
             sender: self.sender,
@@ -935,6 +995,7 @@ impl DeleteApplication {
             clear_state_program: None,
             foreign_apps: self.foreign_apps,
             foreign_assets: self.foreign_assets,
+            boxes: self.boxes,
             global_state_schema: None,
             local_state_schema: None,
             extra_pages: 0,
@@ -950,6 +1011,7 @@ pub struct OptInApplication {
     app_arguments: Option<Vec<Vec<u8>>>,
     foreign_apps: Option<Vec<u64>>,
     foreign_assets: Option<Vec<u64>>,
+    boxes: Option<Vec<BoxReference>>,
 }
 
 impl OptInApplication {
@@ -961,6 +1023,7 @@ impl OptInApplication {
             app_arguments: None,
             foreign_apps: None,
             foreign_assets: None,
+            boxes: None,
         }
     }
 
@@ -984,6 +1047,11 @@ impl OptInApplication {
         self
     }
 
+    pub fn boxes(mut self, boxes: Vec<BoxReference>) -> Self {
+        self.boxes = Some(boxes);
+        self
+    }
+
     pub fn build(self) -> TransactionType {
         TransactionType::ApplicationCallTransaction(ApplicationCallTransaction {
             sender: self.sender,
@@ -995,6 +1063,7 @@ impl OptInApplication {
             clear_state_program: None,
             foreign_apps: self.foreign_apps,
             foreign_assets: self.foreign_assets,
+            boxes: self.boxes,
             global_state_schema: None,
             local_state_schema: None,
             extra_pages: 0,
@@ -14,16 +14,36 @@ pub enum TransactionError {
     InvalidNumberOfSubsignatures,
     #[error("Transaction msig public keys do not match.")]
     InvalidPublicKeyInMultisig,
+    #[error("Transaction msig subsignature does not verify.")]
+    InvalidSignatureInMultisig,
+    #[error("Multisig requires {} signatures, got {}.", threshold, signatures)]
+    InsufficientMultisigSignatures { threshold: u8, signatures: usize },
     #[error("Transaction msig has mismatched signatures.")]
     MismatchingSignatures,
     #[error("Empty transaction list.")]
     EmptyTransactionListError,
     #[error("Max group size is {}.", size)]
     MaxTransactionGroupSizeError { size: usize },
+    #[error("Fee payer index {} is out of bounds, group size is {}.", index, len)]
+    InvalidFeePayerIndex { index: usize, len: usize },
+    #[error("Box size {} exceeds the maximum of {} bytes.", size, max)]
+    InvalidBoxSize { size: u64, max: u64 },
     #[error("serde encode error {0}")]
     RmpSerdeError(#[from] rmp_serde::encode::Error),
     #[error("crypto error {0}")]
     MnemonicError(#[from] algonaut_crypto::error::CryptoError),
     #[error("Deserialization error: {0}")]
     Deserialization(String),
+    #[error("Keystore error: {0}")]
+    Keystore(String),
+    #[error("Participation keys error: {0}")]
+    ParticipationKeys(String),
+    #[error("Application state has no key '{}'.", key)]
+    MissingAppStateKey { key: String },
+    #[error("Application state key '{}' has an unexpected type or length.", key)]
+    InvalidAppStateValue { key: String },
+    #[error("ABI error: {0}")]
+    Abi(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
 }
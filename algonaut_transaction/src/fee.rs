@@ -0,0 +1,193 @@
+use crate::error::TransactionError;
+use crate::transaction::{SignerKind, Transaction};
+use algonaut_core::{MicroAlgos, SuggestedTransactionParams};
+
+/// Determines which transactions of a group pay the pooled fee.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FeePolicy {
+    /// The transaction at the given index pays the fee of the whole group.
+    /// The fee of the other transactions is set to zero.
+    Payer(usize),
+    /// The total fee is split evenly between all the transactions of the group.
+    /// The remainder of the division is added to the first transaction.
+    SplitEvenly,
+}
+
+/// Computes the fee required by a transaction group and distributes it according to a [FeePolicy].
+///
+/// Algorand pools the fees of a group: the group is valid as long as the sum of the fees covers
+/// the fee each transaction (and each inner transaction issued by application calls) would need
+/// on its own. The planner has to be applied before [crate::tx_group::TxGroup::assign_group_id],
+/// as changing the fee changes the transaction ids.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GroupFeePlanner {
+    fee_per_byte: MicroAlgos,
+    min_fee: MicroAlgos,
+    inner_transactions: u64,
+    signers: Vec<SignerKind>,
+    policy: FeePolicy,
+}
+
+impl GroupFeePlanner {
+    /// Convenience to initialize planner with suggested transaction params
+    pub fn with(params: &SuggestedTransactionParams, policy: FeePolicy) -> Self {
+        Self::new(params.fee, params.min_fee, policy)
+    }
+
+    pub fn new(fee_per_byte: MicroAlgos, min_fee: MicroAlgos, policy: FeePolicy) -> Self {
+        GroupFeePlanner {
+            fee_per_byte,
+            min_fee,
+            inner_transactions: 0,
+            signers: vec![],
+            policy,
+        }
+    }
+
+    /// Number of inner transactions expected to be issued by application calls of the group.
+    /// Each of them adds the min fee to the total.
+    pub fn inner_transactions(mut self, inner_transactions: u64) -> Self {
+        self.inner_transactions = inner_transactions;
+        self
+    }
+
+    /// Signer kinds of the transactions, in group order, used to estimate their size.
+    /// Transactions without an entry are assumed to be signed with a single signature.
+    pub fn signers(mut self, signers: Vec<SignerKind>) -> Self {
+        self.signers = signers;
+        self
+    }
+
+    /// The fee required to submit the group: the sum of the individual transaction fees
+    /// (at least min fee each) plus the min fee for every expected inner transaction.
+    ///
+    /// The encoded size grows with the fee, which isn't assigned yet: transactions are
+    /// estimated with the max fee, so the result covers whatever fee they end up with.
+    pub fn total_fee(&self, txns: &[&mut Transaction]) -> Result<MicroAlgos, TransactionError> {
+        let mut total = self.min_fee * self.inner_transactions;
+        for (i, t) in txns.iter().enumerate() {
+            let signer = self.signers.get(i).unwrap_or(&SignerKind::Single);
+            let mut placeholder = (**t).clone();
+            placeholder.fee = MicroAlgos(u64::MAX);
+            total = total
+                + self
+                    .min_fee
+                    .max(self.fee_per_byte * placeholder.estimate_size(signer)?);
+        }
+        Ok(total)
+    }
+
+    /// Sets the fee of the transactions according to the policy and returns the total fee.
+    pub fn assign_fees(&self, txns: Vec<&mut Transaction>) -> Result<MicroAlgos, TransactionError> {
+        if txns.is_empty() {
+            return Err(TransactionError::EmptyTransactionListError);
+        }
+        let total = self.total_fee(&txns)?;
+        let fees = self.split(total, txns.len())?;
+        for (t, fee) in txns.into_iter().zip(fees) {
+            t.fee = fee;
+        }
+        Ok(total)
+    }
+
+    fn split(&self, total: MicroAlgos, len: usize) -> Result<Vec<MicroAlgos>, TransactionError> {
+        match self.policy {
+            FeePolicy::Payer(index) => {
+                if index >= len {
+                    return Err(TransactionError::InvalidFeePayerIndex { index, len });
+                }
+                let mut fees = vec![MicroAlgos(0); len];
+                fees[index] = total;
+                Ok(fees)
+            }
+            FeePolicy::SplitEvenly => {
+                let share = total.0 / len as u64;
+                let remainder = total.0 % len as u64;
+                let mut fees = vec![MicroAlgos(share); len];
+                fees[0] = fees[0] + remainder;
+                Ok(fees)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Pay, TxnBuilder};
+    use algonaut_core::{Address, Round};
+    use algonaut_crypto::HashDigest;
+
+    fn payment(fee: MicroAlgos) -> Transaction {
+        TxnBuilder::new(
+            fee,
+            Round(1),
+            Round(1000),
+            HashDigest([0; 32]),
+            Pay::new(Address([1; 32]), Address([2; 32]), MicroAlgos(1_000_000)).build(),
+        )
+        .build()
+    }
+
+    #[test]
+    fn test_payer_covers_group() {
+        let t1 = &mut payment(MicroAlgos(1000));
+        let t2 = &mut payment(MicroAlgos(1000));
+        let t3 = &mut payment(MicroAlgos(1000));
+
+        let planner = GroupFeePlanner::new(MicroAlgos(0), MicroAlgos(1000), FeePolicy::Payer(1))
+            .inner_transactions(2);
+        let total = planner.assign_fees(vec![t1, t2, t3]).unwrap();
+
+        assert_eq!(MicroAlgos(5000), total);
+        assert_eq!(MicroAlgos(0), t1.fee);
+        assert_eq!(MicroAlgos(5000), t2.fee);
+        assert_eq!(MicroAlgos(0), t3.fee);
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        let t1 = &mut payment(MicroAlgos(0));
+        let t2 = &mut payment(MicroAlgos(0));
+        let t3 = &mut payment(MicroAlgos(0));
+
+        let planner = GroupFeePlanner::new(MicroAlgos(0), MicroAlgos(1000), FeePolicy::SplitEvenly)
+            .inner_transactions(1);
+        let total = planner.assign_fees(vec![t1, t2, t3]).unwrap();
+
+        assert_eq!(MicroAlgos(4000), total);
+        assert_eq!(MicroAlgos(1334), t1.fee);
+        assert_eq!(MicroAlgos(1333), t2.fee);
+        assert_eq!(MicroAlgos(1333), t3.fee);
+    }
+
+    #[test]
+    fn test_fee_per_byte_above_min_fee() {
+        let t1 = &mut payment(MicroAlgos(0));
+        let t2 = &mut payment(MicroAlgos(0));
+        let msig = SignerKind::Multisig {
+            keys: 3,
+            threshold: 2,
+        };
+
+        let planner = GroupFeePlanner::new(MicroAlgos(10), MicroAlgos(1000), FeePolicy::Payer(0))
+            .signers(vec![SignerKind::Single, msig.clone()]);
+        let total = planner.assign_fees(vec![t1, t2]).unwrap();
+
+        assert!(total > MicroAlgos(2000));
+        assert_eq!(total, t1.fee);
+        // The fee has to cover the size of the transactions with the fees they were assigned
+        let required = MicroAlgos(10) * t1.estimate_size(&SignerKind::Single).unwrap()
+            + MicroAlgos(10) * t2.estimate_size(&msig).unwrap();
+        assert!(total >= required);
+        // Overestimated by at most the encoded placeholder fee field ("fee" key and a uint64)
+        assert!(total <= required + MicroAlgos(10) * 13 * 2);
+    }
+
+    #[test]
+    fn test_invalid_payer_index() {
+        let t1 = &mut payment(MicroAlgos(1000));
+        let planner = GroupFeePlanner::new(MicroAlgos(0), MicroAlgos(1000), FeePolicy::Payer(1));
+        assert!(planner.assign_fees(vec![t1]).is_err());
+    }
+}
@@ -0,0 +1,405 @@
+use crate::account::Account;
+use crate::error::TransactionError;
+use algonaut_core::Address;
+use algonaut_crypto::secret::Secret;
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use data_encoding::BASE64;
+use rand::rngs::OsRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Version of the keystore layout, incremented on incompatible changes.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const KDF_SCRYPT: &str = "scrypt";
+const CIPHER_XCHACHA20POLY1305: &str = "xchacha20-poly1305";
+const KEYSTORE_EXTENSION: &str = "json";
+const TMP_EXTENSION: &str = "json.tmp";
+
+/// Default scrypt cost parameters: N = 2^15, r = 8, p = 1
+pub(crate) const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Upper bounds of the scrypt parameters read from keystore files, so a crafted file can't make
+/// key derivation allocate unbounded memory: 128 * r * 2^log_n bytes, i.e. 1 GiB at most.
+const MAX_SCRYPT_LOG_N: u8 = 20;
+const MAX_SCRYPT_P: u32 = 16;
+
+/// JSON layout of an encrypted account.
+/// The secret (the account's seed or, for derived accounts, its expanded secret key) is encrypted with
+/// XChaCha20-Poly1305, using a key derived from the password with scrypt.
+/// The address is authenticated as associated data.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    /// Base32 encoded
+    pub address: String,
+    pub kdf: KdfParams,
+    pub cipher: CipherParams,
+    /// Base64 encoded
+    pub ciphertext: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub name: String,
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    /// Base64 encoded
+    pub salt: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub name: String,
+    /// Base64 encoded
+    pub nonce: String,
+}
+
+impl Keystore {
+    pub(crate) fn encrypt(
+        address: Address,
+        secret: &[u8],
+        password: &str,
+        log_n: u8,
+    ) -> Result<Keystore, TransactionError> {
+        let salt: [u8; 32] = OsRng.gen();
+        let nonce: [u8; 24] = OsRng.gen();
+        let key = derive_key(password, &salt, log_n, SCRYPT_R, SCRYPT_P)?;
+
+        let ciphertext = XChaCha20Poly1305::new_from_slice(key.expose_secret())
+            .expect("32 bytes is a valid key length")
+            .encrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: secret,
+                    aad: &address.0,
+                },
+            )
+            .map_err(|_| TransactionError::Keystore("Encryption failed".to_owned()))?;
+
+        Ok(Keystore {
+            version: KEYSTORE_VERSION,
+            address: address.to_string(),
+            kdf: KdfParams {
+                name: KDF_SCRYPT.to_owned(),
+                log_n,
+                r: SCRYPT_R,
+                p: SCRYPT_P,
+                salt: BASE64.encode(&salt),
+            },
+            cipher: CipherParams {
+                name: CIPHER_XCHACHA20POLY1305.to_owned(),
+                nonce: BASE64.encode(&nonce),
+            },
+            ciphertext: BASE64.encode(&ciphertext),
+        })
+    }
+
+    pub(crate) fn decrypt(&self, password: &str) -> Result<Secret<Vec<u8>>, TransactionError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(TransactionError::Keystore(format!(
+                "Unsupported version: {}",
+                self.version
+            )));
+        }
+        if self.kdf.name != KDF_SCRYPT || self.cipher.name != CIPHER_XCHACHA20POLY1305 {
+            return Err(TransactionError::Keystore(format!(
+                "Unsupported kdf: {} or cipher: {}",
+                self.kdf.name, self.cipher.name
+            )));
+        }
+        let address = self.address()?;
+        let salt = decode_base64(&self.kdf.salt)?;
+        let nonce: [u8; 24] = decode_base64(&self.cipher.nonce)?
+            .try_into()
+            .map_err(|_| TransactionError::Keystore("Invalid nonce".to_owned()))?;
+        let ciphertext = decode_base64(&self.ciphertext)?;
+        let key = derive_key(password, &salt, self.kdf.log_n, self.kdf.r, self.kdf.p)?;
+
+        XChaCha20Poly1305::new_from_slice(key.expose_secret())
+            .expect("32 bytes is a valid key length")
+            .decrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &address.0,
+                },
+            )
+            .map(Secret::new)
+            .map_err(|_| {
+                TransactionError::Keystore("Wrong password or corrupted keystore".to_owned())
+            })
+    }
+
+    pub fn address(&self) -> Result<Address, TransactionError> {
+        Address::from_str(&self.address).map_err(TransactionError::Keystore)
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>, TransactionError> {
+        serde_json::to_vec_pretty(self).map_err(|e| TransactionError::Keystore(e.to_string()))
+    }
+
+    pub fn from_json(bytes: &[u8]) -> Result<Keystore, TransactionError> {
+        serde_json::from_slice(bytes).map_err(|e| TransactionError::Keystore(e.to_string()))
+    }
+}
+
+/// A directory of keystore files, one per account, named after the account's address.
+#[derive(Debug, Clone)]
+pub struct KeystoreDir {
+    path: PathBuf,
+}
+
+impl KeystoreDir {
+    /// Uses the directory at `path`, creating it if it doesn't exist.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<KeystoreDir, TransactionError> {
+        fs::create_dir_all(&path)?;
+        Ok(KeystoreDir {
+            path: path.as_ref().to_owned(),
+        })
+    }
+
+    /// Encrypts the account with `password` and writes it to the directory, replacing an existing file.
+    pub fn store(&self, account: &Account, password: &str) -> Result<Address, TransactionError> {
+        self.write(&account.address(), &account.to_keystore(password)?)?;
+        Ok(account.address())
+    }
+
+    /// Loads and decrypts the account with `address`.
+    pub fn load(&self, address: &Address, password: &str) -> Result<Account, TransactionError> {
+        Account::from_keystore(&fs::read(self.file(address))?, password)
+    }
+
+    /// Addresses of the accounts stored in the directory.
+    /// Files which aren't keystores, or can't be read, are skipped.
+    pub fn list(&self) -> Result<Vec<Address>, TransactionError> {
+        let mut addresses = vec![];
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(KEYSTORE_EXTENSION) {
+                continue;
+            }
+            let address = fs::read(path)
+                .map_err(TransactionError::from)
+                .and_then(|bytes| Keystore::from_json(&bytes))
+                .and_then(|keystore| keystore.address());
+            if let Ok(address) = address {
+                addresses.push(address);
+            }
+        }
+        Ok(addresses)
+    }
+
+    /// Removes the account with `address` from the directory.
+    pub fn remove(&self, address: &Address) -> Result<(), TransactionError> {
+        Ok(fs::remove_file(self.file(address))?)
+    }
+
+    /// Writes the file readable only by the owner, to a temporary file first and then renamed over
+    /// the account's file, so an interrupted write doesn't leave a truncated keystore behind.
+    fn write(&self, address: &Address, json: &[u8]) -> Result<(), TransactionError> {
+        let file = self.file(address);
+        let tmp = file.with_extension(TMP_EXTENSION);
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let written = options.open(&tmp).and_then(|mut f| {
+            f.write_all(json)?;
+            f.sync_all()
+        });
+        if let Err(e) = written.and_then(|_| fs::rename(&tmp, &file)) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    fn file(&self, address: &Address) -> PathBuf {
+        self.path
+            .join(address.to_string())
+            .with_extension(KEYSTORE_EXTENSION)
+    }
+}
+
+fn derive_key(
+    password: &str,
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> Result<Secret<[u8; 32]>, TransactionError> {
+    if log_n > MAX_SCRYPT_LOG_N || r != SCRYPT_R || p == 0 || p > MAX_SCRYPT_P {
+        return Err(TransactionError::Keystore(format!(
+            "Unsupported scrypt params: log_n {}, r {}, p {}",
+            log_n, r, p
+        )));
+    }
+    let params = scrypt::Params::new(log_n, r, p)
+        .map_err(|_| TransactionError::Keystore("Invalid scrypt params".to_owned()))?;
+    let mut key = Secret::new([0; 32]);
+    scrypt::scrypt(password.as_bytes(), salt, &params, key.expose_secret_mut())
+        .map_err(|_| TransactionError::Keystore("Invalid scrypt output length".to_owned()))?;
+    Ok(key)
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>, TransactionError> {
+    BASE64
+        .decode(s.as_bytes())
+        .map_err(|e| TransactionError::Keystore(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algonaut_crypto::bip32_ed25519::{DerivationPath, DerivationScheme, ExtendedPrivateKey};
+
+    // Low cost, to keep the tests fast
+    const TEST_LOG_N: u8 = 4;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let account = Account::generate();
+        let keystore = account.keystore(TEST_LOG_N, "password").unwrap();
+        let bytes = keystore.to_json().unwrap();
+
+        let decrypted = Account::from_keystore(&bytes, "password").unwrap();
+        assert_eq!(account.address(), decrypted.address());
+        assert_eq!(
+            account.seed().unwrap().expose_secret(),
+            decrypted.seed().unwrap().expose_secret()
+        );
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_derived_account() {
+        let mnemonic = "salon zoo engage submit smile frost later decide wing sight chaos renew lizard rely canal coral scene hobby scare step bus leaf tobacco slice";
+        let root = ExtendedPrivateKey::from_mnemonic(mnemonic, "").unwrap();
+        let account = Account::from_derivation_path(
+            &root,
+            &DerivationPath::algorand(0, 0),
+            DerivationScheme::Peikert,
+        )
+        .unwrap();
+        let bytes = account
+            .keystore(TEST_LOG_N, "password")
+            .unwrap()
+            .to_json()
+            .unwrap();
+
+        let decrypted = Account::from_keystore(&bytes, "password").unwrap();
+        assert_eq!(account.address(), decrypted.address());
+        assert_eq!(
+            account.generate_sig(b"message"),
+            decrypted.generate_sig(b"message")
+        );
+    }
+
+    #[test]
+    fn test_wrong_password() {
+        let account = Account::generate();
+        let bytes = account
+            .keystore(TEST_LOG_N, "password")
+            .unwrap()
+            .to_json()
+            .unwrap();
+        assert!(Account::from_keystore(&bytes, "wrong password").is_err());
+    }
+
+    #[test]
+    fn test_tampered_address() {
+        let account = Account::generate();
+        let mut keystore = account.keystore(TEST_LOG_N, "password").unwrap();
+        keystore.address = Account::generate().address().to_string();
+        assert!(Account::from_keystore(&keystore.to_json().unwrap(), "password").is_err());
+    }
+
+    #[test]
+    fn test_scrypt_params_bounds() {
+        let account = Account::generate();
+        let keystore = account.keystore(TEST_LOG_N, "password").unwrap();
+
+        let mut expensive = keystore.clone();
+        expensive.kdf.log_n = 40;
+        assert!(Account::from_keystore(&expensive.to_json().unwrap(), "password").is_err());
+
+        let mut expensive = keystore.clone();
+        expensive.kdf.r = 1 << 20;
+        assert!(Account::from_keystore(&expensive.to_json().unwrap(), "password").is_err());
+
+        let mut expensive = keystore;
+        expensive.kdf.p = u32::MAX;
+        assert!(Account::from_keystore(&expensive.to_json().unwrap(), "password").is_err());
+
+        assert!(account.keystore(MAX_SCRYPT_LOG_N + 1, "password").is_err());
+    }
+
+    #[test]
+    fn test_keystore_dir() {
+        let path = std::env::temp_dir().join(format!(
+            "algonaut-keystore-{}",
+            Account::generate().address()
+        ));
+        let dir = KeystoreDir::new(&path).unwrap();
+        let account1 = Account::generate();
+        let account2 = Account::generate();
+        dir.write(
+            &account1.address(),
+            &account1
+                .keystore(TEST_LOG_N, "password1")
+                .unwrap()
+                .to_json()
+                .unwrap(),
+        )
+        .unwrap();
+        dir.write(
+            &account2.address(),
+            &account2
+                .keystore(TEST_LOG_N, "password2")
+                .unwrap()
+                .to_json()
+                .unwrap(),
+        )
+        .unwrap();
+        // Not keystores
+        fs::write(path.join("notes.json"), b"{}").unwrap();
+        fs::write(path.join("corrupt.json"), b"{\"version\":").unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let metadata = fs::metadata(dir.file(&account1.address())).unwrap();
+            assert_eq!(0o600, metadata.permissions().mode() & 0o777);
+        }
+        assert!(!dir
+            .file(&account1.address())
+            .with_extension(TMP_EXTENSION)
+            .exists());
+
+        let mut addresses = dir.list().unwrap();
+        addresses.sort_by_key(|a| a.to_string());
+        let mut expected = vec![account1.address(), account2.address()];
+        expected.sort_by_key(|a| a.to_string());
+        assert_eq!(expected, addresses);
+
+        let loaded = dir.load(&account2.address(), "password2").unwrap();
+        assert_eq!(account2.address(), loaded.address());
+
+        dir.remove(&account1.address()).unwrap();
+        assert_eq!(vec![account2.address()], dir.list().unwrap());
+
+        fs::remove_dir_all(path).unwrap();
+    }
+}
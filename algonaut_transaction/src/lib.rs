@@ -1,8 +1,16 @@
+// Allows the code generated by algonaut_derive to refer to this crate from within it
+extern crate self as algonaut_transaction;
+
+pub mod abi;
 pub mod account;
 mod api_model;
+pub mod app_state;
 pub mod auction;
 pub mod builder;
 pub mod error;
+pub mod fee;
+pub mod keystore;
+pub mod participation;
 pub mod transaction;
 pub mod tx_group;
 pub mod url;
@@ -11,4 +19,4 @@ pub use builder::{
     AcceptAsset, ClawbackAsset, CreateApplication, CreateAsset, FreezeAsset, Pay, RegisterKey,
     TransferAsset, TxnBuilder,
 };
-pub use transaction::{SignedTransaction, Transaction, TransactionType};
+pub use transaction::{SignedTransaction, SignerKind, Transaction, TransactionType};
@@ -0,0 +1,618 @@
+//! Participation keys, generated offline.
+//!
+//! Participation keys consist of a VRF key, used to prove selection in the committees, and a two-level tree
+//! of ed25519 one-time signature keys, used to sign votes:
+//! - the voting key (`VotePk`) signs one subkey per batch of `key_dilution` rounds, and is then discarded
+//! - the subkey of a batch signs a fresh subkey for each round (offset) of the batch, which signs the vote
+//!
+//! Like in go-algorand, the offset subkeys are generated when signing, so only the batch subkeys are stored.
+//! Batch subkeys can be deleted after use with [ParticipationKeys::delete_before], for forward security.
+//!
+//! # File format
+//!
+//! [ParticipationKeys::save] writes a JSON file. Keys and signatures are base64 encoded,
+//! the parent address is base32 encoded:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "parent": "<address of the account registering the keys>",
+//!   "first_valid": 1000,
+//!   "last_valid": 3001000,
+//!   "key_dilution": 1733,
+//!   "vrf_secret_key": "<32 byte seed of the VRF key>",
+//!   "vote_public_key": "<voting key, which signed the batch subkeys>",
+//!   "first_batch": 0,
+//!   "batches": [
+//!     {
+//!       "public_key": "<subkey>",
+//!       "secret_key": "<32 byte seed of the subkey>",
+//!       "signature": "<voting key's signature of the subkey and batch number>"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! The file is not encrypted: it's written readable only by its owner on unix.
+
+use crate::builder::RegisterKey;
+use crate::error::TransactionError;
+use algonaut_core::{Address, Round, VotePk, VrfPk};
+use algonaut_crypto::secret::SecretSeed;
+use algonaut_crypto::vrf::{VrfProof, VrfSecretKey};
+use algonaut_crypto::{Ed25519PublicKey, Signature};
+use data_encoding::BASE64;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use rand::rngs::OsRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Version of the participation key file layout, incremented on incompatible changes.
+pub const PARTICIPATION_KEYS_VERSION: u32 = 1;
+
+/// Max number of batch subkeys generated for a key, e.g. 3000000 rounds with a key dilution of 46.
+pub const MAX_BATCHES: u64 = 1 << 16;
+
+/// Hash prefixes of the one-time signature subkey identifiers
+const BATCH_ID_PREFIX: &[u8] = b"OT1";
+const OFFSET_ID_PREFIX: &[u8] = b"OT2";
+
+/// Participation keys of an account for the rounds `first_valid` to `last_valid`.
+#[derive(Debug)]
+pub struct ParticipationKeys {
+    parent: Address,
+    first_valid: Round,
+    last_valid: Round,
+    key_dilution: u64,
+    vrf: VrfSecretKey,
+    vote_pk: VotePk,
+    first_batch: u64,
+    batches: Vec<BatchSubkey>,
+}
+
+#[derive(Debug)]
+struct BatchSubkey {
+    public_key: Ed25519PublicKey,
+    secret_key: SecretSeed,
+    signature: Signature,
+}
+
+/// A vote signature: the message signature with the chain of subkeys which certifies it.
+///
+/// Fields are declared in the canonical msgpack order of go-algorand's `OneTimeSignature`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OneTimeSignature {
+    /// Offset subkey
+    #[serde(rename = "p")]
+    pub pk: Ed25519PublicKey,
+    /// Signature of the offset subkey by the batch subkey
+    #[serde(rename = "p1s")]
+    pub pk1_sig: Signature,
+    /// Batch subkey
+    #[serde(rename = "p2")]
+    pub pk2: Ed25519PublicKey,
+    /// Signature of the batch subkey by the voting key
+    #[serde(rename = "p2s")]
+    pub pk2_sig: Signature,
+    /// Signature of the offset subkey in the old format, deprecated: always zero in new signatures
+    #[serde(
+        rename = "ps",
+        default = "zero_signature",
+        skip_serializing_if = "is_zero_signature"
+    )]
+    pub pk_sig_old: Signature,
+    /// Signature of the message by the offset subkey
+    #[serde(rename = "s")]
+    pub sig: Signature,
+}
+
+impl ParticipationKeys {
+    /// Generates participation keys of `parent` for the rounds `first_valid` to `last_valid`,
+    /// with a batch subkey every `key_dilution` rounds (see [ParticipationKeys::default_key_dilution]).
+    pub fn generate(
+        parent: Address,
+        first_valid: Round,
+        last_valid: Round,
+        key_dilution: u64,
+    ) -> Result<ParticipationKeys, TransactionError> {
+        if first_valid.0 > last_valid.0 {
+            return Err(participation_error(
+                "First valid round is after last valid round",
+            ));
+        }
+        if key_dilution == 0 {
+            return Err(participation_error("Key dilution must be positive"));
+        }
+
+        let first_batch = first_valid.0 / key_dilution;
+        let last_batch = last_valid.0 / key_dilution;
+        let batch_count = (last_batch - first_batch).saturating_add(1);
+        if batch_count > MAX_BATCHES {
+            return Err(participation_error(format!(
+                "{} batch subkeys exceed the maximum of {}, increase the key dilution",
+                batch_count, MAX_BATCHES
+            )));
+        }
+
+        let vote_key = keypair(&OsRng.gen());
+        let batches = (first_batch..=last_batch)
+            .map(|batch| {
+                let secret_key = SecretSeed::new(OsRng.gen());
+                let public_key =
+                    Ed25519PublicKey(keypair(secret_key.expose_secret()).public.to_bytes());
+                let signature = sign(&vote_key, &batch_id(&public_key, batch));
+                BatchSubkey {
+                    public_key,
+                    secret_key,
+                    signature,
+                }
+            })
+            .collect();
+
+        Ok(ParticipationKeys {
+            parent,
+            first_valid,
+            last_valid,
+            key_dilution,
+            vrf: VrfSecretKey::generate(),
+            vote_pk: VotePk(vote_key.public.to_bytes()),
+            first_batch,
+            batches,
+        })
+    }
+
+    /// Default key dilution of goal: 1 + sqrt(rounds), balancing the number of batch and offset subkeys.
+    pub fn default_key_dilution(first_valid: Round, last_valid: Round) -> u64 {
+        let rounds = last_valid.0.saturating_sub(first_valid.0);
+        let mut sqrt = (rounds as f64).sqrt() as u64;
+        // Correct the floating point approximation
+        while sqrt * sqrt > rounds {
+            sqrt -= 1;
+        }
+        while (sqrt + 1) * (sqrt + 1) <= rounds {
+            sqrt += 1;
+        }
+        1 + sqrt
+    }
+
+    pub fn parent(&self) -> Address {
+        self.parent
+    }
+
+    pub fn first_valid(&self) -> Round {
+        self.first_valid
+    }
+
+    pub fn last_valid(&self) -> Round {
+        self.last_valid
+    }
+
+    pub fn key_dilution(&self) -> u64 {
+        self.key_dilution
+    }
+
+    /// Public voting key, registered as `votekey`
+    pub fn vote_pk(&self) -> VotePk {
+        self.vote_pk
+    }
+
+    /// Public VRF key, registered as `selkey`
+    pub fn selection_pk(&self) -> VrfPk {
+        VrfPk(self.vrf.public_key())
+    }
+
+    /// Key registration transaction of the parent account, bringing it online with these keys.
+    pub fn register_key(&self) -> RegisterKey {
+        RegisterKey::online(
+            self.parent,
+            self.vote_pk(),
+            self.selection_pk(),
+            self.first_valid,
+            self.last_valid,
+            self.key_dilution,
+        )
+    }
+
+    /// VRF proof of `message` with the selection key.
+    pub fn prove(&self, message: &[u8]) -> VrfProof {
+        self.vrf.prove(message)
+    }
+
+    /// Signs `message` (including its hash prefix) for `round`, with a fresh offset subkey
+    /// signed by the subkey of the round's batch.
+    pub fn sign(&self, round: Round, message: &[u8]) -> Result<OneTimeSignature, TransactionError> {
+        if round.0 < self.first_valid.0 || round.0 > self.last_valid.0 {
+            return Err(participation_error(format!(
+                "Round {} is out of the key's validity range",
+                round
+            )));
+        }
+        let (batch, offset) = self.batch_and_offset(round);
+        let subkey = batch
+            .checked_sub(self.first_batch)
+            .and_then(|index| self.batches.get(index as usize))
+            .ok_or_else(|| {
+                participation_error(format!("The key of round {} was deleted", round))
+            })?;
+
+        let offset_key = keypair(&OsRng.gen());
+        let pk = Ed25519PublicKey(offset_key.public.to_bytes());
+        Ok(OneTimeSignature {
+            pk,
+            pk1_sig: sign(
+                &keypair(subkey.secret_key.expose_secret()),
+                &offset_id(&pk, batch, offset),
+            ),
+            pk2: subkey.public_key,
+            pk2_sig: subkey.signature,
+            pk_sig_old: zero_signature(),
+            sig: sign(&offset_key, message),
+        })
+    }
+
+    /// Deletes the batch subkeys of the batches before `round`'s, so past rounds can't be signed anymore.
+    pub fn delete_before(&mut self, round: Round) {
+        let (batch, _) = self.batch_and_offset(round);
+        let count = batch
+            .saturating_sub(self.first_batch)
+            .min(self.batches.len() as u64);
+        self.batches.drain(..count as usize);
+        self.first_batch += count;
+    }
+
+    /// Writes the keys to a JSON file (see the [module documentation](self)), replacing an existing file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), TransactionError> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(path)?.write_all(&self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<ParticipationKeys, TransactionError> {
+        Self::from_json(&fs::read(path)?)
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>, TransactionError> {
+        let file = ParticipationKeysFile {
+            version: PARTICIPATION_KEYS_VERSION,
+            parent: self.parent.to_string(),
+            first_valid: self.first_valid.0,
+            last_valid: self.last_valid.0,
+            key_dilution: self.key_dilution,
+            vrf_secret_key: BASE64.encode(self.vrf.expose_secret()),
+            vote_public_key: self.vote_pk.to_base64_str(),
+            first_batch: self.first_batch,
+            batches: self
+                .batches
+                .iter()
+                .map(|b| BatchSubkeyFile {
+                    public_key: BASE64.encode(&b.public_key.0),
+                    secret_key: BASE64.encode(b.secret_key.expose_secret()),
+                    signature: BASE64.encode(&b.signature.0),
+                })
+                .collect(),
+        };
+        serde_json::to_vec_pretty(&file).map_err(|e| participation_error(e.to_string()))
+    }
+
+    pub fn from_json(bytes: &[u8]) -> Result<ParticipationKeys, TransactionError> {
+        let file: ParticipationKeysFile =
+            serde_json::from_slice(bytes).map_err(|e| participation_error(e.to_string()))?;
+        if file.version != PARTICIPATION_KEYS_VERSION {
+            return Err(participation_error(format!(
+                "Unsupported version: {}",
+                file.version
+            )));
+        }
+        let batches = file
+            .batches
+            .iter()
+            .map(|b| {
+                Ok(BatchSubkey {
+                    public_key: Ed25519PublicKey(decode_base64(&b.public_key)?),
+                    secret_key: SecretSeed::new(decode_base64(&b.secret_key)?),
+                    signature: Signature(decode_base64(&b.signature)?),
+                })
+            })
+            .collect::<Result<Vec<_>, TransactionError>>()?;
+
+        if file.key_dilution == 0 {
+            return Err(participation_error("Key dilution must be positive"));
+        }
+        if file.first_valid > file.last_valid {
+            return Err(participation_error(
+                "First valid round is after last valid round",
+            ));
+        }
+        // The stored batches are the ones remaining in the validity range after deleting past rounds
+        let first_batch = file.first_valid / file.key_dilution;
+        let last_batch = file.last_valid / file.key_dilution;
+        let end_batch = file.first_batch.saturating_add(batches.len() as u64);
+        if file.first_batch < first_batch || end_batch > last_batch.saturating_add(1) {
+            return Err(participation_error(
+                "The batch subkeys don't fit the validity range",
+            ));
+        }
+
+        Ok(ParticipationKeys {
+            parent: Address::from_str(&file.parent).map_err(participation_error)?,
+            first_valid: Round(file.first_valid),
+            last_valid: Round(file.last_valid),
+            key_dilution: file.key_dilution,
+            vrf: VrfSecretKey::from_seed(SecretSeed::new(decode_base64(&file.vrf_secret_key)?)),
+            vote_pk: VotePk(decode_base64(&file.vote_public_key)?),
+            first_batch: file.first_batch,
+            batches,
+        })
+    }
+
+    fn batch_and_offset(&self, round: Round) -> (u64, u64) {
+        (round.0 / self.key_dilution, round.0 % self.key_dilution)
+    }
+}
+
+impl OneTimeSignature {
+    /// Verifies the signature of `message` for `round` by the voting key `vote_pk`, registered with `key_dilution`.
+    pub fn verify(
+        &self,
+        vote_pk: &VotePk,
+        round: Round,
+        key_dilution: u64,
+        message: &[u8],
+    ) -> bool {
+        if key_dilution == 0 {
+            return false;
+        }
+        let (batch, offset) = (round.0 / key_dilution, round.0 % key_dilution);
+        Ed25519PublicKey(vote_pk.0).verify(&batch_id(&self.pk2, batch), &self.pk2_sig)
+            && self
+                .pk2
+                .verify(&offset_id(&self.pk, batch, offset), &self.pk1_sig)
+            && self.pk.verify(message, &self.sig)
+    }
+}
+
+/// Identifier of a batch subkey, signed by the voting key
+#[derive(Serialize)]
+struct SubkeyBatchId<'a> {
+    #[serde(skip_serializing_if = "is_zero")]
+    batch: u64,
+    pk: &'a Ed25519PublicKey,
+}
+
+/// Identifier of an offset subkey, signed by the batch subkey
+#[derive(Serialize)]
+struct SubkeyOffsetId<'a> {
+    #[serde(skip_serializing_if = "is_zero")]
+    batch: u64,
+    #[serde(skip_serializing_if = "is_zero")]
+    off: u64,
+    pk: &'a Ed25519PublicKey,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ParticipationKeysFile {
+    version: u32,
+    parent: String,
+    first_valid: u64,
+    last_valid: u64,
+    key_dilution: u64,
+    vrf_secret_key: String,
+    vote_public_key: String,
+    first_batch: u64,
+    batches: Vec<BatchSubkeyFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BatchSubkeyFile {
+    public_key: String,
+    secret_key: String,
+    signature: String,
+}
+
+fn batch_id(pk: &Ed25519PublicKey, batch: u64) -> Vec<u8> {
+    hash_rep(BATCH_ID_PREFIX, &SubkeyBatchId { batch, pk })
+}
+
+fn offset_id(pk: &Ed25519PublicKey, batch: u64, offset: u64) -> Vec<u8> {
+    hash_rep(
+        OFFSET_ID_PREFIX,
+        &SubkeyOffsetId {
+            batch,
+            off: offset,
+            pk,
+        },
+    )
+}
+
+// Prefixed canonical msgpack, like go-algorand's HashRep. Fields are declared in alphabetical order.
+fn hash_rep<T: Serialize>(prefix: &[u8], value: &T) -> Vec<u8> {
+    let mut bytes = prefix.to_vec();
+    bytes.extend(rmp_serde::to_vec_named(value).expect("Subkey ids are serializable"));
+    bytes
+}
+
+fn keypair(seed: &[u8; 32]) -> Keypair {
+    let secret = SecretKey::from_bytes(seed).expect("32 bytes is a valid ed25519 seed");
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+fn sign(keypair: &Keypair, message: &[u8]) -> Signature {
+    Signature(keypair.sign(message).to_bytes())
+}
+
+fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
+fn zero_signature() -> Signature {
+    Signature([0; 64])
+}
+
+fn is_zero_signature(signature: &Signature) -> bool {
+    *signature == zero_signature()
+}
+
+fn participation_error<E: ToString>(e: E) -> TransactionError {
+    TransactionError::ParticipationKeys(e.to_string())
+}
+
+fn decode_base64<const N: usize>(s: &str) -> Result<[u8; N], TransactionError> {
+    BASE64
+        .decode(s.as_bytes())
+        .map_err(participation_error)?
+        .try_into()
+        .map_err(|_| participation_error("Invalid key length"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransactionType;
+
+    fn keys() -> ParticipationKeys {
+        ParticipationKeys::generate(Address([1; 32]), Round(1000), Round(1100), 10).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let keys = keys();
+        assert_eq!(11, keys.batches.len());
+        assert_eq!(100, keys.first_batch);
+
+        match keys.register_key().build() {
+            TransactionType::KeyRegistration(reg) => {
+                assert_eq!(Some(keys.vote_pk()), reg.vote_pk);
+                assert_eq!(Some(keys.selection_pk()), reg.selection_pk);
+                assert_eq!(Some(10), reg.vote_key_dilution);
+                assert_eq!(Some(Round(1100)), reg.vote_last);
+            }
+            _ => panic!("Expected a key registration"),
+        }
+
+        assert!(ParticipationKeys::generate(Address([1; 32]), Round(10), Round(9), 10).is_err());
+        assert!(ParticipationKeys::generate(Address([1; 32]), Round(1), Round(9), 0).is_err());
+        assert!(
+            ParticipationKeys::generate(Address([1; 32]), Round(0), Round(u64::MAX), 1).is_err()
+        );
+    }
+
+    #[test]
+    fn test_default_key_dilution() {
+        assert_eq!(
+            1733,
+            ParticipationKeys::default_key_dilution(Round(0), Round(3_000_000))
+        );
+        assert_eq!(
+            1,
+            ParticipationKeys::default_key_dilution(Round(5), Round(5))
+        );
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let keys = keys();
+        let signature = keys.sign(Round(1055), b"vote").unwrap();
+
+        assert!(signature.verify(&keys.vote_pk(), Round(1055), 10, b"vote"));
+        assert!(!signature.verify(&keys.vote_pk(), Round(1056), 10, b"vote"));
+        assert!(!signature.verify(&keys.vote_pk(), Round(1055), 10, b"other vote"));
+        assert!(!signature.verify(&VotePk([2; 32]), Round(1055), 10, b"vote"));
+        assert!(keys.sign(Round(1101), b"vote").is_err());
+    }
+
+    #[test]
+    fn test_signature_msgpack_field_order() {
+        let signature = keys().sign(Round(1055), b"vote").unwrap();
+        let bytes = rmp_serde::to_vec_named(&signature).unwrap();
+        // Map of 5 fields, without the zero "ps"
+        assert_eq!(0x85, bytes[0]);
+        let positions: Vec<_> = ["p", "p1s", "p2", "p2s", "s"]
+            .iter()
+            .map(|key| {
+                let mut encoded = vec![0xa0 | key.len() as u8];
+                encoded.extend(key.as_bytes());
+                bytes
+                    .windows(encoded.len())
+                    .position(|w| w == encoded.as_slice())
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(1, positions[0]);
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+
+        let with_old = OneTimeSignature {
+            pk_sig_old: Signature([1; 64]),
+            ..signature
+        };
+        let bytes = rmp_serde::to_vec_named(&with_old).unwrap();
+        assert_eq!(with_old, rmp_serde::from_slice(&bytes).unwrap());
+    }
+
+    #[test]
+    fn test_delete_before() {
+        let mut keys = keys();
+        keys.delete_before(Round(1055));
+        assert!(keys.sign(Round(1049), b"vote").is_err());
+        assert!(keys.sign(Round(1050), b"vote").is_ok());
+        keys.delete_before(Round(2000));
+        assert!(keys.batches.is_empty());
+    }
+
+    #[test]
+    fn test_vrf() {
+        let keys = keys();
+        let proof = keys.prove(b"seed");
+        assert!(keys.selection_pk().verify(&proof, b"seed").is_ok());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let keys = keys();
+        let loaded = ParticipationKeys::from_json(&keys.to_json().unwrap()).unwrap();
+
+        assert_eq!(keys.parent(), loaded.parent());
+        assert_eq!(keys.vote_pk(), loaded.vote_pk());
+        assert_eq!(keys.selection_pk(), loaded.selection_pk());
+        assert_eq!(keys.first_batch, loaded.first_batch);
+        let signature = loaded.sign(Round(1100), b"vote").unwrap();
+        assert!(signature.verify(&keys.vote_pk(), Round(1100), 10, b"vote"));
+    }
+
+    fn edited_json(edit: impl FnOnce(&mut serde_json::Value)) -> Vec<u8> {
+        let mut json: serde_json::Value =
+            serde_json::from_slice(&keys().to_json().unwrap()).unwrap();
+        edit(&mut json);
+        serde_json::to_vec(&json).unwrap()
+    }
+
+    #[test]
+    fn test_from_json_rejects_invalid_keys() {
+        let zero_dilution = edited_json(|json| json["key_dilution"] = 0.into());
+        assert!(ParticipationKeys::from_json(&zero_dilution).is_err());
+
+        let inverted_range = edited_json(|json| json["first_valid"] = 1200.into());
+        assert!(ParticipationKeys::from_json(&inverted_range).is_err());
+
+        let batches_before_range = edited_json(|json| json["first_batch"] = 99.into());
+        assert!(ParticipationKeys::from_json(&batches_before_range).is_err());
+
+        let batches_after_range = edited_json(|json| json["first_batch"] = 101.into());
+        assert!(ParticipationKeys::from_json(&batches_after_range).is_err());
+
+        let mut keys = keys();
+        keys.delete_before(Round(1055));
+        assert!(ParticipationKeys::from_json(&keys.to_json().unwrap()).is_ok());
+    }
+}
@@ -1,12 +1,11 @@
-use crate::account::Account;
 use crate::error::TransactionError;
 use algonaut_core::CompiledTealBytes;
 use algonaut_core::SignedLogic;
 use algonaut_core::ToMsgPack;
-use algonaut_core::{Address, MultisigSignature};
-use algonaut_core::{MicroAlgos, Round, VotePk, VrfPk};
-use algonaut_crypto::HashDigest;
+use algonaut_core::{Address, LogicSignature, MultisigSignature, MultisigSubsig, MULTISIG_VERSION};
+use algonaut_core::{MicroAlgos, Round, StateProofPk, VotePk, VrfPk};
 use algonaut_crypto::Signature;
+use algonaut_crypto::{Ed25519PublicKey, HashDigest};
 use data_encoding::BASE32_NOPAD;
 use sha2::Digest;
 
@@ -78,12 +77,14 @@ pub struct Transaction {
 }
 
 impl Transaction {
-    /// Creates a new transaction with a fee calculated based on `fee_per_byte`.
+    /// Creates a new transaction with a fee calculated based on `fee_per_byte`,
+    /// for the size the transaction will have once signed by `signer`.
     pub fn fee_per_byte(
         mut self,
         fee_per_byte: MicroAlgos,
+        signer: &SignerKind,
     ) -> Result<Transaction, TransactionError> {
-        self.fee = MIN_TXN_FEE.max(fee_per_byte * self.estimate_size()?);
+        self.fee = MIN_TXN_FEE.max(fee_per_byte * self.estimate_size(signer)?);
         Ok(self)
     }
 
@@ -107,10 +108,16 @@ impl Transaction {
         self.group = Some(group_id)
     }
 
-    // Estimates the size of the encoded transaction, used in calculating the fee
-    fn estimate_size(&self) -> Result<u64, TransactionError> {
-        let account = Account::generate();
-        let signed_transaction = account.sign_transaction(self)?;
+    /// Size of the encoded transaction once signed by `signer`, used in calculating the fee.
+    /// The transaction is signed with placeholder keys and signatures: the encoded length of
+    /// byte fields doesn't depend on their content, so the result is exact and no key is generated.
+    pub fn estimate_size(&self, signer: &SignerKind) -> Result<u64, TransactionError> {
+        let signed_transaction = SignedTransaction {
+            transaction: self.clone(),
+            transaction_id: String::new(),
+            sig: signer.placeholder_signature(),
+            auth_address: None,
+        };
         Ok(signed_transaction.to_msg_pack()?.len() as u64)
     }
 
@@ -177,6 +184,9 @@ pub struct KeyRegistration {
     /// longer earn rewards. It is unlikely that you will ever need to do this and exists mainly
     /// for economic-related functions on the network.
     pub nonparticipating: Option<bool>,
+
+    /// The root of the state proof keys, to participate in state proofs.
+    pub state_proof_pk: Option<StateProofPk>,
 }
 
 /// This is used to create, configure and destroy an asset depending on which fields are set.
@@ -347,6 +357,10 @@ pub struct ApplicationCallTransaction {
     /// clear-state-program. The access is read-only.
     pub foreign_assets: Option<Vec<u64>>,
 
+    /// Lists the boxes which may be accessed by this application's approval-program and
+    /// clear-state-program.
+    pub boxes: Option<Vec<BoxReference>>,
+
     /// Holds the maximum number of global state values defined within a StateSchema object.
     pub global_state_schema: Option<StateSchema>,
 
@@ -357,6 +371,36 @@ pub struct ApplicationCallTransaction {
     pub extra_pages: u64,
 }
 
+impl ApplicationCallTransaction {
+    /// Address of the called application's account.
+    /// None when creating the application, as its id is assigned when the transaction is confirmed.
+    pub fn app_address(&self) -> Option<Address> {
+        self.app_id.map(Address::for_application)
+    }
+
+    /// Accounts the call can access: the sender, the `accounts` array and the accounts of the
+    /// called and foreign applications, without duplicates.
+    pub fn accessible_accounts(&self) -> Vec<Address> {
+        let mut accounts = vec![self.sender];
+        accounts.extend(self.accounts.iter().flatten());
+        accounts.extend(self.app_address());
+        accounts.extend(
+            self.foreign_apps
+                .iter()
+                .flatten()
+                .map(|id| Address::for_application(*id)),
+        );
+
+        let mut distinct = vec![];
+        for account in accounts {
+            if !distinct.contains(&account) {
+                distinct.push(account);
+            }
+        }
+        distinct
+    }
+}
+
 /// An application transaction must indicate the action to be taken following the execution of its approvalProgram or clearStateProgram. The variants below describe the available actions.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ApplicationCallOnComplete {
@@ -388,6 +432,44 @@ pub struct StateSchema {
     pub number_byteslices: u64,
 }
 
+/// A box of an application, which can be accessed by an application call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoxReference {
+    /// Index of the application owning the box: 0 for the called application, otherwise
+    /// the position in `foreign_apps` plus 1.
+    pub app_index: u64,
+
+    /// Name of the box.
+    pub name: Vec<u8>,
+}
+
+impl BoxReference {
+    /// Flat min balance requirement of a box.
+    pub const FLAT_MIN_BALANCE: MicroAlgos = MicroAlgos(2500);
+
+    /// Min balance requirement per byte of the box name and value.
+    pub const BYTE_MIN_BALANCE: MicroAlgos = MicroAlgos(400);
+
+    /// Maximum size of a box value, in bytes.
+    pub const MAX_SIZE: u64 = 32768;
+
+    pub fn new(app_index: u64, name: Vec<u8>) -> Self {
+        BoxReference { app_index, name }
+    }
+
+    /// Increase of the application account's min balance when the box is created with `size` bytes.
+    /// Fails if `size` exceeds [BoxReference::MAX_SIZE].
+    pub fn min_balance(&self, size: u64) -> Result<MicroAlgos, TransactionError> {
+        if size > Self::MAX_SIZE {
+            return Err(TransactionError::InvalidBoxSize {
+                size,
+                max: Self::MAX_SIZE,
+            });
+        }
+        Ok(Self::FLAT_MIN_BALANCE + Self::BYTE_MIN_BALANCE * (self.name.len() as u64 + size))
+    }
+}
+
 /// Wraps a transaction in a signature. The encoding of this struct is suitable to be broadcast
 /// on the network
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -395,6 +477,88 @@ pub struct SignedTransaction {
     pub transaction: Transaction,
     pub transaction_id: String,
     pub sig: TransactionSignature,
+    /// The address which signed the transaction, if it's not the sender (rekeyed sender).
+    pub auth_address: Option<Address>,
+}
+
+impl SignedTransaction {
+    /// Transactions with a single signature verified with one ed25519 batch verification.
+    const VERIFY_BATCH_SIZE: usize = 1024;
+
+    /// Verifies the signature against the sender address, or the auth address of a rekeyed sender.
+    pub fn verify(&self) -> Result<bool, TransactionError> {
+        let message = self.transaction.bytes_to_sign()?;
+        let sender = self.signer();
+        Ok(match &self.sig {
+            TransactionSignature::Single(sig) => sender.as_public_key().verify(&message, sig),
+            TransactionSignature::Multi(msig) => msig.address() == sender && msig.verify(&message),
+            TransactionSignature::Logic(lsig) => lsig.verify(sender),
+        })
+    }
+
+    /// Verifies the signatures of a batch of transactions, returning true only if all of them are valid.
+    /// Single signatures are checked using ed25519 batch verification, the others with [SignedTransaction::verify].
+    /// With the `parallel` feature the work is distributed over multiple threads.
+    pub fn verify_batch(txns: &[SignedTransaction]) -> Result<bool, TransactionError> {
+        let (single, other): (Vec<&SignedTransaction>, Vec<&SignedTransaction>) = txns
+            .iter()
+            .partition(|t| matches!(t.sig, TransactionSignature::Single(_)));
+        let single_batches: Vec<&[&SignedTransaction]> =
+            single.chunks(Self::VERIFY_BATCH_SIZE).collect();
+
+        Ok(
+            all(&single_batches, |batch| verify_single_sigs(batch))?
+                && all(&other, |t| t.verify())?,
+        )
+    }
+
+    /// The address expected to sign: the auth address if set, otherwise the sender.
+    pub fn signer(&self) -> Address {
+        self.auth_address
+            .unwrap_or_else(|| self.transaction.sender())
+    }
+}
+
+fn verify_single_sigs(txns: &[&SignedTransaction]) -> Result<bool, TransactionError> {
+    let mut messages = Vec::with_capacity(txns.len());
+    let mut signatures = Vec::with_capacity(txns.len());
+    let mut public_keys = Vec::with_capacity(txns.len());
+    for t in txns {
+        if let TransactionSignature::Single(sig) = t.sig {
+            messages.push(t.transaction.bytes_to_sign()?);
+            signatures.push(sig);
+            public_keys.push(t.signer().as_public_key());
+        }
+    }
+    let messages: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+    Ok(algonaut_crypto::verify_batch(
+        &messages,
+        &signatures,
+        &public_keys,
+    ))
+}
+
+/// Returns true if `f` returns true for all the items, in parallel with the `parallel` feature.
+fn all<T, F>(items: &[T], f: F) -> Result<bool, TransactionError>
+where
+    T: Sync,
+    F: Fn(&T) -> Result<bool, TransactionError> + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        let results = items.par_iter().map(f).collect::<Result<Vec<_>, _>>()?;
+        Ok(results.into_iter().all(|valid| valid))
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        for item in items {
+            if !f(item)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -403,3 +567,239 @@ pub enum TransactionSignature {
     Multi(MultisigSignature),
     Logic(SignedLogic),
 }
+
+/// The kind of signature a transaction will be signed with. Used to estimate the encoded size.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignerKind {
+    /// Single ed25519 signature.
+    Single,
+    /// Multisig with `keys` subsigs, `threshold` of which are signed.
+    Multisig { keys: u8, threshold: u8 },
+    /// Contract account logic signature with the given program and arguments.
+    Logic {
+        program: CompiledTealBytes,
+        args: Vec<Vec<u8>>,
+    },
+}
+
+impl SignerKind {
+    fn placeholder_signature(&self) -> TransactionSignature {
+        match self {
+            SignerKind::Single => TransactionSignature::Single(Signature([0; 64])),
+            SignerKind::Multisig { keys, threshold } => {
+                TransactionSignature::Multi(MultisigSignature {
+                    version: MULTISIG_VERSION,
+                    threshold: *threshold,
+                    subsigs: (0..*keys)
+                        .map(|i| MultisigSubsig {
+                            key: Ed25519PublicKey([0; 32]),
+                            sig: if i < *threshold {
+                                Some(Signature([0; 64]))
+                            } else {
+                                None
+                            },
+                        })
+                        .collect(),
+                })
+            }
+            SignerKind::Logic { program, args } => TransactionSignature::Logic(SignedLogic {
+                logic: program.clone(),
+                args: args.clone(),
+                sig: LogicSignature::ContractAccount,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{Account, ContractAccount};
+    use crate::{Pay, TxnBuilder};
+    use algonaut_core::MultisigAddress;
+
+    fn payment(sender: Address) -> Transaction {
+        TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1000),
+            HashDigest([0; 32]),
+            Pay::new(sender, Address([2; 32]), MicroAlgos(1_000_000)).build(),
+        )
+        .build()
+    }
+
+    #[test]
+    fn test_estimate_size_single() {
+        let account = Account::generate();
+        let t = payment(account.address());
+        let signed_t = account.sign_transaction(&t).unwrap();
+
+        assert_eq!(
+            signed_t.to_msg_pack().unwrap().len() as u64,
+            t.estimate_size(&SignerKind::Single).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_estimate_size_multisig() {
+        let accounts = [
+            Account::generate(),
+            Account::generate(),
+            Account::generate(),
+        ];
+        let addresses: Vec<Address> = accounts.iter().map(|a| a.address()).collect();
+        let ma = MultisigAddress::new(1, 2, &addresses).unwrap();
+        let t = payment(ma.address());
+        let msig = accounts[0].init_transaction_msig(&t, &ma).unwrap();
+        let msig = accounts[1].append_to_transaction_msig(&t, msig).unwrap();
+        let signed_t = SignedTransaction {
+            transaction: t.clone(),
+            transaction_id: t.id().unwrap(),
+            sig: TransactionSignature::Multi(msig),
+            auth_address: None,
+        };
+
+        assert_eq!(
+            signed_t.to_msg_pack().unwrap().len() as u64,
+            t.estimate_size(&SignerKind::Multisig {
+                keys: 3,
+                threshold: 2
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_estimate_size_logic() {
+        let program = CompiledTealBytes(vec![
+            0x01, 0x20, 0x01, 0x01, 0x22, // int 1
+        ]);
+        let args = vec![vec![1, 2, 3]];
+        let contract_account = ContractAccount {
+            address: Address([1; 32]),
+            program: program.clone(),
+        };
+        let t = payment(contract_account.address);
+        let signed_t = contract_account.sign(&t, args.clone()).unwrap();
+
+        assert_eq!(
+            signed_t.to_msg_pack().unwrap().len() as u64,
+            t.estimate_size(&SignerKind::Logic { program, args })
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let account = Account::generate();
+        let txns: Vec<Transaction> = (0..10).map(|_| payment(account.address())).collect();
+        let mut signed_txns = account.sign_transactions(&txns).unwrap();
+
+        let program = CompiledTealBytes(vec![
+            0x01, 0x20, 0x01, 0x01, 0x22, // int 1
+        ]);
+        let contract_account = ContractAccount {
+            address: Address(sha2::Sha512Trunc256::digest(&program.bytes_to_sign()).into()),
+            program,
+        };
+        signed_txns.push(
+            contract_account
+                .sign(&payment(contract_account.address), vec![])
+                .unwrap(),
+        );
+
+        assert_eq!(11, signed_txns.len());
+        assert!(signed_txns.iter().all(|t| t.verify().unwrap()));
+        assert!(SignedTransaction::verify_batch(&signed_txns).unwrap());
+
+        signed_txns[3].transaction.fee = MicroAlgos(2000);
+        assert!(!signed_txns[3].verify().unwrap());
+        assert!(!SignedTransaction::verify_batch(&signed_txns).unwrap());
+    }
+
+    #[test]
+    fn test_verify_delegated_multisig() {
+        let program = CompiledTealBytes(vec![
+            0x01, 0x20, 0x01, 0x01, 0x22, // int 1
+        ]);
+        let accounts = [Account::generate(), Account::generate()];
+        let addresses: Vec<Address> = accounts.iter().map(|a| a.address()).collect();
+        let ma = MultisigAddress::new(1, 2, &addresses).unwrap();
+        let msig = accounts[0].init_logic_msig(&program, &ma).unwrap();
+        let msig = accounts[1].append_to_logic_msig(&program, msig).unwrap();
+        let signed = |sender: Address| {
+            let t = payment(sender);
+            SignedTransaction {
+                transaction_id: t.id().unwrap(),
+                transaction: t,
+                sig: TransactionSignature::Logic(SignedLogic {
+                    logic: program.clone(),
+                    args: vec![],
+                    sig: LogicSignature::DelegatedMultiSig(msig.clone()),
+                }),
+                auth_address: None,
+            }
+        };
+
+        assert!(signed(ma.address()).verify().unwrap());
+        // Delegated by another multisig
+        let other = signed(Address([7; 32]));
+        assert!(!other.verify().unwrap());
+        assert!(!SignedTransaction::verify_batch(&[other]).unwrap());
+    }
+
+    #[test]
+    fn test_fee_per_byte() {
+        let t = payment(Address([1; 32]));
+        let size = t.estimate_size(&SignerKind::Single).unwrap();
+        let t = t
+            .fee_per_byte(MicroAlgos(100), &SignerKind::Single)
+            .unwrap();
+
+        assert_eq!(MicroAlgos(100) * size, t.fee);
+    }
+
+    #[test]
+    fn test_box_min_balance() {
+        let box_ref = BoxReference::new(0, b"name".to_vec());
+        assert_eq!(
+            MicroAlgos(2500 + 400 * (4 + 100)),
+            box_ref.min_balance(100).unwrap()
+        );
+        assert_eq!(
+            MicroAlgos(2500),
+            BoxReference::new(1, vec![]).min_balance(0).unwrap()
+        );
+        assert_eq!(
+            MicroAlgos(2500 + 400 * (4 + 32768)),
+            box_ref.min_balance(BoxReference::MAX_SIZE).unwrap()
+        );
+        assert!(box_ref.min_balance(BoxReference::MAX_SIZE + 1).is_err());
+        assert!(box_ref.min_balance(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_accessible_accounts() {
+        let sender = Address([1; 32]);
+        let t = crate::builder::CallApplication::new(sender, 5)
+            .accounts(vec![Address([2; 32]), sender])
+            .foreign_apps(vec![6, 5])
+            .build();
+        let call = match t {
+            TransactionType::ApplicationCallTransaction(call) => call,
+            _ => panic!("Expected an application call"),
+        };
+
+        assert_eq!(Some(Address::for_application(5)), call.app_address());
+        assert_eq!(
+            vec![
+                sender,
+                Address([2; 32]),
+                Address::for_application(5),
+                Address::for_application(6)
+            ],
+            call.accessible_accounts()
+        );
+    }
+}
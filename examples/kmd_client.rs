@@ -16,7 +16,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
             "testwallet",
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await?;
     let wallet_id = create_wallet_response.wallet.id;
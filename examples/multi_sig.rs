@@ -41,6 +41,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
         transaction: t,
         transaction_id: "".to_owned(),
         sig,
+        auth_address: None,
     };
 
     let send_response = algod.broadcast_signed_transaction(&signed_t).await;
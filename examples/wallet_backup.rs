@@ -31,7 +31,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let mdk = export_response.master_derivation_key;
 
     // String representation of the mdk, keep in safe place and don't share it
-    let string_to_save = mnemonic::from_key(&mdk.0)?;
+    let string_to_save = mnemonic::from_key(mdk.expose_secret())?;
 
     println!("Backup phrase: {}", string_to_save);
 
@@ -0,0 +1,18 @@
+use algonaut::crypto::mnemonic;
+use algonaut::crypto::MasterDerivationKey;
+use algonaut_transaction::account::Account;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // the backup phrase of the wallet's master derivation key (see wallet_backup example)
+    let backup_phrase = "fire enlist diesel stamp nuclear chunk student stumble call snow flock brush example slab guide choice option recall south kangaroo hundred matrix school above zero";
+    let mdk = MasterDerivationKey::from_secret(mnemonic::to_key(backup_phrase)?);
+
+    // the accounts kmd generates for this wallet, in order, derived without kmd
+    for index in 0..5 {
+        let account = Account::from_master_derivation_key(&mdk, index);
+        println!("{}: {}", index, account.address());
+    }
+
+    Ok(())
+}
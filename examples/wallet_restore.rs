@@ -13,8 +13,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let kmd = Kmd::new(&env::var("KMD_URL")?, &env::var("KMD_TOKEN")?)?;
 
     let backup_phrase = "fire enlist diesel stamp nuclear chunk student stumble call snow flock brush example slab guide choice option recall south kangaroo hundred matrix school above zero";
-    let key_bytes = mnemonic::to_key(backup_phrase)?;
-    let mdk = MasterDerivationKey(key_bytes);
+    let mdk = MasterDerivationKey::from_secret(mnemonic::to_key(backup_phrase)?);
 
     let create_wallet_response = kmd
         .create_wallet("testwallet", "testpassword", "sqlite", mdk)
@@ -1,9 +1,11 @@
 use algonaut_client::{algod::v2::Client, token::ApiToken, Headers};
 use algonaut_core::{Address, Round, SuggestedTransactionParams, ToMsgPack};
 use algonaut_model::algod::v2::{
-    Account, Application, Asset, Block, Catchup, CompiledTeal, DryrunRequest, DryrunResponse,
-    GenesisBlock, KeyRegistration, NodeStatus, PendingTransaction, PendingTransactions, Supply,
-    TransactionParams, TransactionResponse, Version,
+    Account, AccountApplicationInformation, AccountAssetInformation, Application, ApplicationBox,
+    Asset, Block, BlockHash, BoxesResponse, Catchup, CompiledTeal, DisassembledTeal, DryrunRequest,
+    DryrunResponse, GenesisBlock, HashType, KeyRegistration, LightBlockHeaderProof, NodeStatus,
+    ParticipationKey, ParticipationKeyId, PendingTransaction, PendingTransactions, Supply,
+    SyncRound, TransactionParams, TransactionProof, TransactionResponse, Version,
 };
 use algonaut_transaction::SignedTransaction;
 
@@ -60,6 +62,36 @@ impl Algod {
             .await?)
     }
 
+    /// Get an account's information about an asset.
+    ///
+    /// Given an account address and an asset id, returns the account's holding of the asset
+    /// and, if the account created the asset, the asset's parameters.
+    pub async fn account_asset_information(
+        &self,
+        address: &Address,
+        asset_id: u64,
+    ) -> Result<AccountAssetInformation, AlgonautError> {
+        Ok(self
+            .client
+            .account_asset_information(&address.to_string(), asset_id)
+            .await?)
+    }
+
+    /// Get an account's information about an application.
+    ///
+    /// Given an account address and an application id, returns the account's local state of the application
+    /// and, if the account created the application, the application's parameters.
+    pub async fn account_application_information(
+        &self,
+        address: &Address,
+        application_id: u64,
+    ) -> Result<AccountApplicationInformation, AlgonautError> {
+        Ok(self
+            .client
+            .account_application_information(&address.to_string(), application_id)
+            .await?)
+    }
+
     /// Get a list of unconfirmed transactions currently in the transaction pool by address.
     /// Description: Get the list of pending transactions by address, sorted by priority,
     /// in decreasing order, truncated at the end at MAX. If MAX = 0, returns all pending transactions.
@@ -82,6 +114,26 @@ impl Algod {
         Ok(self.client.application_information(id).await?)
     }
 
+    /// Get a box of an application by its name.
+    pub async fn application_box(
+        &self,
+        id: u64,
+        name: &[u8],
+    ) -> Result<ApplicationBox, AlgonautError> {
+        Ok(self.client.application_box(id, name).await?)
+    }
+
+    /// Get the box names of an application.
+    ///
+    /// Returns at most MAX box names. If MAX = 0, returns all box names.
+    pub async fn application_boxes(
+        &self,
+        id: u64,
+        max: u64,
+    ) -> Result<BoxesResponse, AlgonautError> {
+        Ok(self.client.application_boxes(id, max).await?)
+    }
+
     /// Get asset information.
     ///
     /// Given a asset id, it returns asset information including creator, name,
@@ -95,6 +147,33 @@ impl Algod {
         Ok(self.client.block(round).await?)
     }
 
+    /// Get the hash of the block for the given round.
+    pub async fn block_hash(&self, round: Round) -> Result<BlockHash, AlgonautError> {
+        Ok(self.client.block_hash(round).await?)
+    }
+
+    /// Get a proof of the membership of the transaction `txid` in the block for the given round.
+    pub async fn transaction_proof(
+        &self,
+        round: Round,
+        txid: &str,
+        hash_type: HashType,
+    ) -> Result<TransactionProof, AlgonautError> {
+        Ok(self
+            .client
+            .transaction_proof(round, txid, hash_type)
+            .await?)
+    }
+
+    /// Get a proof of the light block header of the given round, in the state proof interval's
+    /// commitment to light block headers.
+    pub async fn light_block_header_proof(
+        &self,
+        round: Round,
+    ) -> Result<LightBlockHeaderProof, AlgonautError> {
+        Ok(self.client.light_block_header_proof(round).await?)
+    }
+
     /// Starts a catchpoint catchup.
     pub async fn start_catchup(&self, catchpoint: &str) -> Result<Catchup, AlgonautError> {
         Ok(self.client.start_catchup(catchpoint).await?)
@@ -110,6 +189,22 @@ impl Algod {
         Ok(self.client.ledger_supply().await?)
     }
 
+    /// Get the minimum sync round of the ledger.
+    /// The node keeps the blocks from this round on, for follower nodes.
+    pub async fn sync_round(&self) -> Result<SyncRound, AlgonautError> {
+        Ok(self.client.sync_round().await?)
+    }
+
+    /// Set the minimum sync round of the ledger.
+    pub async fn set_sync_round(&self, round: Round) -> Result<(), AlgonautError> {
+        Ok(self.client.set_sync_round(round).await?)
+    }
+
+    /// Unset the minimum sync round of the ledger.
+    pub async fn unset_sync_round(&self) -> Result<(), AlgonautError> {
+        Ok(self.client.unset_sync_round().await?)
+    }
+
     /// Generate (or renew) and register participation keys on the node for a given account address.
     ///
     /// address: The account-id to update, or all to update all accounts.
@@ -129,6 +224,40 @@ impl Algod {
             .await?)
     }
 
+    /// Lists the participation keys installed on the node.
+    pub async fn participation_keys(&self) -> Result<Vec<ParticipationKey>, AlgonautError> {
+        Ok(self.client.participation_keys().await?)
+    }
+
+    /// Installs a participation key on the node, returning its participation id.
+    /// `key_file` is the content of a participation key file created by algod's tooling (`goal account addpartkey`
+    /// or `algokey part generate`).
+    pub async fn add_participation_key(
+        &self,
+        key_file: &[u8],
+    ) -> Result<ParticipationKeyId, AlgonautError> {
+        Ok(self.client.add_participation_key(key_file).await?)
+    }
+
+    /// Gets the participation key with the participation id `id`.
+    pub async fn participation_key(&self, id: &str) -> Result<ParticipationKey, AlgonautError> {
+        Ok(self.client.participation_key(id).await?)
+    }
+
+    /// Appends the msgpack encoded state proof keys `keys` to the participation key with the participation id `id`.
+    pub async fn append_participation_keys(
+        &self,
+        id: &str,
+        keys: &[u8],
+    ) -> Result<ParticipationKey, AlgonautError> {
+        Ok(self.client.append_participation_keys(id, keys).await?)
+    }
+
+    /// Deletes the participation key with the participation id `id` from the node.
+    pub async fn delete_participation_key(&self, id: &str) -> Result<(), AlgonautError> {
+        Ok(self.client.delete_participation_key(id).await?)
+    }
+
     /// Special management endpoint to shutdown the node. Optionally provide a timeout parameter
     /// to indicate that the node should begin shutting down after a number of seconds.
     pub async fn shutdown(&self, timeout: usize) -> Result<(), AlgonautError> {
@@ -154,6 +283,16 @@ impl Algod {
         Ok(self.client.compile_teal(teal.to_vec()).await?)
     }
 
+    /// Disassemble program bytes into TEAL source code.
+    ///
+    /// This endpoint is only enabled when a node's configuration file sets EnableDeveloperAPI to true.
+    pub async fn disassemble_teal(
+        &self,
+        program: &[u8],
+    ) -> Result<DisassembledTeal, AlgonautError> {
+        Ok(self.client.disassemble_teal(program.to_vec()).await?)
+    }
+
     /// Provide debugging information for a transaction (or group).
     ///
     /// Executes TEAL program(s) in context and returns debugging information about the execution.
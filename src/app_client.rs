@@ -0,0 +1,156 @@
+use crate::algod::v2::Algod;
+use crate::error::AlgonautError;
+use crate::signer::Signer;
+use algonaut_core::{Address, Round};
+use algonaut_model::algod::v2::PendingTransaction;
+use algonaut_transaction::abi::{AbiArgValue, AbiMethod, AbiMethodCall, AbiValue, FromAbiValue};
+use algonaut_transaction::fee::{FeePolicy, GroupFeePlanner};
+use algonaut_transaction::transaction::{ApplicationCallOnComplete, BoxReference};
+use algonaut_transaction::tx_group::TxGroup;
+use algonaut_transaction::TxnBuilder;
+
+/// Generates a typed client for an ARC-4 contract, from its JSON description.
+///
+/// See [algonaut_derive::app_client].
+pub use algonaut_derive::app_client;
+
+/// Max number of rounds to wait for the confirmation of a call.
+const MAX_WAIT_ROUNDS: u64 = 10;
+
+/// Calls the ARC-4 methods of an application, submitting them through [Algod].
+///
+/// Usually wrapped by a client generated with [app_client!], which has a typed method
+/// for each method of the contract.
+pub struct AppClient<'a> {
+    algod: &'a Algod,
+    app_id: u64,
+    sender: Address,
+    signer: &'a (dyn Signer + Sync),
+}
+
+/// Options of the application calls.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallOptions {
+    /// Overrides the default on completion action of the method.
+    pub on_complete: Option<ApplicationCallOnComplete>,
+    /// Number of inner transactions issued by the call, whose fee is paid by the call.
+    pub inner_transactions: u64,
+    pub boxes: Vec<BoxReference>,
+}
+
+/// The confirmed call of a method.
+#[derive(Debug, Clone)]
+pub struct AppCallResult<T> {
+    /// Id of the application call transaction.
+    pub transaction_id: String,
+    /// The return value of the method.
+    pub value: T,
+    /// The confirmed application call, e.g. to inspect its logs or state deltas.
+    pub transaction: PendingTransaction,
+}
+
+impl AppCallResult<AbiValue> {
+    /// Converts the return value to a Rust type.
+    pub fn decode<T: FromAbiValue>(self) -> Result<AppCallResult<T>, AlgonautError> {
+        Ok(AppCallResult {
+            transaction_id: self.transaction_id,
+            value: T::from_abi_value(self.value)?,
+            transaction: self.transaction,
+        })
+    }
+}
+
+impl<'a> AppClient<'a> {
+    /// The signer signs the calls sent by `sender`, and the transaction arguments of the methods.
+    pub fn new(
+        algod: &'a Algod,
+        app_id: u64,
+        sender: Address,
+        signer: &'a (dyn Signer + Sync),
+    ) -> AppClient<'a> {
+        AppClient {
+            algod,
+            app_id,
+            sender,
+            signer,
+        }
+    }
+
+    pub fn app_id(&self) -> u64 {
+        self.app_id
+    }
+
+    pub fn sender(&self) -> Address {
+        self.sender
+    }
+
+    /// Calls the method and waits for its confirmation.
+    ///
+    /// The call is grouped with the transaction arguments and pays the fee of the whole group,
+    /// including the inner transactions set in the options.
+    pub async fn call(
+        &self,
+        method: &AbiMethod,
+        args: Vec<AbiArgValue>,
+        options: &CallOptions,
+    ) -> Result<AppCallResult<AbiValue>, AlgonautError> {
+        let params = self.algod.suggested_transaction_params().await?;
+
+        let mut call = AbiMethodCall::new(self.sender, self.app_id, method.clone(), args);
+        if let Some(on_complete) = &options.on_complete {
+            call = call.on_complete(on_complete.clone());
+        }
+        if !options.boxes.is_empty() {
+            call = call.boxes(options.boxes.clone());
+        }
+        let mut txns = call.transaction_args();
+        txns.push(TxnBuilder::with(params.clone(), call.build()?).build());
+
+        GroupFeePlanner::with(&params, FeePolicy::Payer(txns.len() - 1))
+            .inner_transactions(options.inner_transactions)
+            .assign_fees(txns.iter_mut().collect())?;
+        if txns.len() > 1 {
+            TxGroup::assign_group_id(txns.iter_mut().collect())?;
+        }
+        let transaction_id = txns.last().expect("Contains the call").id()?;
+
+        let signed = self.signer.sign_transactions(&txns).await?;
+        self.algod.broadcast_signed_transactions(&signed).await?;
+
+        let transaction = self.wait_for_confirmation(&transaction_id).await?;
+        let value = method.decode_return(&transaction.logs)?;
+        Ok(AppCallResult {
+            transaction_id,
+            value,
+            transaction,
+        })
+    }
+
+    async fn wait_for_confirmation(
+        &self,
+        transaction_id: &str,
+    ) -> Result<PendingTransaction, AlgonautError> {
+        let mut round = self.algod.status().await?.last_round;
+        for _ in 0..MAX_WAIT_ROUNDS {
+            let pending = self
+                .algod
+                .pending_transaction_with_id(transaction_id)
+                .await?;
+            if pending.confirmed_round.is_some() {
+                return Ok(pending);
+            }
+            if !pending.pool_error.is_empty() {
+                return Err(AlgonautError::Transaction(format!(
+                    "Transaction {} was rejected: {}",
+                    transaction_id, pending.pool_error
+                )));
+            }
+            self.algod.status_after_round(Round(round)).await?;
+            round += 1;
+        }
+        Err(AlgonautError::Transaction(format!(
+            "Transaction {} not confirmed after {} rounds",
+            transaction_id, MAX_WAIT_ROUNDS
+        )))
+    }
+}
@@ -21,6 +21,9 @@ pub enum AlgonautError {
     /// HTTP calls errors
     #[error("http error: {0}")]
     Request(RequestError),
+    /// Transaction building or signing errors.
+    #[error("Transaction error: {0}")]
+    Transaction(String),
     /// Internal errors (please open an [issue](https://github.com/manuelmauro/algonaut/issues)!)
     #[error("Internal error: {0}")]
     Internal(String),
@@ -88,3 +91,9 @@ impl From<rmp_serde::encode::Error> for AlgonautError {
         AlgonautError::Internal(error.to_string())
     }
 }
+
+impl From<algonaut_transaction::error::TransactionError> for AlgonautError {
+    fn from(error: algonaut_transaction::error::TransactionError) -> Self {
+        AlgonautError::Transaction(error.to_string())
+    }
+}
@@ -1,11 +1,14 @@
 use algonaut_client::{indexer::v2::Client, Headers};
 use algonaut_core::{Address, Round};
 use algonaut_model::indexer::v2::{
-    AccountInfoResponse, AccountResponse, AccountTransactionResponse, ApplicationInfoResponse,
-    ApplicationResponse, AssetResponse, AssetTransactionResponse, AssetsInfoResponse,
-    BalancesResponse, Block, QueryAccount, QueryAccountInfo, QueryAccountTransaction,
-    QueryApplicationInfo, QueryApplications, QueryAssetTransaction, QueryAssets, QueryAssetsInfo,
-    QueryBalances, QueryTransaction, TransactionResponse,
+    AccountInfoResponse, AccountResponse, AccountTransactionResponse, ApplicationBox,
+    ApplicationInfoResponse, ApplicationLocalStatesResponse, ApplicationLogsResponse,
+    ApplicationResponse, AssetHoldingsResponse, AssetResponse, AssetTransactionResponse,
+    AssetsInfoResponse, BalancesResponse, Block, BlockHeadersResponse, BoxesResponse, QueryAccount,
+    QueryAccountApplications, QueryAccountAssets, QueryAccountInfo, QueryAccountTransaction,
+    QueryApplicationBoxes, QueryApplicationInfo, QueryApplicationLogs, QueryApplications,
+    QueryAssetTransaction, QueryAssets, QueryAssetsInfo, QueryBalances, QueryBlockHeaders,
+    QueryTransaction, TransactionResponse,
 };
 
 use crate::error::AlgonautError;
@@ -61,6 +64,45 @@ impl Indexer {
         Ok(self.client.account_transactions(address, query).await?)
     }
 
+    /// Lookup assets created by an account.
+    pub async fn account_created_assets(
+        &self,
+        address: &Address,
+        query: &QueryAccountAssets,
+    ) -> Result<AssetResponse, AlgonautError> {
+        Ok(self.client.account_created_assets(address, query).await?)
+    }
+
+    /// Lookup applications created by an account.
+    pub async fn account_created_applications(
+        &self,
+        address: &Address,
+        query: &QueryAccountApplications,
+    ) -> Result<ApplicationResponse, AlgonautError> {
+        Ok(self
+            .client
+            .account_created_applications(address, query)
+            .await?)
+    }
+
+    /// Lookup the asset holdings of an account.
+    pub async fn account_assets(
+        &self,
+        address: &Address,
+        query: &QueryAccountAssets,
+    ) -> Result<AssetHoldingsResponse, AlgonautError> {
+        Ok(self.client.account_assets(address, query).await?)
+    }
+
+    /// Lookup the application local states of an account.
+    pub async fn account_apps_local_state(
+        &self,
+        address: &Address,
+        query: &QueryAccountApplications,
+    ) -> Result<ApplicationLocalStatesResponse, AlgonautError> {
+        Ok(self.client.account_apps_local_state(address, query).await?)
+    }
+
     /// Search for applications
     pub async fn applications(
         &self,
@@ -78,6 +120,33 @@ impl Indexer {
         Ok(self.client.application_info(id, query).await?)
     }
 
+    /// Lookup the logs of an application.
+    pub async fn application_logs(
+        &self,
+        id: u64,
+        query: &QueryApplicationLogs,
+    ) -> Result<ApplicationLogsResponse, AlgonautError> {
+        Ok(self.client.application_logs(id, query).await?)
+    }
+
+    /// Lookup the box names of an application.
+    pub async fn application_boxes(
+        &self,
+        id: u64,
+        query: &QueryApplicationBoxes,
+    ) -> Result<BoxesResponse, AlgonautError> {
+        Ok(self.client.application_boxes(id, query).await?)
+    }
+
+    /// Lookup a box of an application by its name.
+    pub async fn application_box(
+        &self,
+        id: u64,
+        name: &[u8],
+    ) -> Result<ApplicationBox, AlgonautError> {
+        Ok(self.client.application_box(id, name).await?)
+    }
+
     /// Search for assets.
     pub async fn assets(&self, query: &QueryAssets) -> Result<AssetResponse, AlgonautError> {
         Ok(self.client.assets(query).await?)
@@ -115,6 +184,14 @@ impl Indexer {
         Ok(self.client.block(round).await?)
     }
 
+    /// Search for block headers.
+    pub async fn block_headers(
+        &self,
+        query: &QueryBlockHeaders,
+    ) -> Result<BlockHeadersResponse, AlgonautError> {
+        Ok(self.client.block_headers(query).await?)
+    }
+
     /// Search for transactions.
     pub async fn transactions(
         &self,
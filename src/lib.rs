@@ -23,6 +23,8 @@ pub use algonaut_model as model;
 pub use algonaut_transaction as transaction;
 
 pub mod algod;
+pub mod app_client;
 pub mod error;
 pub mod indexer;
 pub mod kmd;
+pub mod signer;
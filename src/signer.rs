@@ -0,0 +1,423 @@
+use crate::error::AlgonautError;
+use crate::kmd::v1::Kmd;
+use algonaut_core::{MultisigAddress, MultisigSignature, MultisigSubsig, SignedLogic};
+use algonaut_crypto::secret::Secret;
+use algonaut_transaction::account::{Account, ContractAccount};
+use algonaut_transaction::error::TransactionError;
+use algonaut_transaction::transaction::TransactionSignature;
+use algonaut_transaction::{SignedTransaction, Transaction};
+use async_trait::async_trait;
+
+/// Signs transactions.
+///
+/// Implemented for a local [Account], a kmd wallet ([KmdSigner]), a set of signers of a multisig address
+/// ([MultisigSigner]) and logic signatures ([LogicSigner]).
+/// Implement it to plug in other backends, e.g. a remote signing service.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Signer {
+    /// Signs the transactions, returning the signed transactions in the same order.
+    async fn sign_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<Vec<SignedTransaction>, AlgonautError>;
+
+    async fn sign_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<SignedTransaction, AlgonautError> {
+        self.sign_transactions(std::slice::from_ref(transaction))
+            .await?
+            .pop()
+            .ok_or_else(|| AlgonautError::Internal("Signer returned no transaction".to_owned()))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for Account {
+    async fn sign_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<Vec<SignedTransaction>, AlgonautError> {
+        Ok(Account::sign_transactions(self, transactions)?)
+    }
+}
+
+/// Signs with the keys of an unlocked kmd wallet. The transaction sender's key has to be in the wallet.
+#[derive(Debug)]
+pub struct KmdSigner<'a> {
+    kmd: &'a Kmd,
+    wallet_handle: String,
+    wallet_password: Secret<String>,
+}
+
+impl<'a> KmdSigner<'a> {
+    /// `wallet_handle` is the token returned by [Kmd::init_wallet_handle].
+    pub fn new(kmd: &'a Kmd, wallet_handle: &str, wallet_password: &str) -> KmdSigner<'a> {
+        KmdSigner {
+            kmd,
+            wallet_handle: wallet_handle.to_owned(),
+            wallet_password: Secret::new(wallet_password.to_owned()),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for KmdSigner<'_> {
+    async fn sign_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<Vec<SignedTransaction>, AlgonautError> {
+        let mut signed_transactions = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let response = self
+                .kmd
+                .sign_transaction(
+                    &self.wallet_handle,
+                    self.wallet_password.expose_secret(),
+                    transaction,
+                )
+                .await?;
+            signed_transactions.push(
+                rmp_serde::from_slice(&response.signed_transaction)
+                    .map_err(|e| AlgonautError::Internal(e.to_string()))?,
+            );
+        }
+        Ok(signed_transactions)
+    }
+}
+
+/// Signs as a multisig address, collecting the signatures of a set of signers.
+///
+/// Each signer can return single signatures, which are matched with the address' public keys,
+/// or (partial) multisig signatures, which are merged.
+/// Signing fails if the signers don't reach the threshold.
+pub struct MultisigSigner<'a> {
+    address: MultisigAddress,
+    signers: Vec<&'a (dyn Signer + Sync)>,
+}
+
+impl<'a> MultisigSigner<'a> {
+    pub fn new(address: MultisigAddress, signers: Vec<&'a (dyn Signer + Sync)>) -> Self {
+        MultisigSigner { address, signers }
+    }
+
+    fn add_signature(
+        &self,
+        msig: &mut MultisigSignature,
+        transaction: &Transaction,
+        signed: SignedTransaction,
+    ) -> Result<(), AlgonautError> {
+        let bytes = transaction.bytes_to_sign()?;
+        match signed.sig {
+            TransactionSignature::Single(sig) => {
+                let subsig = msig
+                    .subsigs
+                    .iter_mut()
+                    .find(|subsig| subsig.key.verify(&bytes, &sig))
+                    .ok_or(TransactionError::InvalidSecretKeyInMultisig)?;
+                subsig.sig = Some(sig);
+            }
+            TransactionSignature::Multi(other) => {
+                if other.version != msig.version || other.threshold != msig.threshold {
+                    return Err(TransactionError::InvalidSenderInMultisig.into());
+                }
+                if other.subsigs.len() != msig.subsigs.len() {
+                    return Err(TransactionError::InvalidNumberOfSubsignatures.into());
+                }
+                if other
+                    .subsigs
+                    .iter()
+                    .zip(&msig.subsigs)
+                    .any(|(other, subsig)| other.key != subsig.key)
+                {
+                    return Err(TransactionError::InvalidPublicKeyInMultisig.into());
+                }
+                for (subsig, other) in msig.subsigs.iter_mut().zip(other.subsigs) {
+                    if let Some(sig) = other.sig {
+                        if !subsig.key.verify(&bytes, &sig) {
+                            return Err(TransactionError::InvalidSignatureInMultisig.into());
+                        }
+                        subsig.sig = Some(sig);
+                    }
+                }
+            }
+            TransactionSignature::Logic(_) => {
+                return Err(TransactionError::InvalidSecretKeyInMultisig.into())
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for MultisigSigner<'_> {
+    async fn sign_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<Vec<SignedTransaction>, AlgonautError> {
+        if transactions
+            .iter()
+            .any(|t| t.sender() != self.address.address())
+        {
+            return Err(TransactionError::InvalidSenderInMultisig.into());
+        }
+
+        let mut msigs: Vec<MultisigSignature> = transactions
+            .iter()
+            .map(|_| MultisigSignature {
+                version: self.address.version,
+                threshold: self.address.threshold,
+                subsigs: self
+                    .address
+                    .public_keys
+                    .iter()
+                    .map(|key| MultisigSubsig {
+                        key: *key,
+                        sig: None,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        for signer in &self.signers {
+            let signed_transactions = signer.sign_transactions(transactions).await?;
+            for ((msig, transaction), signed) in
+                msigs.iter_mut().zip(transactions).zip(signed_transactions)
+            {
+                self.add_signature(msig, transaction, signed)?;
+            }
+        }
+
+        transactions
+            .iter()
+            .zip(msigs)
+            .map(|(transaction, msig)| {
+                let signatures = msig.subsigs.iter().filter(|s| s.sig.is_some()).count();
+                if signatures < msig.threshold as usize {
+                    return Err(TransactionError::InsufficientMultisigSignatures {
+                        threshold: msig.threshold,
+                        signatures,
+                    }
+                    .into());
+                }
+                Ok(SignedTransaction {
+                    transaction: transaction.clone(),
+                    transaction_id: transaction.id()?,
+                    sig: TransactionSignature::Multi(msig),
+                    auth_address: None,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Signs with a logic signature: a contract account or a delegated (multi) signature of a program.
+#[derive(Debug, Clone)]
+pub struct LogicSigner {
+    logic: SignedLogic,
+}
+
+impl LogicSigner {
+    pub fn new(logic: SignedLogic) -> LogicSigner {
+        LogicSigner { logic }
+    }
+
+    /// Signs as contract account, passing `args` to the program.
+    pub fn contract_account(account: &ContractAccount, args: Vec<Vec<u8>>) -> LogicSigner {
+        LogicSigner {
+            logic: SignedLogic {
+                logic: account.program.clone(),
+                args,
+                sig: algonaut_core::LogicSignature::ContractAccount,
+            },
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for LogicSigner {
+    async fn sign_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<Vec<SignedTransaction>, AlgonautError> {
+        transactions
+            .iter()
+            .map(|transaction| {
+                Ok(SignedTransaction {
+                    transaction: transaction.clone(),
+                    transaction_id: transaction.id()?,
+                    sig: TransactionSignature::Logic(self.logic.clone()),
+                    auth_address: None,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algonaut_core::{CompiledTealBytes, MicroAlgos, Round};
+    use algonaut_crypto::HashDigest;
+    use algonaut_transaction::{Pay, TxnBuilder};
+
+    fn payment(sender: algonaut_core::Address) -> Transaction {
+        TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1000),
+            HashDigest([0; 32]),
+            Pay::new(sender, sender, MicroAlgos(1)).build(),
+        )
+        .build()
+    }
+
+    #[tokio::test]
+    async fn test_account_signer() {
+        let account = Account::generate();
+        let t = payment(account.address());
+
+        let signed = Signer::sign_transaction(&account, &t).await.unwrap();
+
+        assert_eq!(account.sign_transaction(&t).unwrap(), signed);
+        assert!(signed.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_multisig_signer() {
+        let accounts = [
+            Account::generate(),
+            Account::generate(),
+            Account::generate(),
+        ];
+        let address = MultisigAddress::new(
+            1,
+            2,
+            &accounts.iter().map(|a| a.address()).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let t = payment(address.address());
+
+        let signer = MultisigSigner::new(address.clone(), vec![&accounts[0], &accounts[2]]);
+        let signed = signer.sign_transaction(&t).await.unwrap();
+        assert!(signed.verify().unwrap());
+
+        let signer = MultisigSigner::new(address, vec![&accounts[1]]);
+        assert!(signer.sign_transaction(&t).await.is_err());
+    }
+
+    /// Returns a fixed signature, e.g. a partial multisig signature from another party.
+    struct SignatureSigner(TransactionSignature);
+
+    #[async_trait]
+    impl Signer for SignatureSigner {
+        async fn sign_transactions(
+            &self,
+            transactions: &[Transaction],
+        ) -> Result<Vec<SignedTransaction>, AlgonautError> {
+            transactions
+                .iter()
+                .map(|transaction| {
+                    Ok(SignedTransaction {
+                        transaction: transaction.clone(),
+                        transaction_id: transaction.id()?,
+                        sig: self.0.clone(),
+                        auth_address: None,
+                    })
+                })
+                .collect()
+        }
+    }
+
+    /// Signs with `account`, merging the partial signature `msig`.
+    async fn merge_partial(
+        address: MultisigAddress,
+        msig: MultisigSignature,
+        account: &Account,
+        t: Transaction,
+    ) -> Result<SignedTransaction, AlgonautError> {
+        let other = SignatureSigner(TransactionSignature::Multi(msig));
+        MultisigSigner::new(address, vec![&other, account])
+            .sign_transaction(&t)
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_multisig_signer_merges_partial_signatures() {
+        let accounts = [Account::generate(), Account::generate()];
+        let address = MultisigAddress::new(
+            1,
+            2,
+            &accounts.iter().map(|a| a.address()).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let t = payment(address.address());
+        let partial = || accounts[0].init_transaction_msig(&t, &address).unwrap();
+        let merge = |msig| merge_partial(address.clone(), msig, &accounts[1], t.clone());
+
+        let signed = merge(partial()).await.unwrap();
+        assert!(signed.verify().unwrap());
+
+        let mut other_threshold = partial();
+        other_threshold.threshold = 1;
+        assert!(merge(other_threshold).await.is_err());
+
+        let mut other_version = partial();
+        other_version.version = 2;
+        assert!(merge(other_version).await.is_err());
+
+        let mut missing_key = partial();
+        missing_key.subsigs.pop();
+        assert!(merge(missing_key).await.is_err());
+
+        let mut other_key = partial();
+        other_key.subsigs[1].key = Account::generate().address().as_public_key();
+        assert!(merge(other_key).await.is_err());
+
+        let mut invalid_sig = partial();
+        invalid_sig.subsigs[0].sig = Some(accounts[0].generate_sig(b"other"));
+        assert!(merge(invalid_sig).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_multisig_signer_rejects_other_sender() {
+        let accounts = [Account::generate(), Account::generate()];
+        let address = MultisigAddress::new(
+            1,
+            1,
+            &accounts.iter().map(|a| a.address()).collect::<Vec<_>>(),
+        )
+        .unwrap();
+
+        let signer = MultisigSigner::new(address, vec![&accounts[0]]);
+        assert!(signer
+            .sign_transaction(&payment(accounts[0].address()))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_contract_account_signer() {
+        let program = CompiledTealBytes(vec![1, 32, 1, 1, 34]);
+        let contract = ContractAccount {
+            address: SignedLogic {
+                logic: program.clone(),
+                args: vec![],
+                sig: algonaut_core::LogicSignature::ContractAccount,
+            }
+            .as_address(),
+            program,
+        };
+        let t = payment(contract.address);
+
+        let signer = LogicSigner::contract_account(&contract, vec![]);
+        let signed = signer.sign_transaction(&t).await.unwrap();
+
+        assert!(signed.verify().unwrap());
+    }
+}
@@ -4,7 +4,7 @@ use crate::step_defs::util::{
 };
 use algonaut::{algod::v2::Algod, kmd::v1::Kmd};
 use algonaut_core::{Address, CompiledTealBytes, MicroAlgos};
-use algonaut_model::algod::v2::{Application, ApplicationLocalState};
+use algonaut_model::algod::v2::{Application, ApplicationLocalState, TealValue};
 use algonaut_transaction::account::Account;
 use algonaut_transaction::builder::{
     CallApplication, ClearApplication, CloseApplication, DeleteApplication, OptInApplication,
@@ -401,23 +401,26 @@ async fn the_transient_account_should_have(
     }
 
     let mut key_value_found = false;
-    for key_value in key_values.iter().filter(|kv| kv.key == key) {
-        if key_value.value.value_type == 1 {
-            let value_bytes = BASE64.decode(value.as_bytes())?;
-            if key_value.value.bytes != value_bytes {
-                Err(format!(
-                    "Value mismatch (bytes): expected: '{:?}', got '{:?}'",
-                    value_bytes, key_value.value.bytes
-                ))?
+    for key_value in key_values.iter().filter(|kv| BASE64.encode(&kv.key) == key) {
+        match &key_value.value {
+            TealValue::Bytes(bytes) => {
+                let value_bytes = BASE64.decode(value.as_bytes())?;
+                if *bytes != value_bytes {
+                    Err(format!(
+                        "Value mismatch (bytes): expected: '{:?}', got '{:?}'",
+                        value_bytes, bytes
+                    ))?
+                }
             }
-        } else if key_value.value.value_type == 0 {
-            let int_value = value.parse::<u64>()?;
-
-            if key_value.value.uint != int_value {
-                Err(format!(
-                    "Value mismatch (uint): expected: '{}', got '{}'",
-                    value, key_value.value.uint
-                ))?
+            TealValue::Uint(uint) => {
+                let int_value = value.parse::<u64>()?;
+
+                if *uint != int_value {
+                    Err(format!(
+                        "Value mismatch (uint): expected: '{}', got '{}'",
+                        value, uint
+                    ))?
+                }
             }
         }
         key_value_found = true;
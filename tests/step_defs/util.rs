@@ -71,5 +71,5 @@ pub fn parse_app_args(args_str: String) -> Result<Vec<Vec<u8>>, Box<dyn Error>>
 }
 
 pub fn account_from_kmd_response(key_res: &ExportKeyResponse) -> Result<Account, Box<dyn Error>> {
-    Ok(Account::from_seed(key_res.private_key[0..32].try_into()?))
+    Ok(Account::from_seed(&key_res.private_key[0..32].try_into()?))
 }
@@ -104,6 +104,38 @@ async fn test_application_information_endpoint() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+#[ignore]
+async fn test_application_box_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let algod = Algod::new(&env::var("ALGOD_URL")?, &env::var("ALGOD_TOKEN")?)?;
+
+    let res = algod.application_box(123, b"name").await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+async fn test_application_boxes_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let algod = Algod::new(&env::var("ALGOD_URL")?, &env::var("ALGOD_TOKEN")?)?;
+
+    let res = algod.application_boxes(123, 0).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
 #[test]
 #[ignore]
 async fn test_asset_information_endpoint() -> Result<(), Box<dyn Error>> {
@@ -137,6 +169,23 @@ async fn test_block_endpoint() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+#[ignore]
+async fn test_block_hash_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let algod = Algod::new(&env::var("ALGOD_URL")?, &env::var("ALGOD_TOKEN")?)?;
+
+    let last_round = algod.status().await?.last_round;
+    let res = algod.block_hash(Round(last_round)).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
 #[test]
 #[ignore]
 async fn test_start_catchup_endpoint() -> Result<(), Box<dyn Error>> {
@@ -213,6 +262,26 @@ async fn test_register_participation_keys_endpoint() -> Result<(), Box<dyn Error
     Ok(())
 }
 
+#[test]
+#[ignore]
+async fn test_participation_keys_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let algod = Algod::new(&env::var("ALGOD_URL")?, &env::var("ALGOD_TOKEN")?)?;
+
+    let res = algod.participation_keys().await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    for key in res? {
+        assert!(algod.participation_key(&key.id).await.is_ok());
+    }
+
+    Ok(())
+}
+
 #[test]
 #[ignore]
 async fn test_shutdown_endpoint() -> Result<(), Box<dyn Error>> {
@@ -304,6 +373,24 @@ async fn test_failure_compiling_teal() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+async fn test_disassemble_teal_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let algod = Algod::new(&env::var("ALGOD_URL")?, &env::var("ALGOD_TOKEN")?)?;
+
+    let compiled = algod
+        .compile_teal("#pragma version 2\nint 1".as_bytes())
+        .await?;
+    let res = algod.disassemble_teal(&compiled.program.0).await;
+
+    println!("{:#?}", res);
+    assert!(res?.result.contains("int 1"));
+
+    Ok(())
+}
+
 #[test]
 #[ignore = "TODO"]
 async fn test_dryrun_teal_endpoint() -> Result<(), Box<dyn Error>> {
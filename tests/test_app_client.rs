@@ -0,0 +1,73 @@
+use algonaut::algod::v2::Algod;
+use algonaut::app_client::app_client;
+use algonaut_transaction::abi::EventRegistry;
+use algonaut_transaction::account::Account;
+use algonaut_transaction::transaction::ApplicationCallOnComplete;
+use dotenv::dotenv;
+use std::env;
+use std::error::Error;
+use tokio::test;
+
+app_client!(Calculator, "tests/contracts/calculator.json");
+
+#[test]
+async fn test_generated_client_contract() -> Result<(), Box<dyn Error>> {
+    let algod = Algod::new("http://localhost:4001", &"a".repeat(64))?;
+    let account = Account::generate();
+    let calculator = Calculator::new(&algod, 1, account.address(), &account)?;
+
+    let contract = calculator.contract();
+    assert_eq!("Calculator", contract.name);
+    assert_eq!(5, contract.methods.len());
+    assert_eq!(
+        "deposit(pay,asset,byte[32])void",
+        contract.method("deposit")?.signature()
+    );
+    assert_eq!(
+        ApplicationCallOnComplete::OptIn,
+        contract.method("optIn")?.on_complete()?
+    );
+    assert_eq!(1, calculator.client().app_id());
+
+    let registry = EventRegistry::from_contract(contract)?;
+    let added = &contract.events[0];
+    assert_eq!("Added(uint64,uint64)", added.signature());
+    assert_eq!(Some(added), registry.event(&added.selector()));
+
+    Ok(())
+}
+
+// Preconditions: deploy the calculator contract and fund the sender account
+#[test]
+#[ignore]
+async fn test_generated_client_call() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let algod = Algod::new(&env::var("ALGOD_URL")?, &env::var("ALGOD_TOKEN")?)?;
+    let sender = Account::from_mnemonic(&env::var("ACCOUNT_MNEMONIC")?)?;
+    let app_id = env::var("CALCULATOR_APP_ID")?.parse()?;
+
+    let calculator = Calculator::new(&algod, app_id, sender.address(), &sender)?;
+
+    let res = calculator.add(1, 2).await?;
+    println!("{:#?}", res);
+    assert_eq!(3, res.value);
+
+    let registry = EventRegistry::from_contract(calculator.contract())?;
+    let events = registry.decode_pending_transaction(&res.transaction).events;
+    assert_eq!(1, events.len());
+    assert_eq!(app_id, events[0].app_id);
+    assert_eq!((1, 2), events[0].event.decode::<(u64, u64)>()?);
+
+    let (sum, _) = calculator.sum_array(vec![1, 2, 3]).await?.value;
+    assert_eq!(6, sum);
+
+    let greeting = calculator
+        .greet(sender.address(), "Hello".to_owned())
+        .await?
+        .value;
+    println!("{}", greeting);
+
+    Ok(())
+}
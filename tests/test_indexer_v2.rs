@@ -1,9 +1,10 @@
 use algonaut::indexer::v2::Indexer;
 use algonaut_core::Round;
 use algonaut_model::indexer::v2::{
-    QueryAccount, QueryAccountInfo, QueryAccountTransaction, QueryApplicationInfo,
+    QueryAccount, QueryAccountApplications, QueryAccountAssets, QueryAccountInfo,
+    QueryAccountTransaction, QueryApplicationBoxes, QueryApplicationInfo, QueryApplicationLogs,
     QueryApplications, QueryAssetTransaction, QueryAssets, QueryAssetsInfo, QueryBalances,
-    QueryTransaction, Role,
+    QueryBlockHeaders, QueryTransaction, Role,
 };
 use dotenv::dotenv;
 use std::env;
@@ -108,6 +109,102 @@ async fn test_account_transactions_endpoint() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+async fn test_account_created_assets_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let indexer = Indexer::new(&env::var("INDEXER_URL")?)?;
+
+    let address = env::var("ACCOUNT")?.parse()?;
+
+    let query = QueryAccountAssets {
+        asset_id: None,
+        include_all: None,
+        limit: Some(2),
+        next: None,
+    };
+
+    let res = indexer.account_created_assets(&address, &query).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[test]
+async fn test_account_created_applications_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let indexer = Indexer::new(&env::var("INDEXER_URL")?)?;
+
+    let address = env::var("ACCOUNT")?.parse()?;
+
+    let query = QueryAccountApplications {
+        application_id: None,
+        include_all: None,
+        limit: Some(2),
+        next: None,
+    };
+
+    let res = indexer.account_created_applications(&address, &query).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[test]
+async fn test_account_assets_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let indexer = Indexer::new(&env::var("INDEXER_URL")?)?;
+
+    let address = env::var("ACCOUNT")?.parse()?;
+
+    let query = QueryAccountAssets {
+        asset_id: None,
+        include_all: None,
+        limit: Some(2),
+        next: None,
+    };
+
+    let res = indexer.account_assets(&address, &query).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[test]
+async fn test_account_apps_local_state_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let indexer = Indexer::new(&env::var("INDEXER_URL")?)?;
+
+    let address = env::var("ACCOUNT")?.parse()?;
+
+    let query = QueryAccountApplications {
+        application_id: None,
+        include_all: None,
+        limit: Some(2),
+        next: None,
+    };
+
+    let res = indexer.account_apps_local_state(&address, &query).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
 #[test]
 async fn test_applications_endpoint() -> Result<(), Box<dyn Error>> {
     // load variables in .env
@@ -147,6 +244,64 @@ async fn test_applications_info_endpoint() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+#[ignore]
+async fn test_application_logs_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let indexer = Indexer::new(&env::var("INDEXER_URL")?)?;
+
+    let query = QueryApplicationLogs {
+        limit: Some(2),
+        ..QueryApplicationLogs::default()
+    };
+
+    let res = indexer.application_logs(123, &query).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+async fn test_application_boxes_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let indexer = Indexer::new(&env::var("INDEXER_URL")?)?;
+
+    let query = QueryApplicationBoxes {
+        limit: Some(2),
+        next: None,
+    };
+
+    let res = indexer.application_boxes(123, &query).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+async fn test_application_box_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let indexer = Indexer::new(&env::var("INDEXER_URL")?)?;
+
+    let res = indexer.application_box(123, b"name").await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
 #[test]
 async fn test_assets_endpoint() -> Result<(), Box<dyn Error>> {
     // load variables in .env
@@ -262,6 +417,26 @@ async fn test_block_endpoint() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+async fn test_block_headers_endpoint() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let indexer = Indexer::new(&env::var("INDEXER_URL")?)?;
+
+    let query = QueryBlockHeaders {
+        limit: Some(2),
+        ..QueryBlockHeaders::default()
+    };
+
+    let res = indexer.block_headers(&query).await;
+
+    println!("{:#?}", res);
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
 #[test]
 #[ignore]
 async fn test_transactions_endpoint() -> Result<(), Box<dyn Error>> {
@@ -1,5 +1,6 @@
 use algonaut::kmd::v1::Kmd;
 use algonaut_crypto::{Ed25519PublicKey, MasterDerivationKey};
+use algonaut_transaction::account::Account;
 use dotenv::dotenv;
 use rand::{distributions::Alphanumeric, Rng};
 use std::env;
@@ -52,7 +53,7 @@ async fn test_create_wallet_and_obtain_handle() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
 
@@ -88,7 +89,7 @@ async fn test_release_wallet_handle_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -130,7 +131,7 @@ async fn test_renew_wallet_handle_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -172,7 +173,7 @@ async fn test_rename_wallet_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -216,7 +217,7 @@ async fn test_get_wallet_info_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -258,7 +259,7 @@ async fn test_export_wallet_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -300,7 +301,7 @@ async fn test_import_export_key() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -357,7 +358,7 @@ async fn test_generate_key_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -381,6 +382,39 @@ async fn test_generate_key_endpoint() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+async fn test_generated_keys_match_offline_derivation() -> Result<(), Box<dyn Error>> {
+    // load variables in .env
+    dotenv().ok();
+
+    let kmd = Kmd::new(&env::var("KMD_URL")?, &env::var("KMD_TOKEN")?)?;
+
+    let wallet_name: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(7)
+        .map(char::from)
+        .collect();
+
+    let mdk = MasterDerivationKey::new(rand::thread_rng().gen());
+    let wallet = kmd
+        .create_wallet(wallet_name.as_ref(), "testpassword", "sqlite", mdk.clone())
+        .await?;
+
+    let handle = kmd
+        .init_wallet_handle(wallet.wallet.id.as_ref(), "testpassword")
+        .await?;
+
+    for index in 0..3 {
+        let res = kmd
+            .generate_key(handle.wallet_handle_token.as_ref())
+            .await?;
+        let account = Account::from_master_derivation_key(&mdk, index);
+        assert_eq!(res.address, account.address().to_string());
+    }
+
+    Ok(())
+}
+
 #[test]
 async fn test_delete_key_endpoint() -> Result<(), Box<dyn Error>> {
     // load variables in .env
@@ -399,7 +433,7 @@ async fn test_delete_key_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -454,7 +488,7 @@ async fn test_list_keys_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -501,7 +535,7 @@ async fn test_list_keys_of_empty_wallet() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -543,7 +577,7 @@ async fn test_list_multisig_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -589,7 +623,7 @@ async fn test_import_export_multisig() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);
@@ -652,7 +686,7 @@ async fn test_delete_multisig_endpoint() -> Result<(), Box<dyn Error>> {
             wallet_name.as_ref(),
             "testpassword",
             "sqlite",
-            MasterDerivationKey([0; 32]),
+            MasterDerivationKey::new([0; 32]),
         )
         .await;
     println!("{:#?}", wallet);